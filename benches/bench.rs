@@ -1,5 +1,9 @@
 #![feature(test)]
-#![allow(clippy::struct_excessive_bools, clippy::struct_field_names)]
+#![allow(
+    clippy::struct_excessive_bools,
+    clippy::struct_field_names,
+    clippy::use_self
+)]
 
 extern crate test;
 
@@ -24,6 +28,33 @@ fn bench_deserialize_miniserde(b: &mut Bencher) {
     });
 }
 
+#[bench]
+fn bench_deserialize_miniserde_u32_array(b: &mut Bencher) {
+    let v: Vec<u32> = (0..100_000).map(|i| i * 7919).collect();
+    let j = miniserde::json::to_string(&v);
+    b.iter(|| {
+        miniserde::json::from_str::<Vec<u32>>(&j).unwrap();
+    });
+}
+
+#[bench]
+fn bench_from_value_miniserde_u32_array(b: &mut Bencher) {
+    let v: Vec<u32> = (0..100_000).map(|i| i * 7919).collect();
+    let value = miniserde::json::to_value(&v);
+    b.iter(|| {
+        miniserde::json::from_value::<Vec<u32>>(value.clone()).unwrap();
+    });
+}
+
+#[bench]
+fn bench_deserialize_miniserde_value_short_keys(b: &mut Bencher) {
+    let entries: Vec<String> = (0..5000).map(|i| format!(r#""{i:03x}":{i}"#)).collect();
+    let j = format!("{{{}}}", entries.join(","));
+    b.iter(|| {
+        miniserde::json::from_str::<miniserde::json::Value>(&j).unwrap();
+    });
+}
+
 #[bench]
 fn bench_deserialize_serdejson(b: &mut Bencher) {
     let j = input_json();
@@ -32,6 +63,77 @@ fn bench_deserialize_serdejson(b: &mut Bencher) {
     });
 }
 
+#[bench]
+fn bench_deserialize_miniserde_small_documents(b: &mut Bencher) {
+    let docs: Vec<String> = (0..1000)
+        .map(|i| format!(r#"{{"id":{i},"text":"line\n{i}"}}"#))
+        .collect();
+    b.iter(|| {
+        for j in &docs {
+            miniserde::json::from_str::<Small>(j).unwrap();
+        }
+    });
+}
+
+#[bench]
+fn bench_deserialize_miniserde_small_documents_reused_parser(b: &mut Bencher) {
+    let docs: Vec<String> = (0..1000)
+        .map(|i| format!(r#"{{"id":{i},"text":"line\n{i}"}}"#))
+        .collect();
+    let mut parser = miniserde::json::Parser::new();
+    b.iter(|| {
+        for j in &docs {
+            parser.parse::<Small>(j).unwrap();
+        }
+    });
+}
+
+#[bench]
+fn bench_deserialize_miniserde_wide_structs(b: &mut Bencher) {
+    let j = format!(
+        "[{}]",
+        (0..2000)
+            .map(|i| {
+                let fields: Vec<String> = (0..16).map(|f| format!(r#""field_{f:02}":{i}"#)).collect();
+                format!("{{{}}}", fields.join(","))
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+    );
+    b.iter(|| {
+        miniserde::json::from_str::<Vec<Wide>>(&j).unwrap();
+    });
+}
+
+#[derive(MiniDeserialize)]
+struct Small {
+    #[allow(dead_code)]
+    id: u32,
+    #[allow(dead_code)]
+    text: String,
+}
+
+#[derive(MiniDeserialize)]
+#[allow(dead_code)]
+struct Wide {
+    field_00: u32,
+    field_01: u32,
+    field_02: u32,
+    field_03: u32,
+    field_04: u32,
+    field_05: u32,
+    field_06: u32,
+    field_07: u32,
+    field_08: u32,
+    field_09: u32,
+    field_10: u32,
+    field_11: u32,
+    field_12: u32,
+    field_13: u32,
+    field_14: u32,
+    field_15: u32,
+}
+
 #[bench]
 fn bench_serialize_miniserde(b: &mut Bencher) {
     let s = input_struct();
@@ -48,6 +150,38 @@ fn bench_serialize_serdejson(b: &mut Bencher) {
     });
 }
 
+#[bench]
+fn bench_to_value_miniserde(b: &mut Bencher) {
+    let s = input_struct();
+    b.iter(|| {
+        miniserde::json::to_value(&s);
+    });
+}
+
+#[bench]
+fn bench_to_value_miniserde_u32_array(b: &mut Bencher) {
+    let v: Vec<u32> = (0..1_000_000).map(|i| i * 7919).collect();
+    b.iter(|| {
+        miniserde::json::to_value(&v);
+    });
+}
+
+#[bench]
+fn bench_serialize_miniserde_u32_array(b: &mut Bencher) {
+    let v: Vec<u32> = (0..1_000_000).map(|i| i * 7919).collect();
+    b.iter(|| {
+        miniserde::json::to_string(&v);
+    });
+}
+
+#[bench]
+fn bench_serialize_miniserde_f64_array(b: &mut Bencher) {
+    let v: Vec<f64> = (0..1_000_000).map(|i| f64::from(i) * 0.37).collect();
+    b.iter(|| {
+        miniserde::json::to_string(&v);
+    });
+}
+
 #[derive(Serialize, MiniSerialize, Deserialize, MiniDeserialize)]
 struct Twitter {
     statuses: Vec<Status>,
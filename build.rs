@@ -1,6 +1,8 @@
 use std::env;
 use std::fs;
 use std::path::PathBuf;
+use std::process::Command;
+use std::str;
 
 const PRIVATE: &str = "\
 #[doc(hidden)]
@@ -24,4 +26,32 @@ fn main() {
     let mut mod_place = fs::read_to_string(manifest_dir.join("src").join("place.rs")).unwrap();
     mod_place = mod_place.replace("__private", &format!("__private{patch_version}"));
     fs::write(out_dir.join("place.rs"), mod_place).unwrap();
+
+    println!("cargo:rustc-check-cfg=cfg(no_core_net)");
+    println!("cargo:rustc-check-cfg=cfg(no_saturating)");
+
+    let Some(rustc) = rustc_minor_version() else {
+        return;
+    };
+
+    if rustc < 74 {
+        // https://blog.rust-lang.org/2023/11/16/Rust-1.74.0.html#stabilized-apis
+        println!("cargo:rustc-cfg=no_saturating");
+    }
+
+    if rustc < 77 {
+        // https://blog.rust-lang.org/2024/03/21/Rust-1.77.0.html#stabilized-apis
+        println!("cargo:rustc-cfg=no_core_net");
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = str::from_utf8(&output.stdout).ok()?;
+    let mut pieces = version.split('.');
+    if pieces.next() != Some("rustc 1") {
+        return None;
+    }
+    pieces.next()?.parse().ok()
 }
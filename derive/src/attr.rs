@@ -1,16 +1,35 @@
 use proc_macro2::Ident;
-use syn::{Attribute, DeriveInput, Field, LitStr, Path, Result, Variant};
+use syn::{
+    Attribute, Data, DeriveInput, Error, Field, Fields, FieldsUnnamed, LitStr, Path, Result,
+    Variant,
+};
 
 pub struct FieldAttrs {
-    pub rename: Option<String>,
+    pub rename_serialize: Option<String>,
+    pub rename_deserialize: Option<String>,
     pub skip_serializing_if: Option<Path>,
     pub default: Default,
+    pub default_on_null: bool,
+    pub with: Option<Path>,
+    pub borrow: bool,
 }
 
 pub struct ContainerAttrs {
     pub default: Default,
+    pub name_or_code: bool,
+    pub map_key: bool,
+    pub tag: Option<String>,
+    pub rename_all_fields: Option<RenameRule>,
+    pub deny_unknown_fields: bool,
+}
+
+#[derive(Copy, Clone)]
+pub enum Direction {
+    Serialize,
+    Deserialize,
 }
 
+#[allow(clippy::enum_variant_names)]
 pub enum Default {
     None,
     Default,
@@ -18,9 +37,13 @@ pub enum Default {
 }
 
 pub fn get(field: &Field) -> Result<FieldAttrs> {
-    let mut rename = None;
+    let mut rename_serialize = None;
+    let mut rename_deserialize = None;
     let mut skip_serializing_if = None;
     let mut default = Default::None;
+    let mut default_on_null = false;
+    let mut with = None;
+    let mut borrow = false;
 
     for attr in &field.attrs {
         if !attr.path().is_ident("serde") {
@@ -29,18 +52,36 @@ pub fn get(field: &Field) -> Result<FieldAttrs> {
 
         attr.parse_nested_meta(|meta| {
             if meta.path.is_ident("rename") {
+                if meta.input.peek(syn::token::Paren) {
+                    return meta.parse_nested_meta(|meta| {
+                        let slot = if meta.path.is_ident("serialize") {
+                            &mut rename_serialize
+                        } else if meta.path.is_ident("deserialize") {
+                            &mut rename_deserialize
+                        } else {
+                            return Err(meta.error("expected `serialize` or `deserialize`"));
+                        };
+                        let s: LitStr = meta.value()?.parse()?;
+                        if slot.is_some() {
+                            return Err(meta.error("duplicate rename attribute"));
+                        }
+                        *slot = Some(s.value());
+                        Ok(())
+                    });
+                }
                 let s: LitStr = meta.value()?.parse()?;
-                if rename.is_some() {
+                if rename_serialize.is_some() || rename_deserialize.is_some() {
                     return Err(meta.error("duplicate rename attribute"));
                 }
-                rename = Some(s.value());
+                rename_serialize = Some(s.value());
+                rename_deserialize = Some(s.value());
                 Ok(())
             } else if meta.path.is_ident("skip_serializing_if") {
                 let s: LitStr = meta.value()?.parse()?;
                 if skip_serializing_if.is_some() {
                     return Err(meta.error("duplicate skip_serializing_if attribute"));
                 }
-                skip_serializing_if = Some(s.parse()?);
+                skip_serializing_if = Some(parse_path(&s, "skip_serializing_if", "a predicate")?);
                 Ok(())
             } else if meta.path.is_ident("default") {
                 if !matches!(default, Default::None) {
@@ -50,9 +91,28 @@ pub fn get(field: &Field) -> Result<FieldAttrs> {
                     default = Default::Default;
                 } else {
                     let s: LitStr = meta.value()?.parse()?;
-                    default = Default::Path(s.parse()?);
+                    default = Default::Path(parse_path(&s, "default", "a function or const")?);
                 }
                 Ok(())
+            } else if meta.path.is_ident("default_on_null") {
+                if default_on_null {
+                    return Err(meta.error("duplicate default_on_null attribute"));
+                }
+                default_on_null = true;
+                Ok(())
+            } else if meta.path.is_ident("with") {
+                let s: LitStr = meta.value()?.parse()?;
+                if with.is_some() {
+                    return Err(meta.error("duplicate with attribute"));
+                }
+                with = Some(parse_path(&s, "with", "a module")?);
+                Ok(())
+            } else if meta.path.is_ident("borrow") {
+                if borrow {
+                    return Err(meta.error("duplicate borrow attribute"));
+                }
+                borrow = true;
+                Ok(())
             } else {
                 Err(meta.error("unsupported attribute"))
             }
@@ -60,14 +120,23 @@ pub fn get(field: &Field) -> Result<FieldAttrs> {
     }
 
     Ok(FieldAttrs {
-        rename,
+        rename_serialize,
+        rename_deserialize,
         skip_serializing_if,
         default,
+        default_on_null,
+        with,
+        borrow,
     })
 }
 
 pub fn get_container(input: &DeriveInput) -> Result<ContainerAttrs> {
     let mut default = Default::None;
+    let mut name_or_code = false;
+    let mut map_key = false;
+    let mut tag = None;
+    let mut rename_all_fields = None;
+    let mut deny_unknown_fields = false;
 
     for attr in &input.attrs {
         if !attr.path().is_ident("serde") {
@@ -79,34 +148,310 @@ pub fn get_container(input: &DeriveInput) -> Result<ContainerAttrs> {
                 if !matches!(default, Default::None) {
                     return Err(meta.error("duplicate default attribute"));
                 }
+                if !has_named_fields(input) {
+                    return Err(meta.error("default is only supported on structs with named fields"));
+                }
                 if meta.input.is_empty() || meta.input.peek(syn::Token![,]) {
                     default = Default::Default;
                 } else {
                     let s: LitStr = meta.value()?.parse()?;
-                    default = Default::Path(s.parse()?);
+                    default = Default::Path(parse_path(&s, "default", "a function or const")?);
+                }
+                Ok(())
+            } else if meta.path.is_ident("name_or_code") {
+                if name_or_code {
+                    return Err(meta.error("duplicate name_or_code attribute"));
+                }
+                let Data::Enum(enumeration) = &input.data else {
+                    return Err(meta.error("name_or_code is only supported on enums"));
+                };
+                if enumeration.variants.iter().any(|v| !matches!(v.fields, Fields::Unit)) {
+                    return Err(meta.error("name_or_code is only supported on enums whose variants are all unit variants"));
+                }
+                if tag.is_some() {
+                    return Err(meta.error("name_or_code cannot be used with tag"));
+                }
+                name_or_code = true;
+                Ok(())
+            } else if meta.path.is_ident("map_key") {
+                if map_key {
+                    return Err(meta.error("duplicate map_key attribute"));
                 }
+                let Data::Enum(enumeration) = &input.data else {
+                    return Err(meta.error("map_key is only supported on enums"));
+                };
+                if enumeration.variants.iter().any(|v| !matches!(v.fields, Fields::Unit)) {
+                    return Err(meta.error("map_key is only supported on enums whose variants are all unit variants"));
+                }
+                if tag.is_some() {
+                    return Err(meta.error("map_key cannot be used with tag"));
+                }
+                map_key = true;
+                Ok(())
+            } else if meta.path.is_ident("tag") {
+                let s: LitStr = meta.value()?.parse()?;
+                if tag.is_some() {
+                    return Err(meta.error("duplicate tag attribute"));
+                }
+                if !matches!(input.data, Data::Enum(_)) {
+                    return Err(meta.error("tag is only supported on enums"));
+                }
+                if name_or_code {
+                    return Err(meta.error("tag cannot be used with name_or_code"));
+                }
+                if map_key {
+                    return Err(meta.error("tag cannot be used with map_key"));
+                }
+                tag = Some(s.value());
+                Ok(())
+            } else if meta.path.is_ident("rename_all_fields") {
+                let s: LitStr = meta.value()?.parse()?;
+                if rename_all_fields.is_some() {
+                    return Err(meta.error("duplicate rename_all_fields attribute"));
+                }
+                if !matches!(input.data, Data::Enum(_)) {
+                    return Err(meta.error("rename_all_fields is only supported on enums"));
+                }
+                match RenameRule::from_str(&s.value()) {
+                    Some(rule) => rename_all_fields = Some(rule),
+                    None => return Err(syn::Error::new_spanned(s, "unknown rename rule")),
+                }
+                Ok(())
+            } else if meta.path.is_ident("deny_unknown_fields") {
+                if deny_unknown_fields {
+                    return Err(meta.error("duplicate deny_unknown_fields attribute"));
+                }
+                if !has_named_fields(input) {
+                    return Err(meta.error(
+                        "deny_unknown_fields is only supported on structs with named fields",
+                    ));
+                }
+                deny_unknown_fields = true;
                 Ok(())
             } else {
-                // We ignore other container attributes (like rename_all) as they aren't implemented yet
+                Err(meta.error("unsupported attribute"))
+            }
+        })?;
+    }
+
+    if deny_unknown_fields {
+        check_no_flatten(input)?;
+    }
+    if let (None, Data::Enum(enumeration)) = (&tag, &input.data) {
+        reject_attr(
+            &input.attrs,
+            "rename_all_fields",
+            "rename_all_fields is only supported on enums with tag",
+        )?;
+        for variant in &enumeration.variants {
+            reject_attr(
+                &variant.attrs,
+                "rename_all",
+                "rename_all is only supported on variants of enums with tag",
+            )?;
+        }
+    }
+
+    Ok(ContainerAttrs {
+        default,
+        name_or_code,
+        map_key,
+        tag,
+        rename_all_fields,
+        deny_unknown_fields,
+    })
+}
+
+fn has_named_fields(input: &DeriveInput) -> bool {
+    matches!(&input.data, Data::Struct(data) if matches!(data.fields, Fields::Named(_)))
+}
+
+/// Parse the path in an attribute such as `with = "..."`, with an error that
+/// says what the path should name.
+fn parse_path(s: &LitStr, attr: &str, expected: &str) -> Result<Path> {
+    s.parse().map_err(|_| {
+        Error::new_spanned(
+            s,
+            format!("{attr} must be the path of {expected}, like \"path::to::item\""),
+        )
+    })
+}
+
+/// A flattened field would collect the keys that `deny_unknown_fields` is
+/// meant to reject, so the two cannot be combined. Flattening is not
+/// supported on its own either, but this is the more helpful error.
+fn check_no_flatten(input: &DeriveInput) -> Result<()> {
+    let Data::Struct(data) = &input.data else {
+        return Ok(());
+    };
+    for field in &data.fields {
+        for attr in &field.attrs {
+            if !attr.path().is_ident("serde") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("flatten") {
+                    return Err(meta.error(
+                        "#[serde(flatten)] cannot be used with #[serde(deny_unknown_fields)]",
+                    ));
+                }
+                // Consume any value so the remaining attributes still parse.
+                if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                }
                 Ok(())
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Renaming the fields of struct variants only has an effect in internally
+/// tagged enums, so anywhere else the attribute that asks for it is an error
+/// rather than silently ignored.
+fn reject_attr(attrs: &[Attribute], name: &str, message: &str) -> Result<()> {
+    for attr in attrs {
+        if !attr.path().is_ident("serde") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            // Consume any value so the remaining attributes still parse, and
+            // so that the error spans it.
+            if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+            if meta.path.is_ident(name) {
+                return Err(meta.error(message));
             }
+            Ok(())
         })?;
     }
+    Ok(())
+}
+
+/// Fields of a tuple struct have no names to rename and are always present in
+/// the array, so none of the field attributes apply to them.
+pub fn check_tuple_fields(fields: &FieldsUnnamed) -> Result<()> {
+    for field in &fields.unnamed {
+        for attr in &field.attrs {
+            if attr.path().is_ident("serde") {
+                return Err(Error::new_spanned(
+                    attr,
+                    "#[serde(...)] attributes are not supported on tuple struct fields",
+                ));
+            }
+        }
+    }
+    Ok(())
+}
 
-    Ok(ContainerAttrs { default })
+/// Determine the name of a field in one direction, respecting a rename
+/// attribute.
+pub fn name_of_field(field: &Field, direction: Direction) -> Result<String> {
+    name_of_field_in(field, None, direction)
 }
 
-/// Determine the name of a field, respecting a rename attribute.
-pub fn name_of_field(field: &Field) -> Result<String> {
+/// Determine the name of a field in one direction, respecting a rename
+/// attribute or else the casing rule of the variant containing it.
+pub fn name_of_field_in(
+    field: &Field,
+    rename_all: Option<RenameRule>,
+    direction: Direction,
+) -> Result<String> {
     let attrs = get(field)?;
-    Ok(attrs.rename.unwrap_or_else(|| unraw(field.ident.as_ref().unwrap())))
+    let rename = match direction {
+        Direction::Serialize => attrs.rename_serialize,
+        Direction::Deserialize => attrs.rename_deserialize,
+    };
+    Ok(rename.unwrap_or_else(|| {
+        let name = unraw(field.ident.as_ref().unwrap());
+        match rename_all {
+            Some(rule) => rule.apply_to_field(&name),
+            None => name,
+        }
+    }))
 }
 
-/// Determine the name of a variant, respecting a rename attribute.
-pub fn name_of_variant(var: &Variant) -> Result<String> {
+pub struct VariantAttrs {
+    pub rename: Option<String>,
+    pub default: bool,
+    pub rename_all: Option<RenameRule>,
+}
+
+/// A casing convention for `#[serde(rename_all = "...")]`, applied to field
+/// names written in snake_case.
+#[derive(Copy, Clone)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn from_str(rule: &str) -> Option<Self> {
+        Some(match rule {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            _ => return None,
+        })
+    }
+
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_owned(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply_to_field(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => pascal,
+                }
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+pub fn get_variant(var: &Variant) -> Result<VariantAttrs> {
     let mut rename = None;
+    let mut default = false;
+    let mut rename_all = None;
 
     for attr in &var.attrs {
+        // The `#[default]` of `#[derive(Default)]` marks the same variant.
+        if attr.path().is_ident("default") && attr.meta.require_path_only().is_ok() {
+            default = true;
+            continue;
+        }
+
         if !attr.path().is_ident("serde") {
             continue;
         }
@@ -119,13 +464,51 @@ pub fn name_of_variant(var: &Variant) -> Result<String> {
                 }
                 rename = Some(s.value());
                 Ok(())
+            } else if meta.path.is_ident("default") {
+                if default {
+                    return Err(meta.error("duplicate default attribute"));
+                }
+                default = true;
+                Ok(())
+            } else if meta.path.is_ident("rename_all") {
+                let s: LitStr = meta.value()?.parse()?;
+                if rename_all.is_some() {
+                    return Err(meta.error("duplicate rename_all attribute"));
+                }
+                if !matches!(var.fields, Fields::Named(_)) {
+                    return Err(meta.error("rename_all is only supported on struct variants"));
+                }
+                match RenameRule::from_str(&s.value()) {
+                    Some(rule) => rename_all = Some(rule),
+                    None => return Err(syn::Error::new_spanned(s, "unknown rename rule")),
+                }
+                Ok(())
             } else {
                 Err(meta.error("unsupported attribute"))
             }
         })?;
     }
 
-    Ok(rename.unwrap_or_else(|| unraw(&var.ident)))
+    Ok(VariantAttrs {
+        rename,
+        default,
+        rename_all,
+    })
+}
+
+/// The casing rule for the fields of a variant: its own `rename_all`, or else
+/// the enum's `rename_all_fields`.
+pub fn fields_rename_rule(
+    var: &Variant,
+    rename_all_fields: Option<RenameRule>,
+) -> Result<Option<RenameRule>> {
+    Ok(get_variant(var)?.rename_all.or(rename_all_fields))
+}
+
+/// Determine the name of a variant, respecting a rename attribute.
+pub fn name_of_variant(var: &Variant) -> Result<String> {
+    let attrs = get_variant(var)?;
+    Ok(attrs.rename.unwrap_or_else(|| unraw(&var.ident)))
 }
 
 fn unraw(ident: &Ident) -> String {
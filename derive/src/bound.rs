@@ -36,6 +36,25 @@ pub fn with_lifetime_bound(generics: &Generics, lifetime: &str) -> Generics {
     }
 }
 
+pub fn with_lifetime(generics: &Generics, lifetime: &str) -> Generics {
+    let param = LifetimeParam {
+        attrs: Vec::new(),
+        lifetime: Lifetime::new(lifetime, Span::call_site()),
+        colon_token: None,
+        bounds: Punctuated::new(),
+    };
+
+    let params = Some(GenericParam::Lifetime(param))
+        .into_iter()
+        .chain(generics.params.iter().cloned())
+        .collect();
+
+    Generics {
+        params,
+        ..generics.clone()
+    }
+}
+
 pub fn where_clause_with_bound(generics: &Generics, bound: TokenStream) -> WhereClause {
     let new_predicates = generics.type_params().map::<WherePredicate, _>(|param| {
         let param = &param.ident;
@@ -1,8 +1,10 @@
-use crate::{attr, bound, fallback, private};
-use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use crate::attr::{self, Direction};
+use crate::{bound, external, fallback, private, tagged};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
 use syn::{
-    parse_quote, Data, DataEnum, DataStruct, DeriveInput, Error, Fields, FieldsNamed, Result,
+    parse_quote, Data, DataEnum, DataStruct, DeriveInput, Error, Fields, FieldsNamed,
+    FieldsUnnamed, Path, Result,
 };
 
 pub fn derive(input: &DeriveInput) -> TokenStream {
@@ -16,16 +18,23 @@ pub fn derive(input: &DeriveInput) -> TokenStream {
 }
 
 fn try_expand(input: &DeriveInput) -> Result<TokenStream> {
+    // Reject invalid container attributes even where they would be unused.
+    attr::get_container(input)?;
+
     match &input.data {
         Data::Struct(DataStruct {
                          fields: Fields::Named(fields),
                          ..
                      }) => derive_struct(input, fields),
+        Data::Struct(DataStruct {
+                         fields: Fields::Unit,
+                         ..
+                     }) => Ok(derive_unit_struct(input)),
+        Data::Struct(DataStruct {
+                         fields: Fields::Unnamed(fields),
+                         ..
+                     }) => derive_tuple_struct(input, fields),
         Data::Enum(enumeration) => derive_enum(input, enumeration),
-        Data::Struct(_) => Err(Error::new(
-            Span::call_site(),
-            "currently only structs with named fields are supported",
-        )),
         Data::Union(_) => Err(Error::new(
             Span::call_site(),
             "currently only structs and enums are supported by this derive",
@@ -39,51 +48,176 @@ pub fn derive_struct(input: &DeriveInput, fields: &FieldsNamed) -> Result<TokenS
     let container_attrs = attr::get_container(input)?;
 
     let fieldname = fields.named.iter().map(|f| &f.ident).collect::<Vec<_>>();
-    let fieldty = fields.named.iter().map(|f| &f.ty);
     let fieldstr = fields
         .named
         .iter()
-        .map(attr::name_of_field)
+        .map(|f| attr::name_of_field(f, Direction::Deserialize))
         .collect::<Result<Vec<_>>>()?;
 
+    // Fields with `#[serde(with = "...")]` start out empty and are filled in
+    // by the module's deserialize function rather than the field type's impl.
+    // Fields with a default of their own also start out empty, rather than
+    // with the type's value for a missing field, such as None for an Option.
+    // Fields with `#[serde(default_on_null)]` are deserialized as an Option so
+    // that an explicit null is recorded as None and replaced by the default.
+    // Fields with `#[serde(borrow)]` are begun through DeserializeBorrowed
+    // instead of Deserialize, and then so is the whole struct.
+    let mut fieldty = Vec::new();
+    let mut field_inits = Vec::new();
+    let mut field_begins = Vec::new();
+    let mut borrowed_tys = Vec::new();
+    for f in &fields.named {
+        let ident = &f.ident;
+        let ty = &f.ty;
+        let attrs = attr::get(f)?;
+        if attrs.borrow && attrs.with.is_some() {
+            return Err(Error::new_spanned(
+                f,
+                "#[serde(borrow)] cannot be used with #[serde(with = \"...\")]",
+            ));
+        }
+        let slot_ty = if attrs.default_on_null {
+            if attrs.with.is_some() {
+                return Err(Error::new_spanned(
+                    f,
+                    "#[serde(default_on_null)] cannot be used with #[serde(with = \"...\")]",
+                ));
+            }
+            if matches!(attrs.default, attr::Default::None)
+                && matches!(container_attrs.default, attr::Default::None)
+            {
+                return Err(Error::new_spanned(
+                    f,
+                    "#[serde(default_on_null)] requires a default for the field",
+                ));
+            }
+            quote!(miniserde::#private::Option<#ty>)
+        } else {
+            quote!(#ty)
+        };
+        fieldty.push(slot_ty.clone());
+        if let Some(path) = attrs.with {
+            field_inits.push(quote!(miniserde::#private::None));
+            field_begins.push(quote!(#path::deserialize(&mut self.#ident)));
+            continue;
+        }
+        if attrs.default_on_null || !matches!(attrs.default, attr::Default::None) {
+            // A field's own default replaces the type's, so a missing Option
+            // takes the default while an explicit null is still None.
+            field_inits.push(quote!(miniserde::#private::None));
+        } else if attrs.borrow {
+            field_inits.push(quote!(<#ty as miniserde::de::DeserializeBorrowed<'__de>>::default()));
+        } else {
+            field_inits.push(quote!(miniserde::Deserialize::default()));
+        }
+        if attrs.borrow {
+            field_begins.push(quote! {
+                unsafe {
+                    <#slot_ty as miniserde::de::DeserializeBorrowed<'__de>>::begin_borrowed(&mut self.#ident)
+                }
+            });
+            borrowed_tys.push(slot_ty);
+        } else {
+            field_begins.push(quote!(miniserde::Deserialize::begin(&mut self.#ident)));
+        }
+    }
+
     let unwrap_logic = fields
         .named
         .iter()
         .map(|f| {
             let attrs = attr::get(f)?;
             let field_ident = &f.ident;
+            let flatten = if attrs.default_on_null {
+                quote!(.flatten())
+            } else {
+                quote!()
+            };
 
             // Priority: Field default -> Container default -> Error
-            match attrs.default {
-                attr::Default::Path(path) => Ok(quote!(.unwrap_or_else(#path))),
-                attr::Default::Default => Ok(quote!(.unwrap_or_default())),
+            let unwrap = match attrs.default {
+                attr::Default::Path(path) => {
+                    let value = default_value(&path);
+                    quote!(.unwrap_or_else(|| #value))
+                }
+                attr::Default::Default => quote!(.unwrap_or_default()),
                 attr::Default::None => match &container_attrs.default {
                     attr::Default::Path(path) => {
-                        Ok(quote!(.unwrap_or_else(|| #path().#field_ident)))
+                        let value = default_value(path);
+                        quote!(.unwrap_or_else(|| #value.#field_ident))
                     },
                     attr::Default::Default => {
-                        Ok(quote!(.unwrap_or_else(|| <#ident #ty_generics as Default>::default().#field_ident)))
+                        quote!(.unwrap_or_else(|| <#ident #ty_generics as Default>::default().#field_ident))
                     },
-                    attr::Default::None => Ok(quote!(.take().ok_or(miniserde::Error)?)),
+                    attr::Default::None => quote!(.take().ok_or(miniserde::Error)?),
                 },
-            }
+            };
+            Ok(quote!(#flatten #unwrap))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Under `require_all_fields` a field that would be filled in by a default
+    // is an error instead.
+    let required_checks = fields
+        .named
+        .iter()
+        .map(|f| {
+            let field_ident = &f.ident;
+            let has_default = !matches!(attr::get(f)?.default, attr::Default::None)
+                || !matches!(container_attrs.default, attr::Default::None);
+            Ok(if has_default {
+                quote! {
+                    if self.__require_all_fields && self.#field_ident.is_none() {
+                        return miniserde::#private::Err(miniserde::Error);
+                    }
+                }
+            } else {
+                quote!()
+            })
         })
         .collect::<Result<Vec<_>>>()?;
 
     let wrapper_generics = bound::with_lifetime_bound(&input.generics, "'__a");
     let (wrapper_impl_generics, wrapper_ty_generics, _) = wrapper_generics.split_for_impl();
     let bound = parse_quote!(miniserde::Deserialize);
-    let bounded_where_clause = bound::where_clause_with_bound(&input.generics, bound);
+    let mut bounded_where_clause = bound::where_clause_with_bound(&input.generics, bound);
     let private2 = private;
 
-    Ok(quote! {
-        #[allow(deprecated, non_upper_case_globals)]
-        const _: () = {
-            #[repr(C)]
-            struct __Visitor #impl_generics #where_clause {
-                __out: miniserde::#private::Option<#ident #ty_generics>,
-            }
+    // A struct that borrows is generic over the lifetime of the input, which
+    // every borrowed field must be able to borrow for.
+    let borrowed = !borrowed_tys.is_empty();
+    let (visitor_generics, state_generics) = if borrowed {
+        bounded_where_clause.predicates.extend(
+            borrowed_tys
+                .iter()
+                .map::<syn::WherePredicate, _>(|ty| parse_quote!(#ty: miniserde::de::DeserializeBorrowed<'__de>)),
+        );
+        (
+            bound::with_lifetime(&input.generics, "'__de"),
+            bound::with_lifetime(&wrapper_generics, "'__de"),
+        )
+    } else {
+        (input.generics.clone(), wrapper_generics.clone())
+    };
+    let (visitor_impl_generics, _, _) = visitor_generics.split_for_impl();
+    let (state_impl_generics, _, _) = state_generics.split_for_impl();
 
+    let begin = if borrowed {
+        quote! {
+            unsafe impl #visitor_impl_generics miniserde::de::DeserializeBorrowed<'__de> for #ident #ty_generics #bounded_where_clause {
+                unsafe fn begin_borrowed(__out: &mut miniserde::#private::Option<Self>) -> &mut dyn miniserde::de::Visitor {
+                    unsafe {
+                        &mut *{
+                            __out
+                            as *mut miniserde::#private::Option<Self>
+                            as *mut __Visitor #ty_generics
+                        }
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {
             impl #impl_generics miniserde::Deserialize for #ident #ty_generics #bounded_where_clause {
                 fn begin(__out: &mut miniserde::#private::Option<Self>) -> &mut dyn miniserde::de::Visitor {
                     unsafe {
@@ -95,14 +229,33 @@ pub fn derive_struct(input: &DeriveInput, fields: &FieldsNamed) -> Result<TokenS
                     }
                 }
             }
+        }
+    };
 
-            impl #impl_generics miniserde::de::Visitor for __Visitor #ty_generics #bounded_where_clause {
+    let unknown_field = if container_attrs.deny_unknown_fields {
+        quote!(miniserde::#private::Err(miniserde::Error))
+    } else {
+        quote!(miniserde::#private::Ok(<dyn miniserde::de::Visitor>::ignore()))
+    };
+
+    Ok(quote! {
+        #[allow(deprecated, non_upper_case_globals)]
+        const _: () = {
+            #[repr(C)]
+            struct __Visitor #impl_generics #where_clause {
+                __out: miniserde::#private::Option<#ident #ty_generics>,
+            }
+
+            #begin
+
+            impl #visitor_impl_generics miniserde::de::Visitor for __Visitor #ty_generics #bounded_where_clause {
                 fn map(&mut self) -> miniserde::Result<miniserde::#private::Box<dyn miniserde::de::Map + '_>> {
                     Ok(miniserde::#private::Box::new(__State {
                         #(
-                            #fieldname: miniserde::Deserialize::default(),
+                            #fieldname: #field_inits,
                         )*
                         __out: &mut self.__out,
+                        __require_all_fields: false,
                     }))
                 }
             }
@@ -112,19 +265,25 @@ pub fn derive_struct(input: &DeriveInput, fields: &FieldsNamed) -> Result<TokenS
                     #fieldname: miniserde::#private2::Option<#fieldty>,
                 )*
                 __out: &'__a mut miniserde::#private::Option<#ident #ty_generics>,
+                __require_all_fields: bool,
             }
 
-            impl #wrapper_impl_generics miniserde::de::Map for __State #wrapper_ty_generics #bounded_where_clause {
+            impl #state_impl_generics miniserde::de::Map for __State #wrapper_ty_generics #bounded_where_clause {
                 fn key(&mut self, __k: &miniserde::#private::str) -> miniserde::Result<&mut dyn miniserde::de::Visitor> {
                     match __k {
                         #(
-                            #fieldstr => miniserde::#private2::Ok(miniserde::Deserialize::begin(&mut self.#fieldname)),
+                            #fieldstr => miniserde::#private2::Ok(#field_begins),
                         )*
-                        _ => miniserde::#private::Ok(<dyn miniserde::de::Visitor>::ignore()),
+                        _ => #unknown_field,
                     }
                 }
 
+                fn __require_all_fields(&mut self) {
+                    self.__require_all_fields = true;
+                }
+
                 fn finish(&mut self) -> miniserde::Result<()> {
+                    #(#required_checks)*
                     #(
                         let #fieldname = self.#fieldname.take() #unwrap_logic;
                     )*
@@ -140,6 +299,137 @@ pub fn derive_struct(input: &DeriveInput, fields: &FieldsNamed) -> Result<TokenS
     })
 }
 
+/// The value named by a `default = "..."` path: a call if it names a function,
+/// including an associated function such as `Config::default_port`, or else
+/// the const it names. Which one is left to the type of the path, by way of
+/// the traits next to `DefaultPath`.
+fn default_value(path: &Path) -> TokenStream {
+    quote!({
+        #[allow(unused_imports)]
+        use miniserde::#private::{DefaultFromConst as _, DefaultFromFn as _};
+        (&miniserde::#private::DefaultPath::new(#path)).__default_value()
+    })
+}
+
+/// A unit struct is deserialized from `null`.
+fn derive_unit_struct(input: &DeriveInput) -> TokenStream {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        #[allow(deprecated, non_upper_case_globals)]
+        const _: () = {
+            #[repr(C)]
+            struct __Visitor #impl_generics #where_clause {
+                __out: miniserde::#private::Option<#ident #ty_generics>,
+            }
+
+            impl #impl_generics miniserde::Deserialize for #ident #ty_generics #where_clause {
+                fn begin(__out: &mut miniserde::#private::Option<Self>) -> &mut dyn miniserde::de::Visitor {
+                    unsafe {
+                        &mut *{
+                            __out
+                            as *mut miniserde::#private::Option<Self>
+                            as *mut __Visitor #ty_generics
+                        }
+                    }
+                }
+            }
+
+            impl #impl_generics miniserde::de::Visitor for __Visitor #ty_generics #where_clause {
+                fn null(&mut self) -> miniserde::Result<()> {
+                    self.__out = miniserde::#private::Some(#ident);
+                    miniserde::#private::Ok(())
+                }
+            }
+        };
+    }
+}
+
+/// A tuple struct is deserialized from an array with exactly one element per
+/// field.
+fn derive_tuple_struct(input: &DeriveInput, fields: &FieldsUnnamed) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    attr::check_tuple_fields(fields)?;
+    let fieldname = (0..fields.unnamed.len())
+        .map(|i| format_ident!("__field{}", i))
+        .collect::<Vec<_>>();
+    let fieldty = fields.unnamed.iter().map(|f| &f.ty);
+    let index = 0usize..;
+
+    let wrapper_generics = bound::with_lifetime_bound(&input.generics, "'__a");
+    let (wrapper_impl_generics, wrapper_ty_generics, _) = wrapper_generics.split_for_impl();
+    let bound = parse_quote!(miniserde::Deserialize);
+    let bounded_where_clause = bound::where_clause_with_bound(&input.generics, bound);
+    let private2 = private;
+
+    Ok(quote! {
+        #[allow(deprecated, non_upper_case_globals)]
+        const _: () = {
+            #[repr(C)]
+            struct __Visitor #impl_generics #where_clause {
+                __out: miniserde::#private::Option<#ident #ty_generics>,
+            }
+
+            impl #impl_generics miniserde::Deserialize for #ident #ty_generics #bounded_where_clause {
+                fn begin(__out: &mut miniserde::#private::Option<Self>) -> &mut dyn miniserde::de::Visitor {
+                    unsafe {
+                        &mut *{
+                            __out
+                            as *mut miniserde::#private::Option<Self>
+                            as *mut __Visitor #ty_generics
+                        }
+                    }
+                }
+            }
+
+            impl #impl_generics miniserde::de::Visitor for __Visitor #ty_generics #bounded_where_clause {
+                fn seq(&mut self) -> miniserde::Result<miniserde::#private::Box<dyn miniserde::de::Seq + '_>> {
+                    Ok(miniserde::#private::Box::new(__State {
+                        #(
+                            #fieldname: miniserde::#private2::None,
+                        )*
+                        __index: 0,
+                        __out: &mut self.__out,
+                    }))
+                }
+            }
+
+            struct __State #wrapper_impl_generics #where_clause {
+                #(
+                    #fieldname: miniserde::#private2::Option<#fieldty>,
+                )*
+                __index: miniserde::#private::usize,
+                __out: &'__a mut miniserde::#private::Option<#ident #ty_generics>,
+            }
+
+            impl #wrapper_impl_generics miniserde::de::Seq for __State #wrapper_ty_generics #bounded_where_clause {
+                fn element(&mut self) -> miniserde::Result<&mut dyn miniserde::de::Visitor> {
+                    let __index = self.__index;
+                    self.__index = __index + 1;
+                    match __index {
+                        #(
+                            #index => miniserde::#private2::Ok(miniserde::Deserialize::begin(&mut self.#fieldname)),
+                        )*
+                        _ => miniserde::#private::Err(miniserde::Error),
+                    }
+                }
+
+                fn finish(&mut self) -> miniserde::Result<()> {
+                    *self.__out = miniserde::#private::Some(#ident(
+                        #(
+                            self.#fieldname.take().ok_or(miniserde::Error)?,
+                        )*
+                    ));
+                    miniserde::#private::Ok(())
+                }
+            }
+        };
+    })
+}
+
 pub fn derive_enum(input: &DeriveInput, enumeration: &DataEnum) -> Result<TokenStream> {
     if input.generics.lt_token.is_some() || input.generics.where_clause.is_some() {
         return Err(Error::new(
@@ -149,24 +439,83 @@ pub fn derive_enum(input: &DeriveInput, enumeration: &DataEnum) -> Result<TokenS
     }
 
     let ident = &input.ident;
+    let container_attrs = attr::get_container(input)?;
+    if let Some(tag) = &container_attrs.tag {
+        return derive_tagged_enum(input, enumeration, tag);
+    }
+    if external::has_data(enumeration) {
+        return derive_data_enum(input, enumeration);
+    }
 
     let var_idents = enumeration
         .variants
         .iter()
-        .map(|variant| match variant.fields {
-            Fields::Unit => Ok(&variant.ident),
-            _ => Err(Error::new_spanned(
-                variant,
-                "Invalid variant: only simple enum variants without fields are supported",
-            )),
-        })
-        .collect::<Result<Vec<_>>>()?;
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
     let names = enumeration
         .variants
         .iter()
         .map(attr::name_of_variant)
         .collect::<Result<Vec<_>>>()?;
 
+    let fallback = match default_variant(enumeration)? {
+        Some(var_ident) => quote!(#ident::#var_ident),
+        None => quote!(return miniserde::#private::Err(miniserde::Error)),
+    };
+
+    // With `#[serde(name_or_code)]` the variant may also be given by its
+    // integer discriminant.
+    let (code_fn, code_methods) = if container_attrs.name_or_code {
+        let code_fn = quote! {
+            impl __Visitor {
+                fn code(&mut self, n: i128) -> miniserde::Result<()> {
+                    let value = #(
+                        if n == #ident::#var_idents as i128 {
+                            #ident::#var_idents
+                        } else
+                    )* {
+                        #fallback
+                    };
+                    self.__out = miniserde::#private::Some(value);
+                    miniserde::#private::Ok(())
+                }
+            }
+        };
+        let code_methods = quote! {
+            fn nonnegative(&mut self, n: u64) -> miniserde::Result<()> {
+                self.code(i128::from(n))
+            }
+
+            fn negative(&mut self, n: i64) -> miniserde::Result<()> {
+                self.code(i128::from(n))
+            }
+        };
+        (code_fn, code_methods)
+    } else {
+        (quote!(), quote!())
+    };
+
+    // With `#[serde(map_key)]` the enum can also be the key type of a map,
+    // parsed by variant name. An unknown key is an error even if there is a
+    // default variant, so that distinct keys never collapse into one.
+    let from_str = if container_attrs.map_key {
+        let private2 = private;
+        quote! {
+            impl miniserde::#private::FromStr for #ident {
+                type Err = miniserde::Error;
+
+                fn from_str(s: &miniserde::#private::str) -> miniserde::Result<Self> {
+                    match s {
+                        #( #names => miniserde::#private2::Ok(#ident::#var_idents), )*
+                        _ => miniserde::#private::Err(miniserde::Error),
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
     Ok(quote! {
         #[allow(deprecated, non_upper_case_globals)]
         const _: () = {
@@ -187,16 +536,291 @@ pub fn derive_enum(input: &DeriveInput, enumeration: &DataEnum) -> Result<TokenS
                 }
             }
 
+            #code_fn
+
             impl miniserde::de::Visitor for __Visitor {
                 fn string(&mut self, s: &miniserde::#private::str) -> miniserde::Result<()> {
                     let value = match s {
                         #( #names => #ident::#var_idents, )*
-                        _ => return miniserde::#private::Err(miniserde::Error),
+                        _ => #fallback,
                     };
                     self.__out = miniserde::#private::Some(value);
                     miniserde::#private::Ok(())
                 }
+
+                #code_methods
             }
+
+            #from_str
         };
     })
-}
\ No newline at end of file
+}
+
+fn derive_data_enum(input: &DeriveInput, enumeration: &DataEnum) -> Result<TokenStream> {
+    let ident = &input.ident;
+
+    let mut unit_idents = Vec::new();
+    let mut unit_names = Vec::new();
+    let mut data_idents = Vec::new();
+    let mut data_names = Vec::new();
+    let mut data_tys = Vec::new();
+    for variant in &enumeration.variants {
+        let name = attr::name_of_variant(variant)?;
+        match external::variant_field(variant)? {
+            None => {
+                unit_idents.push(&variant.ident);
+                unit_names.push(name);
+            }
+            Some(field) => {
+                data_idents.push(&variant.ident);
+                data_names.push(name);
+                data_tys.push(&field.ty);
+            }
+        }
+    }
+    let slots = (0..data_idents.len())
+        .map(|i| format_ident!("__variant{}", i))
+        .collect::<Vec<_>>();
+
+    // An unrecognized name falls back to the default variant, if any, in
+    // either form. As a key its value is skipped.
+    let default_variant = default_variant(enumeration)?;
+    let fallback = match default_variant {
+        Some(var_ident) => quote!(#ident::#var_ident),
+        None => quote!(return miniserde::#private::Err(miniserde::Error)),
+    };
+    let (unknown_key, unknown_finish) = match default_variant {
+        Some(var_ident) => (
+            quote!(miniserde::#private::Ok(<dyn miniserde::de::Visitor>::ignore())),
+            quote! {
+                *self.__out = miniserde::#private::Some(#ident::#var_ident);
+                miniserde::#private::Ok(())
+            },
+        ),
+        None => (
+            quote!(miniserde::#private::Err(miniserde::Error)),
+            quote!(miniserde::#private::Err(miniserde::Error)),
+        ),
+    };
+
+    let string_fn = if unit_idents.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            fn string(&mut self, s: &miniserde::#private::str) -> miniserde::Result<()> {
+                let value = match s {
+                    #( #unit_names => #ident::#unit_idents, )*
+                    _ => #fallback,
+                };
+                self.__out = miniserde::#private::Some(value);
+                miniserde::#private::Ok(())
+            }
+        }
+    };
+    let private2 = private;
+
+    Ok(quote! {
+        #[allow(deprecated, non_upper_case_globals)]
+        const _: () = {
+            #[repr(C)]
+            struct __Visitor {
+                __out: miniserde::#private::Option<#ident>,
+            }
+
+            impl miniserde::Deserialize for #ident {
+                fn begin(__out: &mut miniserde::#private::Option<Self>) -> &mut dyn miniserde::de::Visitor {
+                    unsafe {
+                        &mut *{
+                            __out
+                            as *mut miniserde::#private::Option<Self>
+                            as *mut __Visitor
+                        }
+                    }
+                }
+            }
+
+            impl miniserde::de::Visitor for __Visitor {
+                #string_fn
+
+                fn map(&mut self) -> miniserde::Result<miniserde::#private::Box<dyn miniserde::de::Map + '_>> {
+                    miniserde::#private::Ok(miniserde::#private::Box::new(__State {
+                        #(
+                            #slots: miniserde::#private2::None,
+                        )*
+                        __out: &mut self.__out,
+                        __seen_key: false,
+                    }))
+                }
+            }
+
+            struct __State<'__a> {
+                #(
+                    #slots: miniserde::#private2::Option<#data_tys>,
+                )*
+                __out: &'__a mut miniserde::#private::Option<#ident>,
+                __seen_key: bool,
+            }
+
+            impl<'__a> miniserde::de::Map for __State<'__a> {
+                fn key(&mut self, __k: &miniserde::#private::str) -> miniserde::Result<&mut dyn miniserde::de::Visitor> {
+                    // The object holds exactly one variant.
+                    if self.__seen_key {
+                        return miniserde::#private::Err(miniserde::Error);
+                    }
+                    self.__seen_key = true;
+                    match __k {
+                        #(
+                            #data_names => miniserde::#private2::Ok(miniserde::Deserialize::begin(&mut self.#slots)),
+                        )*
+                        _ => #unknown_key,
+                    }
+                }
+
+                fn finish(&mut self) -> miniserde::Result<()> {
+                    if !self.__seen_key {
+                        return miniserde::#private::Err(miniserde::Error);
+                    }
+                    #(
+                        if let miniserde::#private2::Some(__value) = self.#slots.take() {
+                            *self.__out = miniserde::#private2::Some(#ident::#data_idents(__value));
+                            return miniserde::#private2::Ok(());
+                        }
+                    )*
+                    #unknown_finish
+                }
+            }
+        };
+    })
+}
+
+/// The unit variant marked `#[serde(default)]` or `#[default]`, which an
+/// unrecognized variant name falls back to.
+fn default_variant(enumeration: &DataEnum) -> Result<Option<&Ident>> {
+    let mut default_variant = None;
+    for variant in &enumeration.variants {
+        if !attr::get_variant(variant)?.default {
+            continue;
+        }
+        if default_variant.is_some() {
+            return Err(Error::new_spanned(
+                variant,
+                "only one variant can be marked as the default",
+            ));
+        }
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "the default variant must be a unit variant",
+            ));
+        }
+        default_variant = Some(&variant.ident);
+    }
+    Ok(default_variant)
+}
+
+fn derive_tagged_enum(input: &DeriveInput, enumeration: &DataEnum, tag: &str) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let rename_all_fields = attr::get_container(input)?.rename_all_fields;
+
+    let names = enumeration
+        .variants
+        .iter()
+        .map(attr::name_of_variant)
+        .collect::<Result<Vec<_>>>()?;
+
+    let fallback = match default_variant(enumeration)? {
+        Some(var_ident) => quote!(miniserde::#private::Ok(#ident::#var_ident)),
+        None => quote!(miniserde::#private::Err(miniserde::Error)),
+    };
+
+    // Each variant with fields is deserialized from the rest of the object by
+    // way of a derived struct with the same fields.
+    let constructs = enumeration
+        .variants
+        .iter()
+        .map(|variant| {
+            let var_ident = &variant.ident;
+            let rename_all = attr::fields_rename_rule(variant, rename_all_fields)?;
+            let fields = tagged::variant_fields(variant, tag, rename_all)?;
+            if let Fields::Unit = variant.fields {
+                return Ok(quote!(miniserde::#private::Ok(#ident::#var_ident)));
+            }
+            let fieldname = fields.iter().map(|f| &f.ident).collect::<Vec<_>>();
+            let fieldty = fields.iter().map(|f| &f.ty);
+            // The variant's casing rule becomes a rename of each field that
+            // is not renamed explicitly.
+            let fieldattrs = fields
+                .iter()
+                .map(|f| {
+                    let mut attrs = f
+                        .attrs
+                        .iter()
+                        .filter(|attr| attr.path().is_ident("serde"))
+                        .map(|attr| quote!(#attr))
+                        .collect::<Vec<_>>();
+                    if let (None, Some(_)) = (attr::get(f)?.rename_deserialize, rename_all) {
+                        let name = attr::name_of_field_in(f, rename_all, Direction::Deserialize)?;
+                        attrs.push(quote!(#[serde(rename(deserialize = #name))]));
+                    }
+                    Ok(attrs)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(quote!({
+                #[derive(miniserde::Deserialize)]
+                struct __Variant {
+                    #(
+                        #(#fieldattrs)*
+                        #fieldname: #fieldty,
+                    )*
+                }
+
+                let __variant: __Variant =
+                    __config.from_value(miniserde::json::Value::Object(__object))?;
+                miniserde::#private::Ok(#ident::#var_ident {
+                    #(
+                        #fieldname: __variant.#fieldname,
+                    )*
+                })
+            }))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #[allow(deprecated, non_upper_case_globals)]
+        const _: () = {
+            #[repr(C)]
+            struct __Visitor {
+                __out: miniserde::#private::Option<#ident>,
+            }
+
+            impl miniserde::Deserialize for #ident {
+                fn begin(__out: &mut miniserde::#private::Option<Self>) -> &mut dyn miniserde::de::Visitor {
+                    unsafe {
+                        &mut *{
+                            __out
+                            as *mut miniserde::#private::Option<Self>
+                            as *mut __Visitor
+                        }
+                    }
+                }
+            }
+
+            fn __finish(
+                __tag: &miniserde::#private::str,
+                __object: miniserde::json::Object,
+                __config: &miniserde::json::Deserializer,
+            ) -> miniserde::Result<#ident> {
+                match __tag {
+                    #( #names => #constructs, )*
+                    _ => #fallback,
+                }
+            }
+
+            impl miniserde::de::Visitor for __Visitor {
+                fn map(&mut self) -> miniserde::Result<miniserde::#private::Box<dyn miniserde::de::Map + '_>> {
+                    miniserde::#private::Ok(miniserde::#private::begin_tagged(&mut self.__out, #tag, __finish))
+                }
+            }
+        };
+    })
+}
@@ -0,0 +1,43 @@
+use crate::attr;
+use syn::{DataEnum, Error, Field, Fields, Result, Variant};
+
+/// Whether any variant carries data, which makes the enum externally tagged:
+/// unit variants are still just their name, and the others are an object with
+/// the name as its only key.
+pub fn has_data(enumeration: &DataEnum) -> bool {
+    enumeration
+        .variants
+        .iter()
+        .any(|variant| !matches!(variant.fields, Fields::Unit))
+}
+
+/// The field of a newtype variant, or `None` for a unit variant, checking that
+/// the variant is one of the two.
+pub fn variant_field(variant: &Variant) -> Result<Option<&Field>> {
+    let field = match &variant.fields {
+        Fields::Unit => return Ok(None),
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0],
+        _ => {
+            return Err(Error::new_spanned(
+                variant,
+                "Invalid variant: only unit variants and variants with a single unnamed field are supported",
+            ));
+        }
+    };
+
+    let attrs = attr::get(field)?;
+    if attrs.with.is_some() {
+        return Err(Error::new_spanned(
+            field,
+            "#[serde(with = \"...\")] is not supported in enum variants",
+        ));
+    }
+    if attrs.borrow {
+        return Err(Error::new_spanned(
+            field,
+            "#[serde(borrow)] is not supported in enum variants",
+        ));
+    }
+
+    Ok(Some(field))
+}
@@ -9,8 +9,10 @@ extern crate proc_macro;
 mod attr;
 mod bound;
 mod de;
+mod external;
 mod fallback;
 mod ser;
+mod tagged;
 
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
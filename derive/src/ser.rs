@@ -1,8 +1,10 @@
-use crate::{attr, bound, fallback, private};
+use crate::attr::{self, Direction};
+use crate::{bound, external, fallback, private, tagged};
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{
-    parse_quote, Data, DataEnum, DataStruct, DeriveInput, Error, Fields, FieldsNamed, Result,
+    parse_quote, Data, DataEnum, DataStruct, DeriveInput, Error, Fields, FieldsNamed,
+    FieldsUnnamed, Index, Result,
 };
 
 pub fn derive(input: &DeriveInput) -> TokenStream {
@@ -16,16 +18,23 @@ pub fn derive(input: &DeriveInput) -> TokenStream {
 }
 
 fn try_expand(input: &DeriveInput) -> Result<TokenStream> {
+    // Reject invalid container attributes even where they would be unused.
+    attr::get_container(input)?;
+
     match &input.data {
         Data::Struct(DataStruct {
                          fields: Fields::Named(fields),
                          ..
                      }) => derive_struct(input, fields),
+        Data::Struct(DataStruct {
+                         fields: Fields::Unit,
+                         ..
+                     }) => Ok(derive_unit_struct(input)),
+        Data::Struct(DataStruct {
+                         fields: Fields::Unnamed(fields),
+                         ..
+                     }) => derive_tuple_struct(input, fields),
         Data::Enum(enumeration) => derive_enum(input, enumeration),
-        Data::Struct(_) => Err(Error::new(
-            Span::call_site(),
-            "currently only structs with named fields are supported",
-        )),
         Data::Union(_) => Err(Error::new(
             Span::call_site(),
             "currently only structs and enums are supported by this derive",
@@ -37,11 +46,10 @@ fn derive_struct(input: &DeriveInput, fields: &FieldsNamed) -> Result<TokenStrea
     let ident = &input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    let fieldname = &fields.named.iter().map(|f| &f.ident).collect::<Vec<_>>();
     let fieldstr = fields
         .named
         .iter()
-        .map(attr::name_of_field)
+        .map(|f| attr::name_of_field(f, Direction::Serialize))
         .collect::<Result<Vec<_>>>()?;
 
     let skip_checks = fields
@@ -62,6 +70,30 @@ fn derive_struct(input: &DeriveInput, fields: &FieldsNamed) -> Result<TokenStrea
         })
         .collect::<Result<Vec<_>>>()?;
 
+    // Fields with `#[serde(with = "...")]` are serialized through a wrapper
+    // stored in the __Map, which calls the module's serialize function.
+    let mut with_fields = Vec::new();
+    let mut with_tys = Vec::new();
+    let mut with_inits = Vec::new();
+    let mut fieldvalue = Vec::new();
+    for (i, f) in fields.named.iter().enumerate() {
+        let ident = &f.ident;
+        if let Some(path) = attr::get(f)?.with {
+            let with_field = format_ident!("__with{}", i);
+            with_tys.push(&f.ty);
+            with_inits.push(quote! {
+                miniserde::#private::SerializeWith {
+                    value: &self.#ident,
+                    serialize: #path::serialize,
+                }
+            });
+            fieldvalue.push(quote!(&self.#with_field));
+            with_fields.push(with_field);
+        } else {
+            fieldvalue.push(quote!(&self.data.#ident));
+        }
+    }
+
     let index = 0usize..;
 
     let wrapper_generics = bound::with_lifetime_bound(&input.generics, "'__a");
@@ -78,6 +110,9 @@ fn derive_struct(input: &DeriveInput, fields: &FieldsNamed) -> Result<TokenStrea
                     miniserde::ser::Fragment::Map(miniserde::#private::Box::new(__Map {
                         data: self,
                         state: 0,
+                        #(
+                            #with_fields: #with_inits,
+                        )*
                     }))
                 }
             }
@@ -85,6 +120,9 @@ fn derive_struct(input: &DeriveInput, fields: &FieldsNamed) -> Result<TokenStrea
             struct __Map #wrapper_impl_generics #where_clause {
                 data: &'__a #ident #ty_generics,
                 state: miniserde::#private::usize,
+                #(
+                    #with_fields: miniserde::#private2::SerializeWith<'__a, #with_tys>,
+                )*
             }
 
             impl #wrapper_impl_generics miniserde::ser::Map for __Map #wrapper_ty_generics #bounded_where_clause {
@@ -98,7 +136,7 @@ fn derive_struct(input: &DeriveInput, fields: &FieldsNamed) -> Result<TokenStrea
                                     #skip_checks
                                     return miniserde::#private2::Some((
                                         miniserde::#private2::Cow::Borrowed(#fieldstr),
-                                        &self.data.#fieldname,
+                                        #fieldvalue,
                                     ));
                                 }
                             )*
@@ -111,6 +149,71 @@ fn derive_struct(input: &DeriveInput, fields: &FieldsNamed) -> Result<TokenStrea
     })
 }
 
+/// A unit struct is serialized as `null`.
+fn derive_unit_struct(input: &DeriveInput) -> TokenStream {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        #[allow(deprecated, non_upper_case_globals)]
+        const _: () = {
+            impl #impl_generics miniserde::Serialize for #ident #ty_generics #where_clause {
+                fn begin(&self) -> miniserde::ser::Fragment {
+                    miniserde::ser::Fragment::Null
+                }
+            }
+        };
+    }
+}
+
+/// A tuple struct is serialized as an array of its fields.
+fn derive_tuple_struct(input: &DeriveInput, fields: &FieldsUnnamed) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    attr::check_tuple_fields(fields)?;
+    let member = (0..fields.unnamed.len()).map(Index::from);
+    let index = 0usize..;
+
+    let wrapper_generics = bound::with_lifetime_bound(&input.generics, "'__a");
+    let (wrapper_impl_generics, wrapper_ty_generics, _) = wrapper_generics.split_for_impl();
+    let bound = parse_quote!(miniserde::Serialize);
+    let bounded_where_clause = bound::where_clause_with_bound(&input.generics, bound);
+    let private2 = private;
+
+    Ok(quote! {
+        #[allow(deprecated, non_upper_case_globals)]
+        const _: () = {
+            impl #impl_generics miniserde::Serialize for #ident #ty_generics #bounded_where_clause {
+                fn begin(&self) -> miniserde::ser::Fragment {
+                    miniserde::ser::Fragment::Seq(miniserde::#private::Box::new(__Seq {
+                        data: self,
+                        state: 0,
+                    }))
+                }
+            }
+
+            struct __Seq #wrapper_impl_generics #where_clause {
+                data: &'__a #ident #ty_generics,
+                state: miniserde::#private::usize,
+            }
+
+            impl #wrapper_impl_generics miniserde::ser::Seq for __Seq #wrapper_ty_generics #bounded_where_clause {
+                fn next(&mut self) -> miniserde::#private::Option<&dyn miniserde::Serialize> {
+                    let __state = self.state;
+                    self.state = __state + 1;
+                    match __state {
+                        #(
+                            #index => miniserde::#private2::Some(&self.data.#member),
+                        )*
+                        _ => miniserde::#private::None,
+                    }
+                }
+            }
+        };
+    })
+}
+
 fn derive_enum(input: &DeriveInput, enumeration: &DataEnum) -> Result<TokenStream> {
     if input.generics.lt_token.is_some() || input.generics.where_clause.is_some() {
         return Err(Error::new(
@@ -120,18 +223,19 @@ fn derive_enum(input: &DeriveInput, enumeration: &DataEnum) -> Result<TokenStrea
     }
 
     let ident = &input.ident;
+    let container_attrs = attr::get_container(input)?;
+    if let Some(tag) = &container_attrs.tag {
+        return derive_tagged_enum(input, enumeration, tag);
+    }
+    if external::has_data(enumeration) {
+        return derive_data_enum(input, enumeration);
+    }
 
     let var_idents = enumeration
         .variants
         .iter()
-        .map(|variant| match variant.fields {
-            Fields::Unit => Ok(&variant.ident),
-            _ => Err(Error::new_spanned(
-                variant,
-                "Invalid variant: only simple enum variants without fields are supported",
-            )),
-        })
-        .collect::<Result<Vec<_>>>()?;
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
     let names = enumeration
         .variants
         .iter()
@@ -139,6 +243,24 @@ fn derive_enum(input: &DeriveInput, enumeration: &DataEnum) -> Result<TokenStrea
         .collect::<Result<Vec<_>>>()?;
     let private2 = private;
 
+    // With `#[serde(map_key)]` the enum can also be the key type of a map,
+    // written as the variant name.
+    let map_key = if container_attrs.map_key {
+        quote! {
+            impl miniserde::ser::MapKey for #ident {
+                fn serialize_key(&self) -> miniserde::#private::Cow<miniserde::#private::str> {
+                    match self {
+                        #(
+                            #ident::#var_idents => miniserde::#private2::Cow::Borrowed(#names),
+                        )*
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
     Ok(quote! {
         #[allow(deprecated, non_upper_case_globals)]
         const _: () = {
@@ -153,6 +275,160 @@ fn derive_enum(input: &DeriveInput, enumeration: &DataEnum) -> Result<TokenStrea
                     }
                 }
             }
+
+            #map_key
+        };
+    })
+}
+
+fn derive_data_enum(input: &DeriveInput, enumeration: &DataEnum) -> Result<TokenStream> {
+    let ident = &input.ident;
+
+    let arms = enumeration
+        .variants
+        .iter()
+        .map(|variant| {
+            let var_ident = &variant.ident;
+            let name = attr::name_of_variant(variant)?;
+            Ok(match external::variant_field(variant)? {
+                None => quote! {
+                    #ident::#var_ident => {
+                        miniserde::ser::Fragment::Str(miniserde::#private::Cow::Borrowed(#name))
+                    }
+                },
+                Some(_) => quote! {
+                    #ident::#var_ident(__value) => {
+                        miniserde::ser::Fragment::Map(miniserde::#private::Box::new(__Map {
+                            name: miniserde::#private::Some(#name),
+                            value: __value,
+                        }))
+                    }
+                },
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #[allow(deprecated, non_upper_case_globals)]
+        const _: () = {
+            impl miniserde::Serialize for #ident {
+                fn begin(&self) -> miniserde::ser::Fragment {
+                    match self {
+                        #(#arms)*
+                    }
+                }
+            }
+
+            struct __Map<'__a> {
+                name: miniserde::#private::Option<&'static miniserde::#private::str>,
+                value: &'__a dyn miniserde::Serialize,
+            }
+
+            impl<'__a> miniserde::ser::Map for __Map<'__a> {
+                fn next(&mut self) -> miniserde::#private::Option<(miniserde::#private::Cow<miniserde::#private::str>, &dyn miniserde::Serialize)> {
+                    let __name = self.name.take()?;
+                    miniserde::#private::Some((miniserde::#private::Cow::Borrowed(__name), self.value))
+                }
+            }
+        };
+    })
+}
+
+fn derive_tagged_enum(input: &DeriveInput, enumeration: &DataEnum, tag: &str) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let rename_all_fields = attr::get_container(input)?.rename_all_fields;
+
+    let names = enumeration
+        .variants
+        .iter()
+        .map(attr::name_of_variant)
+        .collect::<Result<Vec<_>>>()?;
+
+    // The tag is entry 0 of every variant, followed by its fields in order.
+    let arms = enumeration
+        .variants
+        .iter()
+        .zip(&names)
+        .map(|(variant, name)| {
+            let var_ident = &variant.ident;
+            let rename_all = attr::fields_rename_rule(variant, rename_all_fields)?;
+            let fields = tagged::variant_fields(variant, tag, rename_all)?;
+            let fieldname = fields.iter().map(|f| &f.ident).collect::<Vec<_>>();
+            let fieldstr = fields
+                .iter()
+                .map(|f| attr::name_of_field_in(f, rename_all, Direction::Serialize))
+                .collect::<Result<Vec<_>>>()?;
+            let skip_checks = fields
+                .iter()
+                .map(|f| {
+                    let ident = &f.ident;
+                    Ok(match attr::get(f)?.skip_serializing_if {
+                        Some(path) => quote! {
+                            if #path(#ident) {
+                                continue;
+                            }
+                        },
+                        None => quote!(),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let index = 1usize..;
+            let pattern = match variant.fields {
+                Fields::Unit => quote!(#ident::#var_ident),
+                _ => quote!(#ident::#var_ident { #(#fieldname),* }),
+            };
+            let private2 = private;
+            Ok(quote! {
+                #pattern => match __state {
+                    0 => {
+                        return miniserde::#private::Some((
+                            miniserde::#private::Cow::Borrowed(#tag),
+                            &#name,
+                        ));
+                    }
+                    #(
+                        #index => {
+                            #skip_checks
+                            return miniserde::#private2::Some((
+                                miniserde::#private2::Cow::Borrowed(#fieldstr),
+                                #fieldname,
+                            ));
+                        }
+                    )*
+                    _ => return miniserde::#private::None,
+                },
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #[allow(deprecated, non_upper_case_globals)]
+        const _: () = {
+            impl miniserde::Serialize for #ident {
+                fn begin(&self) -> miniserde::ser::Fragment {
+                    miniserde::ser::Fragment::Map(miniserde::#private::Box::new(__Map {
+                        data: self,
+                        state: 0,
+                    }))
+                }
+            }
+
+            struct __Map<'__a> {
+                data: &'__a #ident,
+                state: miniserde::#private::usize,
+            }
+
+            impl<'__a> miniserde::ser::Map for __Map<'__a> {
+                fn next(&mut self) -> miniserde::#private::Option<(miniserde::#private::Cow<miniserde::#private::str>, &dyn miniserde::Serialize)> {
+                    loop {
+                        let __state = self.state;
+                        self.state = __state + 1;
+                        match self.data {
+                            #(#arms)*
+                        }
+                    }
+                }
+            }
         };
     })
-}
\ No newline at end of file
+}
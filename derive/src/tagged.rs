@@ -0,0 +1,47 @@
+use crate::attr::{self, Direction, RenameRule};
+use syn::{Error, Field, Fields, Result, Variant};
+
+/// The fields of a variant of an internally tagged enum, checking that the
+/// variant can be represented as an object alongside the tag.
+pub fn variant_fields<'a>(
+    variant: &'a Variant,
+    tag: &str,
+    rename_all: Option<RenameRule>,
+) -> Result<Vec<&'a Field>> {
+    let fields = match &variant.fields {
+        Fields::Named(fields) => fields.named.iter().collect(),
+        Fields::Unit => Vec::new(),
+        Fields::Unnamed(_) => {
+            return Err(Error::new_spanned(
+                variant,
+                "Invalid variant: internally tagged enums support only unit variants and variants with named fields",
+            ));
+        }
+    };
+
+    for &field in &fields {
+        let attrs = attr::get(field)?;
+        if attrs.with.is_some() {
+            return Err(Error::new_spanned(
+                field,
+                "#[serde(with = \"...\")] is not supported in enum variants",
+            ));
+        }
+        if attrs.borrow {
+            return Err(Error::new_spanned(
+                field,
+                "#[serde(borrow)] is not supported in enum variants",
+            ));
+        }
+        if attr::name_of_field_in(field, rename_all, Direction::Serialize)? == tag
+            || attr::name_of_field_in(field, rename_all, Direction::Deserialize)? == tag
+        {
+            return Err(Error::new_spanned(
+                field,
+                "field name conflicts with the enum's tag",
+            ));
+        }
+    }
+
+    Ok(fields)
+}
@@ -0,0 +1,30 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use miniserde::json::{self, Value};
+use std::str;
+
+fuzz_target!(|data: &[u8]| {
+    // Any input is allowed to fail, but never to panic, and the str and byte
+    // entry points must agree on whatever is valid UTF-8.
+    let from_slice = json::from_slice::<Value>(data);
+    let _ = json::from_slice_partial::<Value>(data);
+
+    if let Ok(value) = &from_slice {
+        let body = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+        assert!(str::from_utf8(body).is_ok());
+        let j = json::to_string(value);
+        let again = json::from_str::<Value>(&j).unwrap();
+        assert_eq!(json::to_string(&again), j);
+    }
+
+    if let Ok(j) = str::from_utf8(data) {
+        if let Ok(value) = json::from_str::<Value>(j) {
+            let from_slice = from_slice.unwrap();
+            assert_eq!(json::to_string(&from_slice), json::to_string(&value));
+        }
+        let _ = json::from_str_borrowed::<Option<&str>>(j);
+        let mut reader = json::reader(j);
+        while let Ok(Some(_)) = reader.next_token() {}
+    }
+});
@@ -1,19 +1,30 @@
-use crate::de::{Deserialize, Map, Seq, Visitor};
-use crate::error::{Error, Result};
+use crate::de::{Deserialize, DeserializeBorrowed, Map, Seq, Visitor};
+use crate::error::{Error, ErrorKind, Result};
 use crate::ignore::Ignore;
 use crate::ptr::NonuniqueBox;
-use alloc::borrow::ToOwned;
+use alloc::borrow::{Cow, ToOwned};
 use alloc::boxed::Box;
-use alloc::collections::BTreeMap;
+use alloc::collections::{BTreeMap, LinkedList, VecDeque};
+use alloc::rc::Rc;
 use alloc::string::String;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::mem::{self, ManuallyDrop, MaybeUninit};
+#[cfg(not(no_core_net))]
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+#[cfg(not(no_saturating))]
+use core::num::Saturating;
+use core::num::Wrapping;
+use core::ops::{Range, RangeInclusive};
 use core::ptr;
 use core::str::{self, FromStr};
 #[cfg(feature = "std")]
 use std::collections::HashMap;
 #[cfg(feature = "std")]
 use std::hash::{BuildHasher, Hash};
+#[cfg(all(no_core_net, feature = "std"))]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
 impl Deserialize for () {
     fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
@@ -60,6 +71,24 @@ impl Deserialize for String {
     }
 }
 
+const OVERFLOW: Error = Error.with_kind(ErrorKind::IntegerOverflow);
+
+/// Integer types reject every float. One with no fractional part outside of
+/// the type's range, such as an integer too long for `u64`, which is parsed
+/// as a float, is reported as an overflow.
+#[allow(clippy::float_cmp)] // exact comparison is the point
+fn float_to_integer(n: f64, min: f64, max: f64) -> Result<()> {
+    // Compare against one past each end. As floats, the ends of i64 and u64
+    // round to that, which also catches integers such as i64::MIN - 1 that
+    // round to the end itself.
+    let out_of_range = n <= min - 1.0 || n >= max + 1.0;
+    if out_of_range && n % 1.0 == 0.0 {
+        Err(OVERFLOW)
+    } else {
+        Err(Error)
+    }
+}
+
 macro_rules! signed {
     ($ty:ident) => {
         impl Deserialize for $ty {
@@ -72,7 +101,7 @@ macro_rules! signed {
                             self.out = Some(n as $ty);
                             Ok(())
                         } else {
-                            Err(Error)
+                            Err(OVERFLOW)
                         }
                     }
 
@@ -81,9 +110,13 @@ macro_rules! signed {
                             self.out = Some(n as $ty);
                             Ok(())
                         } else {
-                            Err(Error)
+                            Err(OVERFLOW)
                         }
                     }
+
+                    fn float(&mut self, n: f64) -> Result<()> {
+                        float_to_integer(n, $ty::MIN as f64, $ty::MAX as f64)
+                    }
                 }
 
                 Place::new(out)
@@ -104,14 +137,23 @@ macro_rules! unsigned {
                 make_place!(Place);
 
                 impl Visitor for Place<$ty> {
+                    fn negative(&mut self, n: i64) -> Result<()> {
+                        let _ = n;
+                        Err(OVERFLOW)
+                    }
+
                     fn nonnegative(&mut self, n: u64) -> Result<()> {
                         if n <= $ty::MAX as u64 {
                             self.out = Some(n as $ty);
                             Ok(())
                         } else {
-                            Err(Error)
+                            Err(OVERFLOW)
                         }
                     }
+
+                    fn float(&mut self, n: f64) -> Result<()> {
+                        float_to_integer(n, 0.0, $ty::MAX as f64)
+                    }
                 }
 
                 Place::new(out)
@@ -156,6 +198,71 @@ macro_rules! float {
 float!(f32);
 float!(f64);
 
+// The number wrappers deserialize from whatever scalar their contents do.
+macro_rules! num_wrapper {
+    ($(#[$attr:meta])* $wrapper:ident) => {
+        $(#[$attr])*
+        impl<T> Deserialize for $wrapper<T>
+        where
+            T: Deserialize,
+        {
+            fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+                make_place!(Place);
+
+                impl<T> Place<$wrapper<T>>
+                where
+                    T: Deserialize,
+                {
+                    fn wrap(&mut self, visit: impl FnOnce(&mut dyn Visitor) -> Result<()>) -> Result<()> {
+                        let mut out = None;
+                        visit(T::begin(&mut out))?;
+                        self.out = Some($wrapper(out.ok_or(Error)?));
+                        Ok(())
+                    }
+                }
+
+                impl<T> Visitor for Place<$wrapper<T>>
+                where
+                    T: Deserialize,
+                {
+                    fn null(&mut self) -> Result<()> {
+                        self.wrap(|inner| inner.null())
+                    }
+
+                    fn boolean(&mut self, b: bool) -> Result<()> {
+                        self.wrap(|inner| inner.boolean(b))
+                    }
+
+                    fn string(&mut self, s: &str) -> Result<()> {
+                        self.wrap(|inner| inner.string(s))
+                    }
+
+                    fn negative(&mut self, n: i64) -> Result<()> {
+                        self.wrap(|inner| inner.negative(n))
+                    }
+
+                    fn nonnegative(&mut self, n: u64) -> Result<()> {
+                        self.wrap(|inner| inner.nonnegative(n))
+                    }
+
+                    fn float(&mut self, n: f64) -> Result<()> {
+                        self.wrap(|inner| inner.float(n))
+                    }
+                }
+
+                Place::new(out)
+            }
+        }
+    };
+}
+
+num_wrapper!(Wrapping);
+num_wrapper!(
+    #[cfg(not(no_saturating))]
+    #[allow(clippy::incompatible_msrv)]
+    Saturating
+);
+
 impl<T> Deserialize for Box<T>
 where
     T: Deserialize,
@@ -188,6 +295,13 @@ where
                 Ok(())
             }
 
+            fn bytes(&mut self, b: &[u8]) -> Result<()> {
+                let mut out = None;
+                Deserialize::begin(&mut out).bytes(b)?;
+                self.out = Some(Box::new(out.unwrap()));
+                Ok(())
+            }
+
             fn negative(&mut self, n: i64) -> Result<()> {
                 let mut out = None;
                 Deserialize::begin(&mut out).negative(n)?;
@@ -257,6 +371,10 @@ where
                 *self.out = Some(Box::new(self.value.take().unwrap()));
                 Ok(())
             }
+
+            fn size_hint(&mut self, len: usize) {
+                self.seq.size_hint(len);
+            }
         }
 
         struct BoxMap<'a, T: 'a> {
@@ -286,12 +404,357 @@ where
                 *self.out = Some(Box::new(self.value.take().unwrap()));
                 Ok(())
             }
+
+            fn __require_all_fields(&mut self) {
+                self.map.__require_all_fields();
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+/// Deserializes through the `Seq` of a `V` and converts the result into `T`.
+struct ConvertSeq<'a, V: 'a, T: 'a> {
+    out: &'a mut Option<T>,
+    value: NonuniqueBox<Option<V>>,
+    // May borrow from self.value, so must drop first.
+    seq: ManuallyDrop<Box<dyn Seq + 'a>>,
+    convert: fn(V) -> T,
+}
+
+impl<'a, V, T> ConvertSeq<'a, V, T>
+where
+    V: Deserialize,
+{
+    fn begin(out: &'a mut Option<T>, convert: fn(V) -> T) -> Result<Box<dyn Seq + 'a>> {
+        let mut value = NonuniqueBox::new(None);
+        let ptr = unsafe { extend_lifetime!(&mut *value as &mut Option<V>) };
+        Ok(Box::new(ConvertSeq {
+            out,
+            value,
+            seq: ManuallyDrop::new(Deserialize::begin(ptr).seq()?),
+            convert,
+        }))
+    }
+}
+
+impl<'a, V: 'a, T: 'a> Drop for ConvertSeq<'a, V, T> {
+    fn drop(&mut self) {
+        unsafe { ManuallyDrop::drop(&mut self.seq) }
+    }
+}
+
+impl<'a, V, T> Seq for ConvertSeq<'a, V, T> {
+    fn element(&mut self) -> Result<&mut dyn Visitor> {
+        self.seq.element()
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.seq.finish()?;
+        *self.seq = Box::new(Ignore);
+        *self.out = Some((self.convert)(self.value.take().unwrap()));
+        Ok(())
+    }
+
+    fn size_hint(&mut self, len: usize) {
+        self.seq.size_hint(len);
+    }
+}
+
+/// Deserializes through the `Map` of a `V` and converts the result into `T`.
+struct ConvertMap<'a, V: 'a, T: 'a> {
+    out: &'a mut Option<T>,
+    value: NonuniqueBox<Option<V>>,
+    // May borrow from self.value, so must drop first.
+    map: ManuallyDrop<Box<dyn Map + 'a>>,
+    convert: fn(V) -> T,
+}
+
+impl<'a, V, T> ConvertMap<'a, V, T>
+where
+    V: Deserialize,
+{
+    fn begin(out: &'a mut Option<T>, convert: fn(V) -> T) -> Result<Box<dyn Map + 'a>> {
+        let mut value = NonuniqueBox::new(None);
+        let ptr = unsafe { extend_lifetime!(&mut *value as &mut Option<V>) };
+        Ok(Box::new(ConvertMap {
+            out,
+            value,
+            map: ManuallyDrop::new(Deserialize::begin(ptr).map()?),
+            convert,
+        }))
+    }
+}
+
+impl<'a, V: 'a, T: 'a> Drop for ConvertMap<'a, V, T> {
+    fn drop(&mut self) {
+        unsafe { ManuallyDrop::drop(&mut self.map) }
+    }
+}
+
+impl<'a, V, T> Map for ConvertMap<'a, V, T> {
+    fn key(&mut self, k: &str) -> Result<&mut dyn Visitor> {
+        self.map.key(k)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.map.finish()?;
+        *self.map = Box::new(Ignore);
+        *self.out = Some((self.convert)(self.value.take().unwrap()));
+        Ok(())
+    }
+
+    fn __require_all_fields(&mut self) {
+        self.map.__require_all_fields();
+    }
+}
+
+/// Always deserializes to `Cow::Owned`, by way of the owned type's impl.
+impl<'a, T> Deserialize for Cow<'a, T>
+where
+    T: ?Sized + ToOwned,
+    T::Owned: Deserialize,
+{
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl<'a, T> Place<Cow<'a, T>>
+        where
+            T: ?Sized + ToOwned,
+            T::Owned: Deserialize,
+        {
+            fn wrap(&mut self, visit: impl FnOnce(&mut dyn Visitor) -> Result<()>) -> Result<()> {
+                let mut out = None;
+                visit(T::Owned::begin(&mut out))?;
+                self.out = Some(Cow::Owned(out.ok_or(Error)?));
+                Ok(())
+            }
+        }
+
+        impl<'a, T> Visitor for Place<Cow<'a, T>>
+        where
+            T: ?Sized + ToOwned,
+            T::Owned: Deserialize,
+        {
+            fn null(&mut self) -> Result<()> {
+                self.wrap(|inner| inner.null())
+            }
+
+            fn boolean(&mut self, b: bool) -> Result<()> {
+                self.wrap(|inner| inner.boolean(b))
+            }
+
+            fn string(&mut self, s: &str) -> Result<()> {
+                self.wrap(|inner| inner.string(s))
+            }
+
+            fn bytes(&mut self, b: &[u8]) -> Result<()> {
+                self.wrap(|inner| inner.bytes(b))
+            }
+
+            fn negative(&mut self, n: i64) -> Result<()> {
+                self.wrap(|inner| inner.negative(n))
+            }
+
+            fn nonnegative(&mut self, n: u64) -> Result<()> {
+                self.wrap(|inner| inner.nonnegative(n))
+            }
+
+            fn float(&mut self, n: f64) -> Result<()> {
+                self.wrap(|inner| inner.float(n))
+            }
+
+            fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+                ConvertSeq::begin(&mut self.out, Cow::Owned)
+            }
+
+            fn map(&mut self) -> Result<Box<dyn Map + '_>> {
+                ConvertMap::begin(&mut self.out, Cow::Owned)
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+impl Deserialize for Box<str> {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl Visitor for Place<Box<str>> {
+            fn string(&mut self, s: &str) -> Result<()> {
+                self.out = Some(Box::from(s));
+                Ok(())
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+impl Deserialize for Rc<str> {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl Visitor for Place<Rc<str>> {
+            fn string(&mut self, s: &str) -> Result<()> {
+                self.out = Some(Rc::from(s));
+                Ok(())
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl Deserialize for Arc<str> {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl Visitor for Place<Arc<str>> {
+            fn string(&mut self, s: &str) -> Result<()> {
+                self.out = Some(Arc::from(s));
+                Ok(())
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+macro_rules! deserialize_from_str {
+    ($($ty:ident)*) => {
+        $(
+            #[cfg(any(not(no_core_net), feature = "std"))]
+            #[allow(clippy::incompatible_msrv)]
+            impl Deserialize for $ty {
+                fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+                    make_place!(Place);
+
+                    impl Visitor for Place<$ty> {
+                        fn string(&mut self, s: &str) -> Result<()> {
+                            self.out = Some(s.parse().map_err(|_| Error)?);
+                            Ok(())
+                        }
+                    }
+
+                    Place::new(out)
+                }
+            }
+        )*
+    };
+}
+
+deserialize_from_str!(IpAddr Ipv4Addr Ipv6Addr);
+
+impl<T> Deserialize for Box<[T]>
+where
+    T: Deserialize,
+{
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl<T> Visitor for Place<Box<[T]>>
+        where
+            T: Deserialize,
+        {
+            fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+                ConvertSeq::begin(&mut self.out, Vec::into_boxed_slice)
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+impl<T> Deserialize for Rc<[T]>
+where
+    T: Deserialize,
+{
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl<T> Visitor for Place<Rc<[T]>>
+        where
+            T: Deserialize,
+        {
+            fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+                ConvertSeq::begin(&mut self.out, Rc::<[T]>::from as fn(Vec<T>) -> _)
+            }
         }
 
         Place::new(out)
     }
 }
 
+#[cfg(target_has_atomic = "ptr")]
+impl<T> Deserialize for Arc<[T]>
+where
+    T: Deserialize,
+{
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl<T> Visitor for Place<Arc<[T]>>
+        where
+            T: Deserialize,
+        {
+            fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+                ConvertSeq::begin(&mut self.out, Arc::<[T]>::from as fn(Vec<T>) -> _)
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+impl<T> Deserialize for VecDeque<T>
+where
+    T: Deserialize,
+{
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl<T> Visitor for Place<VecDeque<T>>
+        where
+            T: Deserialize,
+        {
+            fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+                ConvertSeq::begin(&mut self.out, VecDeque::from as fn(Vec<T>) -> _)
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+impl<T> Deserialize for LinkedList<T>
+where
+    T: Deserialize,
+{
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl<T> Visitor for Place<LinkedList<T>>
+        where
+            T: Deserialize,
+        {
+            fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+                ConvertSeq::begin(&mut self.out, |vec: Vec<T>| vec.into_iter().collect())
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+/// A missing struct field is deserialized as `None`, and so is `null`.
+///
+/// For `Option<Option<T>>`, `null` is instead deserialized as `Some(None)`,
+/// so that a field that was explicitly set to null can be told apart from one
+/// that was left out.
 impl<T> Deserialize for Option<T>
 where
     T: Deserialize,
@@ -309,7 +772,8 @@ where
             T: Deserialize,
         {
             fn null(&mut self) -> Result<()> {
-                self.out = Some(None);
+                // `T::default()` is `Some(None)` only if T is itself an Option.
+                self.out = Some(T::default());
                 Ok(())
             }
 
@@ -323,6 +787,11 @@ where
                 Deserialize::begin(self.out.as_mut().unwrap()).string(s)
             }
 
+            fn bytes(&mut self, b: &[u8]) -> Result<()> {
+                self.out = Some(None);
+                Deserialize::begin(self.out.as_mut().unwrap()).bytes(b)
+            }
+
             fn negative(&mut self, n: i64) -> Result<()> {
                 self.out = Some(None);
                 Deserialize::begin(self.out.as_mut().unwrap()).negative(n)
@@ -353,6 +822,118 @@ where
     }
 }
 
+/// Borrows strings written without escapes, and fails on any other string.
+unsafe impl<'de: 'a, 'a> DeserializeBorrowed<'de> for &'a str {
+    unsafe fn begin_borrowed(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl<'a> Visitor for Place<&'a str> {
+            unsafe fn __borrowed_string(&mut self, s: &str) -> Result<()> {
+                // The string lives for 'de, which outlives 'a.
+                self.out = Some(unsafe { &*(s as *const str) });
+                Ok(())
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+/// Borrows strings written without escapes, and owns a copy of any other.
+unsafe impl<'de: 'a, 'a> DeserializeBorrowed<'de> for Cow<'a, str> {
+    unsafe fn begin_borrowed(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl<'a> Visitor for Place<Cow<'a, str>> {
+            fn string(&mut self, s: &str) -> Result<()> {
+                self.out = Some(Cow::Owned(s.to_owned()));
+                Ok(())
+            }
+
+            unsafe fn __borrowed_string(&mut self, s: &str) -> Result<()> {
+                // The string lives for 'de, which outlives 'a.
+                self.out = Some(Cow::Borrowed(unsafe { &*(s as *const str) }));
+                Ok(())
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+unsafe impl<'de, T> DeserializeBorrowed<'de> for Option<T>
+where
+    T: DeserializeBorrowed<'de>,
+{
+    #[inline]
+    fn default() -> Option<Self> {
+        Some(None)
+    }
+
+    unsafe fn begin_borrowed(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl<'de, T> Place<Option<T>>
+        where
+            T: DeserializeBorrowed<'de>,
+        {
+            fn inner(&mut self) -> &mut dyn Visitor {
+                self.out = Some(None);
+                // This place was itself begun borrowing for 'de.
+                unsafe { <T as DeserializeBorrowed<'de>>::begin_borrowed(self.out.as_mut().unwrap()) }
+            }
+        }
+
+        impl<'de, T> Visitor for Place<Option<T>>
+        where
+            T: DeserializeBorrowed<'de>,
+        {
+            fn null(&mut self) -> Result<()> {
+                self.out = Some(<T as DeserializeBorrowed<'de>>::default());
+                Ok(())
+            }
+
+            fn boolean(&mut self, b: bool) -> Result<()> {
+                self.inner().boolean(b)
+            }
+
+            fn string(&mut self, s: &str) -> Result<()> {
+                self.inner().string(s)
+            }
+
+            fn bytes(&mut self, b: &[u8]) -> Result<()> {
+                self.inner().bytes(b)
+            }
+
+            fn negative(&mut self, n: i64) -> Result<()> {
+                self.inner().negative(n)
+            }
+
+            fn nonnegative(&mut self, n: u64) -> Result<()> {
+                self.inner().nonnegative(n)
+            }
+
+            fn float(&mut self, n: f64) -> Result<()> {
+                self.inner().float(n)
+            }
+
+            fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+                self.inner().seq()
+            }
+
+            fn map(&mut self) -> Result<Box<dyn Map + '_>> {
+                self.inner().map()
+            }
+
+            unsafe fn __borrowed_string(&mut self, s: &str) -> Result<()> {
+                unsafe { self.inner().__borrowed_string(s) }
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
 impl<A, B> Deserialize for (A, B)
 where
     A: Deserialize,
@@ -408,6 +989,146 @@ where
     }
 }
 
+/// Builds a range from the `start` and `end` keys of an object.
+struct RangeBuilder<'a, T: 'a, R: 'a> {
+    out: &'a mut Option<R>,
+    start: Option<T>,
+    end: Option<T>,
+    make: fn(T, T) -> R,
+}
+
+impl<'a, T: 'a, R: 'a> RangeBuilder<'a, T, R> {
+    fn begin(out: &'a mut Option<R>, make: fn(T, T) -> R) -> Box<dyn Map + 'a>
+    where
+        T: Deserialize,
+    {
+        Box::new(RangeBuilder {
+            out,
+            start: None,
+            end: None,
+            make,
+        })
+    }
+}
+
+impl<'a, T, R> Map for RangeBuilder<'a, T, R>
+where
+    T: Deserialize,
+{
+    fn key(&mut self, k: &str) -> Result<&mut dyn Visitor> {
+        match k {
+            "start" => Ok(Deserialize::begin(&mut self.start)),
+            "end" => Ok(Deserialize::begin(&mut self.end)),
+            _ => Ok(<dyn Visitor>::ignore()),
+        }
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if let (Some(start), Some(end)) = (self.start.take(), self.end.take()) {
+            *self.out = Some((self.make)(start, end));
+            Ok(())
+        } else {
+            Err(Error)
+        }
+    }
+}
+
+impl<T> Deserialize for Range<T>
+where
+    T: Deserialize,
+{
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl<T> Visitor for Place<Range<T>>
+        where
+            T: Deserialize,
+        {
+            fn map(&mut self) -> Result<Box<dyn Map + '_>> {
+                Ok(RangeBuilder::begin(&mut self.out, |start, end| start..end))
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+impl<T> Deserialize for RangeInclusive<T>
+where
+    T: Deserialize,
+{
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl<T> Visitor for Place<RangeInclusive<T>>
+        where
+            T: Deserialize,
+        {
+            fn map(&mut self) -> Result<Box<dyn Map + '_>> {
+                Ok(RangeBuilder::begin(&mut self.out, RangeInclusive::new))
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+/// Deserialized from an object with exactly one key, either `Ok` or `Err`.
+impl<T, E> Deserialize for core::result::Result<T, E>
+where
+    T: Deserialize,
+    E: Deserialize,
+{
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl<T, E> Visitor for Place<core::result::Result<T, E>>
+        where
+            T: Deserialize,
+            E: Deserialize,
+        {
+            fn map(&mut self) -> Result<Box<dyn Map + '_>> {
+                Ok(Box::new(ResultBuilder {
+                    out: &mut self.out,
+                    ok: None,
+                    err: None,
+                }))
+            }
+        }
+
+        struct ResultBuilder<'a, T: 'a, E: 'a> {
+            out: &'a mut Option<core::result::Result<T, E>>,
+            ok: Option<T>,
+            err: Option<E>,
+        }
+
+        impl<'a, T, E> Map for ResultBuilder<'a, T, E>
+        where
+            T: Deserialize,
+            E: Deserialize,
+        {
+            fn key(&mut self, k: &str) -> Result<&mut dyn Visitor> {
+                match k {
+                    "Ok" => Ok(Deserialize::begin(&mut self.ok)),
+                    "Err" => Ok(Deserialize::begin(&mut self.err)),
+                    _ => Err(Error),
+                }
+            }
+
+            fn finish(&mut self) -> Result<()> {
+                *self.out = Some(match (self.ok.take(), self.err.take()) {
+                    (Some(ok), None) => Ok(ok),
+                    (None, Some(err)) => Err(err),
+                    _ => return Err(Error),
+                });
+                Ok(())
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
 impl<T> Deserialize for Vec<T>
 where
     T: Deserialize,
@@ -456,6 +1177,10 @@ where
                 *self.out = Some(mem::take(&mut self.vec));
                 Ok(())
             }
+
+            fn size_hint(&mut self, len: usize) {
+                self.vec.reserve(len);
+            }
         }
 
         Place::new(out)
@@ -537,6 +1262,8 @@ where
     }
 }
 
+/// Deserialized from a JSON object, parsing each key with `FromStr`. A key that
+/// does not parse is an error.
 #[cfg(feature = "std")]
 impl<K, V, H> Deserialize for HashMap<K, V, H>
 where
@@ -609,6 +1336,8 @@ where
     }
 }
 
+/// Deserialized from a JSON object, parsing each key with `FromStr`. A key that
+/// does not parse is an error.
 impl<K, V> Deserialize for BTreeMap<K, V>
 where
     K: FromStr + Ord,
@@ -195,6 +195,7 @@ mod impls;
 
 use crate::error::{Error, Result};
 use alloc::boxed::Box;
+use core::str;
 
 /// Trait for data structures that can be deserialized from a JSON string.
 ///
@@ -245,6 +246,20 @@ pub trait Visitor {
         Err(Error)
     }
 
+    /// Raw bytes, from a format that has a binary type or for a visitor that
+    /// is driven directly.
+    ///
+    /// The default implementation passes valid UTF-8 on to
+    /// [`string`][Self::string] and fails on anything else. The JSON
+    /// deserializer never calls this; JSON strings always go to `string`.
+    #[allow(clippy::option_if_let_else)]
+    fn bytes(&mut self, b: &[u8]) -> Result<()> {
+        match str::from_utf8(b) {
+            Ok(s) => self.string(s),
+            Err(_) => Err(Error),
+        }
+    }
+
     fn negative(&mut self, n: i64) -> Result<()> {
         let _ = n;
         Err(Error)
@@ -267,6 +282,49 @@ pub trait Visitor {
     fn map(&mut self) -> Result<Box<dyn Map + '_>> {
         Err(Error)
     }
+
+    // Not public API. Called instead of `string` by `json::from_str_borrowed`
+    // for a string that appears in the input without escapes. Visitors handed
+    // out by DeserializeBorrowed impls may keep it for the input's lifetime.
+    #[doc(hidden)]
+    #[inline]
+    unsafe fn __borrowed_string(&mut self, s: &str) -> Result<()> {
+        self.string(s)
+    }
+}
+
+/// Trait for data structures that can be deserialized from a JSON string by
+/// borrowing parts of it, rather than copying them, for the lifetime `'de`.
+///
+/// This is implemented for `&str`, `Cow<str>`, `Option` of any of these, and
+/// by `#[derive(Deserialize)]` for structs that have fields marked
+/// `#[serde(borrow)]`. Use it through [`json::from_str_borrowed`].
+///
+/// A `&str` can only borrow a string that is written in the input without
+/// escape sequences, and fails to deserialize otherwise. A `Cow<str>` borrows
+/// where it can and holds an owned copy of any string with escapes.
+///
+/// [`json::from_str_borrowed`]: crate::json::from_str_borrowed
+///
+/// # Safety
+///
+/// The visitor returned by `begin_borrowed` may be given strings that live for
+/// `'de` through a hidden method of `Visitor`, and may keep them for no longer
+/// than that. The visitors it hands out for nested values must come from
+/// `Deserialize` impls or from `DeserializeBorrowed<'de>` impls.
+pub unsafe trait DeserializeBorrowed<'de>: Sized {
+    /// # Safety
+    ///
+    /// Any string that the caller passes to the returned visitor, or to the
+    /// visitors it hands out, as a borrowed string must live for `'de`.
+    unsafe fn begin_borrowed(out: &mut Option<Self>) -> &mut dyn Visitor;
+
+    // Not public API. As for Deserialize, only intended for Option<T>.
+    #[doc(hidden)]
+    #[inline]
+    fn default() -> Option<Self> {
+        None
+    }
 }
 
 /// Trait that can hand out places to write sequence elements.
@@ -275,6 +333,18 @@ pub trait Visitor {
 pub trait Seq {
     fn element(&mut self) -> Result<&mut dyn Visitor>;
     fn finish(&mut self) -> Result<()>;
+
+    /// Called before the first element by deserializers that know how many
+    /// elements follow, so that the collection can be allocated up front.
+    ///
+    /// This is only a hint, and most sequences never receive it. JSON does not
+    /// write the length of an array ahead of its elements, and counting them
+    /// with an extra pass over the input costs more than growing the
+    /// collection, so `json::from_str` and `json::from_slice` never call it.
+    /// `json::from_value` does, since a `json::Array` knows its length.
+    fn size_hint(&mut self, len: usize) {
+        let _ = len;
+    }
 }
 
 /// Trait that can hand out places to write values of a map.
@@ -283,4 +353,10 @@ pub trait Seq {
 pub trait Map {
     fn key(&mut self, k: &str) -> Result<&mut dyn Visitor>;
     fn finish(&mut self) -> Result<()>;
+
+    /// Called before the first key when the JSON deserializer is configured
+    /// with `require_all_fields`. Derived impls stop filling in missing fields
+    /// from `#[serde(default)]` after this.
+    #[doc(hidden)]
+    fn __require_all_fields(&mut self) {}
 }
@@ -1,18 +1,107 @@
 use core::fmt::{self, Display};
 
-/// Error type when deserialization fails.
+/// Error type when deserialization fails, or when serialization is rejected by
+/// a strict [`json::Serializer`][crate::json::Serializer] setting.
 ///
-/// Miniserde errors contain no information about what went wrong. **If you need
-/// more than no information, use Serde.**
-#[derive(Copy, Clone, Debug)]
-pub struct Error;
+/// Miniserde errors contain almost no information about what went wrong. The
+/// only details are the byte offset into the input at which the JSON parser
+/// gave up, when known, and for a few specific failures an [`ErrorKind`].
+/// **If you need more than that, use Serde.**
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    offset: Option<usize>,
+    kind: ErrorKind,
+}
+
+/// The few kinds of error that miniserde tells apart.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Any error without a more specific kind.
+    Other,
+    /// A `\u` escape in a JSON string encodes half of a UTF-16 surrogate pair
+    /// without the other half: a low surrogate on its own, or a high surrogate
+    /// that is not followed by a `\u` escape of a low surrogate.
+    InvalidSurrogate,
+    /// A number is malformed: it has a leading `+`, a leading zero, or no
+    /// digits before or after its `.`, `-` or exponent, as in `+1`, `01`,
+    /// `.5`, `1.` or `1e`.
+    InvalidNumber,
+    /// A number is out of range for the integer type being deserialized, as
+    /// in `256` for `u8`, `-1` for `u32` or `18446744073709551616` for `u64`.
+    /// Only numbers with no fractional part count; others are `Other`.
+    IntegerOverflow,
+}
+
+/// An error without any position.
+///
+/// Spelled like a unit struct so that `Err(Error)` can be written by
+/// Deserialize and Visitor impls, which have no position to report.
+#[allow(non_upper_case_globals)]
+pub const Error: Error = Error {
+    offset: None,
+    kind: ErrorKind::Other,
+};
 
 /// Result type returned by deserialization functions.
 pub type Result<T> = core::result::Result<T, Error>;
 
+impl Error {
+    /// Byte offset into the JSON input at which deserialization failed.
+    ///
+    /// For trailing characters after a complete top-level value, this is the
+    /// offset of the first such character.
+    pub const fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// What kind of error this is, if it is one of the few that are told
+    /// apart.
+    pub const fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub(crate) const fn at_offset(self, offset: usize) -> Self {
+        match self.offset {
+            Some(_) => self,
+            None => Self {
+                offset: Some(offset),
+                kind: self.kind,
+            },
+        }
+    }
+
+    pub(crate) const fn add_offset(self, base: usize) -> Self {
+        match self.offset {
+            Some(offset) => Self {
+                offset: Some(base + offset),
+                kind: self.kind,
+            },
+            None => self,
+        }
+    }
+
+    pub(crate) const fn with_kind(self, kind: ErrorKind) -> Self {
+        Self {
+            offset: self.offset,
+            kind,
+        }
+    }
+}
+
 impl Display for Error {
+    #[allow(clippy::option_if_let_else)]
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("miniserde error")
+        formatter.write_str(match self.kind {
+            ErrorKind::Other => "miniserde error",
+            ErrorKind::InvalidSurrogate => "miniserde error: unpaired UTF-16 surrogate",
+            ErrorKind::InvalidNumber => "miniserde error: malformed number",
+            ErrorKind::IntegerOverflow => "miniserde error: integer out of range",
+        })?;
+        match self.offset {
+            Some(offset) => write!(formatter, " at offset {}", offset),
+            None => Ok(()),
+        }
     }
 }
 
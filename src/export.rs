@@ -7,6 +7,8 @@ pub use core::result::Result::{Err, Ok};
 #[doc(hidden)]
 pub use core::ptr;
 #[doc(hidden)]
+pub use core::str::FromStr;
+#[doc(hidden)]
 pub use core::unreachable;
 
 #[doc(hidden)]
@@ -21,3 +23,68 @@ pub type String = alloc::string::String;
 pub type str = core::primitive::str;
 #[doc(hidden)]
 pub type usize = core::primitive::usize;
+
+/// Serializes a field of a derived struct through the `serialize` function of
+/// its `#[serde(with = "...")]` module.
+#[doc(hidden)]
+pub struct SerializeWith<'a, T: ?core::marker::Sized> {
+    pub value: &'a T,
+    pub serialize: fn(&T) -> crate::ser::Fragment,
+}
+
+impl<'a, T: ?core::marker::Sized> crate::ser::Serialize for SerializeWith<'a, T> {
+    fn begin(&self) -> crate::ser::Fragment {
+        (self.serialize)(self.value)
+    }
+}
+
+#[doc(hidden)]
+pub use crate::tagged::begin_tagged;
+
+/// The value of a `#[serde(default = "...")]` path, which may name either a
+/// function to call or a const. Method resolution picks `DefaultFromFn` for
+/// anything callable with no arguments, because it matches the receiver
+/// without the extra autoref that `DefaultFromConst` needs.
+#[doc(hidden)]
+pub struct DefaultPath<T>(core::cell::Cell<Option<T>>);
+
+impl<T> DefaultPath<T> {
+    pub const fn new(path: T) -> Self {
+        Self(core::cell::Cell::new(Some(path)))
+    }
+
+    fn take(&self) -> T {
+        self.0.take().unwrap()
+    }
+}
+
+#[doc(hidden)]
+pub trait DefaultFromFn {
+    type Value;
+    fn __default_value(self) -> Self::Value;
+}
+
+impl<F, T> DefaultFromFn for &DefaultPath<F>
+where
+    F: FnOnce() -> T,
+{
+    type Value = T;
+
+    fn __default_value(self) -> T {
+        (self.take())()
+    }
+}
+
+#[doc(hidden)]
+pub trait DefaultFromConst {
+    type Value;
+    fn __default_value(self) -> Self::Value;
+}
+
+impl<T> DefaultFromConst for &&DefaultPath<T> {
+    type Value = T;
+
+    fn __default_value(self) -> T {
+        self.take()
+    }
+}
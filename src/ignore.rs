@@ -30,6 +30,10 @@ impl Visitor for Ignore {
         Ok(())
     }
 
+    fn bytes(&mut self, _b: &[u8]) -> Result<()> {
+        Ok(())
+    }
+
     fn negative(&mut self, _n: i64) -> Result<()> {
         Ok(())
     }
@@ -9,9 +9,10 @@ use core::fmt::{self, Debug};
 use core::mem::{self, ManuallyDrop};
 use core::ops::{Deref, DerefMut};
 use core::ptr;
+use core::slice;
 
 /// A `Vec<Value>` with a non-recursive drop impl.
-#[derive(Default)]
+#[derive(Default, PartialEq)]
 pub struct Array {
     inner: Vec<Value>,
 }
@@ -31,6 +32,34 @@ impl Array {
     pub const fn new() -> Self {
         Self { inner: Vec::new() }
     }
+
+    /// Construct an empty array with room for at least `capacity` elements
+    /// before it reallocates, like `Vec::with_capacity`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Remove and return the element at `index`, shifting all elements after
+    /// it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds, like `Vec::remove`.
+    pub fn remove(&mut self, index: usize) -> Value {
+        self.inner.remove(index)
+    }
+
+    /// Iterate over the elements in order.
+    pub fn iter(&self) -> slice::Iter<Value> {
+        self.inner.iter()
+    }
+
+    /// Iterate mutably over the elements in order.
+    pub fn iter_mut(&mut self) -> slice::IterMut<Value> {
+        self.inner.iter_mut()
+    }
 }
 
 impl Deref for Array {
@@ -149,6 +178,10 @@ impl Deserialize for Array {
                 *self.out = Some(mem::replace(&mut self.array, Array::new()));
                 Ok(())
             }
+
+            fn size_hint(&mut self, len: usize) {
+                self.array.reserve(len);
+            }
         }
 
         Place::new(out)
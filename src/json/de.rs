@@ -1,14 +1,17 @@
 use self::Event::*;
-use crate::de::{Deserialize, Map, Seq, Visitor};
-use crate::error::{Error, Result};
+use crate::de::{Deserialize, DeserializeBorrowed, Map, Seq, Visitor};
+use crate::error::{Error, ErrorKind, Result};
 use crate::json::{Number, Value};
 use crate::ptr::NonuniqueBox;
-use alloc::collections::btree_map;
-use alloc::string::String;
+use alloc::collections::{btree_map, BTreeSet};
+use alloc::string::{String, ToString};
 use alloc::vec::{self, Vec};
 use core::char;
+use core::marker::PhantomData;
+use core::mem;
 use core::ptr::NonNull;
 use core::str;
+#[cfg(all(feature = "std", target_arch = "x86_64", not(feature = "portable_simd")))]
 use std::is_x86_feature_detected;
 
 /// Deserialize a JSON string into any deserializable type.
@@ -35,45 +38,969 @@ pub fn from_str<T>(j: &str) -> Result<T>
 where
     T: Deserialize,
 {
-    let mut out = None;
-    from_slice_impl(j.as_bytes(), false, T::begin(&mut out))?;
-    out.ok_or(Error)
+    Deserializer::new().from_str(j)
 }
 
+/// Deserialize a JSON string into a type that borrows strings from it.
+///
+/// Strings are borrowed only by fields marked `#[serde(borrow)]`, and by the
+/// other [`DeserializeBorrowed`] impls; everything else is deserialized as by
+/// [`from_str`].
+///
+/// ```rust
+/// use miniserde::{json, Deserialize};
+///
+/// #[derive(Deserialize, Debug)]
+/// struct Example<'a> {
+///     code: u32,
+///     #[serde(borrow)]
+///     message: &'a str,
+/// }
+///
+/// fn main() -> miniserde::Result<()> {
+///     let j = r#" {"code": 200, "message": "reminiscent of Serde"} "#;
+///
+///     let out: Example = json::from_str_borrowed(j)?;
+///     assert_eq!(out.message, "reminiscent of Serde");
+///
+///     Ok(())
+/// }
+/// ```
+pub fn from_str_borrowed<'de, T>(j: &'de str) -> Result<T>
+where
+    T: DeserializeBorrowed<'de>,
+{
+    Deserializer::new().from_str_borrowed(j)
+}
+
+/// Deserialize JSON bytes into any deserializable type.
+///
+/// A leading UTF-8 byte order mark is skipped. Input starting with a UTF-16
+/// or UTF-32 byte order mark is rejected with an error at offset 0.
 pub fn from_slice<T>(j: &[u8]) -> Result<T>
 where
     T: Deserialize,
 {
-    let mut out = None;
-    from_slice_impl(j, true, T::begin(&mut out))?;
-    out.ok_or(Error)
+    Deserializer::new().from_slice(j)
+}
+
+/// Deserialize the JSON value at the start of some bytes, returning it along
+/// with the number of bytes it took up.
+///
+/// Unlike [`from_slice`], anything after the value is left alone rather than
+/// treated as an error, so JSON framed inside a larger stream can be read
+/// without knowing its length in advance. The byte count includes any
+/// whitespace and byte order mark before the value but none after it.
+///
+/// ```rust
+/// use miniserde::json;
+///
+/// fn main() -> miniserde::Result<()> {
+///     let input = b"[1,2,3]\x00\x01\x02";
+///
+///     let (out, len): (Vec<u8>, usize) = json::from_slice_partial(input)?;
+///     assert_eq!(out, [1, 2, 3]);
+///     assert_eq!(&input[len..], b"\x00\x01\x02");
+///
+///     Ok(())
+/// }
+/// ```
+pub fn from_slice_partial<T>(j: &[u8]) -> Result<(T, usize)>
+where
+    T: Deserialize,
+{
+    Deserializer::new().from_slice_partial(j)
+}
+
+/// Deserialize a stream of newline-delimited JSON values, also known as JSON
+/// Lines or NDJSON.
+///
+/// Each non-blank line of the input is deserialized as one value, lazily as the
+/// iterator is advanced. A malformed line produces an error for that line only;
+/// iteration continues with the next line. Offsets in errors are relative to
+/// the start of the whole input.
+///
+/// ```rust
+/// use miniserde::{json, Deserialize};
+///
+/// #[derive(Deserialize, Debug)]
+/// struct Event {
+///     id: u32,
+/// }
+///
+/// let j = "{\"id\": 1}\n{\"id\": 2}\n";
+/// for event in json::iter_from_str::<Event>(j) {
+///     println!("{:?}", event.unwrap());
+/// }
+/// ```
+pub fn iter_from_str<T>(j: &str) -> JsonLines<T>
+where
+    T: Deserialize,
+{
+    Deserializer::new().iter_from_str(j)
+}
+
+/// Iterator over the values of a newline-delimited JSON stream.
+///
+/// This struct is created by [`iter_from_str`].
+pub struct JsonLines<'a, T> {
+    rest: &'a str,
+    offset: usize,
+    config: Deserializer,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> Iterator for JsonLines<'a, T>
+where
+    T: Deserialize,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+            let (line, rest) = match self.rest.find('\n') {
+                Some(newline) => (&self.rest[..newline], &self.rest[newline + 1..]),
+                None => (self.rest, ""),
+            };
+            let start = self.offset;
+            self.offset += self.rest.len() - rest.len();
+            self.rest = rest;
+
+            if line.bytes().all(|b| matches!(b, b' ' | b'\t' | b'\r')) {
+                continue;
+            }
+            return Some(self.config.from_str(line).map_err(|error| error.add_offset(start)));
+        }
+    }
+}
+
+/// Deserialize a sequence of JSON values that follow one another in the input,
+/// such as `{"a":1}{"a":2}[1,2,3]`.
+///
+/// Values may be separated by whitespace but need not be. Each value is
+/// deserialized lazily as the iterator is advanced. After a value fails to
+/// deserialize, the iterator yields that error and then ends, since there is
+/// no reliable way to tell where the next value would start.
+///
+/// ```rust
+/// use miniserde::json::{self, Value};
+///
+/// let j = r#"{"a":1} {"a":2}[1,2,3]"#;
+/// let values: Vec<Value> = json::stream_from_str(j).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(values.len(), 3);
+/// ```
+pub fn stream_from_str<T>(j: &str) -> ValueStream<T>
+where
+    T: Deserialize,
+{
+    Deserializer::new().stream_from_str(j)
+}
+
+/// Iterator over a sequence of concatenated JSON values.
+///
+/// This struct is created by [`stream_from_str`].
+pub struct ValueStream<'a, T> {
+    input: &'a str,
+    pos: usize,
+    config: Deserializer,
+    failed: bool,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> ValueStream<'a, T> {
+    /// Byte offset in the input just past the last value deserialized so far.
+    pub const fn byte_offset(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<'a, T> Iterator for ValueStream<'a, T>
+where
+    T: Deserialize,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let mut out = None;
+        let mut de = Scanner {
+            input: self.input.as_bytes(),
+            pos: self.pos,
+            token_start: 0,
+            buffer: Vec::new(),
+            stack: Vec::new(),
+            validate_utf8: false,
+            borrow: false,
+            config: self.config,
+        };
+        de.skip_whitespace_and_peek_class()?;
+
+        let result = de.parse_value(T::begin(&mut out));
+        let pos = de.pos;
+        drop(de);
+        match result.and_then(|()| out.ok_or(Error)) {
+            Ok(value) => {
+                self.pos = pos;
+                Some(Ok(value))
+            }
+            Err(error) => {
+                self.failed = true;
+                Some(Err(error.at_offset(pos)))
+            }
+        }
+    }
+}
+
+/// Read a JSON document one token at a time, without deserializing it into any
+/// type.
+///
+/// This is a building block for custom parsers, such as one that looks for a
+/// single key in a large document. Tokens are produced in document order and
+/// strings borrow from the input or from the reader's scratch space, so
+/// nothing is allocated per token.
+///
+/// ```rust
+/// use miniserde::json::{self, Token};
+///
+/// let mut reader = json::reader(r#"{"id": 7, "tags": ["a"]}"#);
+/// let mut tokens = Vec::new();
+/// while let Some(token) = reader.next_token().unwrap() {
+///     tokens.push(format!("{:?}", token));
+/// }
+/// assert_eq!(
+///     tokens,
+///     [
+///         "MapStart", r#"Key("id")"#, "Nonnegative(7)", r#"Key("tags")"#,
+///         "SeqStart", r#"Str("a")"#, "SeqEnd", "MapEnd",
+///     ],
+/// );
+/// ```
+pub fn reader(j: &str) -> Reader {
+    Deserializer::new().reader(j)
+}
+
+/// One token of a JSON document, as produced by a [`Reader`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Token<'a> {
+    Null,
+    Bool(bool),
+    Str(&'a str),
+    Negative(i64),
+    Nonnegative(u64),
+    Float(f64),
+    SeqStart,
+    SeqEnd,
+    MapStart,
+    MapEnd,
+    /// The key of the next entry of an object, which is followed by the
+    /// tokens of its value.
+    Key(&'a str),
+}
+
+/// Pull parser over the tokens of one JSON document.
+///
+/// This struct is created by [`reader`]. The document is checked for
+/// well-formedness as it is read, and characters other than whitespace after
+/// the top-level value are an error. After an error the reader yields no more
+/// tokens.
+pub struct Reader<'a> {
+    input: &'a str,
+    scanner: Scanner<'a, 'static>,
+    frames: Vec<Frame>,
+    expect: Expect,
+    /// Byte range in the input of the last string or key read, or `None` if it
+    /// had escapes and was decoded into the scanner's buffer.
+    string: Option<(usize, usize)>,
+    /// Set during `skip_value`, whose strings and numbers are scanned past
+    /// without being decoded. Their tokens are only placeholders.
+    skipping: bool,
+}
+
+enum Frame {
+    /// The number of elements read so far.
+    Seq(usize),
+    Map(MapKeys),
+}
+
+#[derive(Copy, Clone)]
+enum Expect {
+    /// Nothing has been read yet.
+    Start,
+    /// A value, after the key of an object entry.
+    Value,
+    /// The first element or entry, right after `[` or `{`.
+    FirstOrEnd,
+    /// A comma or the end of the array or object.
+    CommaOrEnd,
+    /// The end of the input, after the top-level value.
+    End,
+    /// Nothing, because the input is finished or an error occurred.
+    Done,
+}
+
+impl<'a> Reader<'a> {
+    /// The next token, or `None` at the end of the document.
+    pub fn next_token(&mut self) -> Result<Option<Token>> {
+        let token = self.advance_or_fail()?;
+        Ok(token.map(|token| match token {
+            Token::Str(_) => Token::Str(self.string()),
+            Token::Key(_) => Token::Key(self.string()),
+            token => token,
+        }))
+    }
+
+    /// Skip the value that comes next, with everything nested in it, without
+    /// producing its tokens.
+    ///
+    /// This is allowed where a value may come next: at the start of the
+    /// document, after a key, or before an element of an array. Anywhere else,
+    /// such as before a key or at the end of an array, it is an error.
+    ///
+    /// Strings and numbers in the skipped value are checked for the JSON
+    /// syntax but not decoded, which makes skipping cheaper than reading.
+    /// This means an escape such as `\uD800`, which is a lone surrogate and an
+    /// error when read, is accepted when skipped.
+    ///
+    /// ```rust
+    /// use miniserde::json::{self, Token};
+    ///
+    /// let mut reader = json::reader(r#"{"skip": {"a": [1, 2]}, "keep": 3}"#);
+    /// assert_eq!(reader.next_token().unwrap(), Some(Token::MapStart));
+    /// assert_eq!(reader.next_token().unwrap(), Some(Token::Key("skip")));
+    /// reader.skip_value().unwrap();
+    /// assert_eq!(reader.next_token().unwrap(), Some(Token::Key("keep")));
+    /// ```
+    pub fn skip_value(&mut self) -> Result<()> {
+        self.skipping = true;
+        let result = self.skip_tokens();
+        self.skipping = false;
+        result
+    }
+
+    fn skip_tokens(&mut self) -> Result<()> {
+        let mut depth = 0usize;
+        loop {
+            match self.advance_or_fail()? {
+                Some(Token::SeqStart | Token::MapStart) => depth += 1,
+                Some(Token::SeqEnd | Token::MapEnd) if depth > 0 => depth -= 1,
+                Some(Token::Key(_)) if depth > 0 => continue,
+                Some(Token::SeqEnd | Token::MapEnd | Token::Key(_)) | None => {
+                    self.expect = Expect::Done;
+                    return Err(Error.at_offset(self.scanner.pos));
+                }
+                Some(_) => {}
+            }
+            if depth == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Byte offset in the input just past the last token read.
+    pub const fn byte_offset(&self) -> usize {
+        self.scanner.pos
+    }
+
+    fn advance_or_fail(&mut self) -> Result<Option<Token<'static>>> {
+        self.advance().map_err(|error| {
+            self.expect = Expect::Done;
+            error.at_offset(self.scanner.pos)
+        })
+    }
+
+    /// Read the next token, with any string left to be looked up by
+    /// `self.string()`.
+    fn advance(&mut self) -> Result<Option<Token<'static>>> {
+        match self.expect {
+            Expect::Start => {
+                if let Some(max) = self.scanner.config.max_input_len {
+                    if self.input.len() > max {
+                        return Err(Error);
+                    }
+                }
+                return self.value().map(Some);
+            }
+            Expect::Value => return self.value().map(Some),
+            Expect::FirstOrEnd | Expect::CommaOrEnd => {}
+            Expect::End => {
+                self.expect = Expect::Done;
+                return match self.scanner.skip_whitespace_and_peek_class() {
+                    Some(_) => Err(Error),
+                    None => Ok(None),
+                };
+            }
+            Expect::Done => return Ok(None),
+        }
+
+        let is_map = matches!(self.frames.last(), Some(Frame::Map(_)));
+        match self.scanner.skip_whitespace_and_peek_class().map(|(b, _)| b) {
+            Some(close @ (b']' | b'}')) => {
+                if (close == b'}') != is_map {
+                    return Err(Error);
+                }
+                self.scanner.bump();
+                self.frames.pop();
+                self.end_value();
+                return Ok(Some(if is_map { Token::MapEnd } else { Token::SeqEnd }));
+            }
+            Some(b',') if matches!(self.expect, Expect::CommaOrEnd) => self.scanner.bump(),
+            _ if matches!(self.expect, Expect::CommaOrEnd) => return Err(Error),
+            _ => {}
+        }
+
+        match self.frames.last_mut() {
+            Some(Frame::Seq(count)) => {
+                *count += 1;
+                if matches!(self.scanner.config.max_array_len, Some(max) if *count > max) {
+                    return Err(Error);
+                }
+                self.value().map(Some)
+            }
+            _ => self.key().map(Some),
+        }
+    }
+
+    fn value(&mut self) -> Result<Token<'static>> {
+        if self.skipping {
+            let skipped = match self.scanner.skip_whitespace_and_peek_class() {
+                Some((b'"', _)) => {
+                    self.scanner.bump();
+                    self.scanner.skip_str()?;
+                    true
+                }
+                // Anything else, including `-Infinity`, is read as usual.
+                Some((b'0'..=b'9', _)) => {
+                    self.scanner.skip_number()?;
+                    true
+                }
+                Some((b'-', _))
+                    if matches!(self.scanner.input.get(self.scanner.pos + 1), Some(b'0'..=b'9')) =>
+                {
+                    self.scanner.skip_number()?;
+                    true
+                }
+                _ => false,
+            };
+            if skipped {
+                self.end_value();
+                return Ok(Token::Null);
+            }
+        }
+
+        let token = match self.scanner.event()? {
+            Null => Token::Null,
+            Bool(b) => Token::Bool(b),
+            Str(_) => {
+                self.record_string();
+                Token::Str("")
+            }
+            Negative(n) => Token::Negative(n),
+            Nonnegative(n) => Token::Nonnegative(n),
+            Float(n) => match integral(n) {
+                Some(Nonnegative(n)) if self.scanner.config.coerce_integral_floats => {
+                    Token::Nonnegative(n)
+                }
+                Some(Negative(n)) if self.scanner.config.coerce_integral_floats => {
+                    Token::Negative(n)
+                }
+                _ => Token::Float(n),
+            },
+            SeqStart => {
+                self.frames.push(Frame::Seq(0));
+                self.expect = Expect::FirstOrEnd;
+                return Ok(Token::SeqStart);
+            }
+            MapStart => {
+                self.frames.push(Frame::Map(MapKeys::default()));
+                self.expect = Expect::FirstOrEnd;
+                return Ok(Token::MapStart);
+            }
+        };
+        self.end_value();
+        Ok(token)
+    }
+
+    fn key(&mut self) -> Result<Token<'static>> {
+        match self.scanner.skip_whitespace_and_peek_class() {
+            Some((b'"', _)) => {}
+            _ => return Err(Error),
+        }
+        let Some(Frame::Map(keys)) = self.frames.last_mut() else {
+            unreachable!();
+        };
+        keys.count += 1;
+        if matches!(self.scanner.config.max_object_keys, Some(max) if keys.count > max) {
+            return Err(Error);
+        }
+        let deny_duplicate_keys = self.scanner.config.deny_duplicate_keys;
+        if self.skipping && !deny_duplicate_keys {
+            self.scanner.bump();
+            self.scanner.skip_str()?;
+        } else {
+            let key = self.scanner.event()?.str()?;
+            if deny_duplicate_keys && !keys.seen.insert(key.to_string()) {
+                return Err(Error);
+            }
+            self.record_string();
+        }
+        match self.scanner.skip_whitespace_and_peek_class() {
+            Some((b':', _)) => self.scanner.bump(),
+            _ => return Err(Error),
+        }
+        self.expect = Expect::Value;
+        Ok(Token::Key(""))
+    }
+
+    fn end_value(&mut self) {
+        self.expect = if self.frames.is_empty() {
+            Expect::End
+        } else {
+            Expect::CommaOrEnd
+        };
+    }
+
+    /// Remember where the string that was just parsed can be found.
+    fn record_string(&mut self) {
+        // Strings without escapes are parsed without touching the buffer.
+        self.string = if self.scanner.buffer.is_empty() {
+            Some((self.scanner.token_start + 1, self.scanner.pos - 1))
+        } else {
+            None
+        };
+    }
+
+    fn string(&self) -> &str {
+        match self.string {
+            Some((start, end)) => &self.input[start..end],
+            // The buffer holds only decoded escapes and chunks of the input
+            // between them, so it is valid UTF-8.
+            None => unsafe { str::from_utf8_unchecked(&self.scanner.buffer) },
+        }
+    }
+}
+
+/// Whether any string in a JSON document contains a backslash escape.
+///
+/// Strings without escapes are handed to the deserialized type directly out of
+/// the input. A string with an escape is first decoded into scratch space,
+/// which may allocate. Latency-sensitive callers can use this to route
+/// documents that never take that path separately.
+///
+/// The input is not validated. For malformed JSON the answer is unspecified.
+///
+/// ```rust
+/// use miniserde::json;
+///
+/// assert!(!json::needs_escape_scratch(br#"{"text":"plain"}"#));
+/// assert!(json::needs_escape_scratch(br#"{"text":"two\nlines"}"#));
+/// ```
+pub fn needs_escape_scratch(input: &[u8]) -> bool {
+    let mut pos = 0;
+    let mut in_string = false;
+    loop {
+        pos += find_next_special_character(&input[pos..]);
+        match input.get(pos) {
+            Some(b'"') => in_string = !in_string,
+            Some(_) if in_string => return true,
+            Some(_) => {}
+            None => return false,
+        }
+        pos += 1;
+    }
+}
+
+pub fn from_value<T>(value: Value) -> Result<T>
+where
+    T: Deserialize,
+{
+    Deserializer::new().from_value(value)
+}
+
+/// JSON deserializer with non-default settings.
+///
+/// The plain [`from_str`] and [`from_slice`] functions are equivalent to
+/// deserializing with `Deserializer::new()`.
+///
+/// When an object repeats a key, every occurrence is passed on to the
+/// deserialized type and the last one wins. Use
+/// [`deny_duplicate_keys`][Self::deny_duplicate_keys] to reject such input.
+///
+/// ```rust
+/// use miniserde::json;
+///
+/// fn main() -> miniserde::Result<()> {
+///     let j = "[NaN, Infinity, -Infinity]";
+///
+///     let out: Vec<f64> = json::Deserializer::new()
+///         .allow_non_finite(true)
+///         .from_str(j)?;
+///     assert!(out[0].is_nan());
+///     assert_eq!(out[1..], [f64::INFINITY, f64::NEG_INFINITY]);
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Deserializer {
+    allow_non_finite: bool,
+    deny_duplicate_keys: bool,
+    max_object_keys: Option<usize>,
+    max_array_len: Option<usize>,
+    max_input_len: Option<usize>,
+    max_number_len: Option<usize>,
+    require_all_fields: bool,
+    coerce_integral_floats: bool,
+    imprecise_floats: bool,
+}
+
+#[allow(clippy::return_self_not_must_use)]
+impl Deserializer {
+    pub const fn new() -> Self {
+        Self {
+            allow_non_finite: false,
+            deny_duplicate_keys: false,
+            max_object_keys: None,
+            max_array_len: None,
+            max_input_len: None,
+            max_number_len: None,
+            require_all_fields: false,
+            coerce_integral_floats: false,
+            imprecise_floats: false,
+        }
+    }
+
+    /// Accept the non-standard literals `NaN`, `Infinity` and `-Infinity` as
+    /// floating point numbers.
+    ///
+    /// These are not valid JSON but are emitted by some producers, such as
+    /// JavaScript's and Python's non-strict encoders.
+    pub const fn allow_non_finite(mut self, allow: bool) -> Self {
+        self.allow_non_finite = allow;
+        self
+    }
+
+    /// Pass a number written with a decimal point or exponent, such as `5.0`
+    /// or `1e3`, on as an integer if it has no fractional part and fits in
+    /// `u64` or `i64`.
+    ///
+    /// This lets integer types accept such numbers. Numbers with a fractional
+    /// part, and `-0.0`, are still passed on as floats and so are rejected by
+    /// integer types.
+    pub const fn coerce_integral_floats(mut self, coerce: bool) -> Self {
+        self.coerce_integral_floats = coerce;
+        self
+    }
+
+    /// Parse every float to the `f64` nearest to its decimal value. This is on
+    /// by default, so that any float written by a correct serializer reads
+    /// back bit for bit.
+    ///
+    /// Most floats are computed exactly from their digits either way. The
+    /// rest, such as those with more than 15 significant digits or a large
+    /// exponent, are normally parsed again from their text. With this turned
+    /// off they are instead scaled by powers of ten, which is faster but may be
+    /// off from the nearest `f64` by a few units in the last place.
+    ///
+    /// ```rust
+    /// use miniserde::json;
+    ///
+    /// let fast = json::Deserializer::new().precise_floats(false);
+    /// let f: f64 = fast.from_str("0.1").unwrap();
+    /// assert_eq!(f, 0.1);
+    /// let f: f64 = fast.from_str("2.2250738585072011e-308").unwrap();
+    /// assert!((f - 2.2250738585072011e-308).abs() <= 1e-323);
+    /// ```
+    pub const fn precise_floats(mut self, precise: bool) -> Self {
+        self.imprecise_floats = !precise;
+        self
+    }
+
+    /// Fail on an object that contains the same key more than once, instead of
+    /// keeping the last value.
+    pub const fn deny_duplicate_keys(mut self, deny: bool) -> Self {
+        self.deny_duplicate_keys = deny;
+        self
+    }
+
+    /// Fail on an object with more than `max` keys.
+    ///
+    /// The limit applies to each object separately, counting duplicate keys
+    /// every time they occur. Input is rejected as soon as the first key past
+    /// the limit is reached, before any more of the object is read.
+    pub const fn max_object_keys(mut self, max: usize) -> Self {
+        self.max_object_keys = Some(max);
+        self
+    }
+
+    /// Fail on an array with more than `max` elements.
+    ///
+    /// The limit applies to each array separately. Input is rejected as soon
+    /// as the first element past the limit is reached, before it is read.
+    /// Together with [`max_object_keys`][Self::max_object_keys] this bounds
+    /// how much any single container in untrusted input can grow.
+    pub const fn max_array_len(mut self, max: usize) -> Self {
+        self.max_array_len = Some(max);
+        self
+    }
+
+    /// Fail on input longer than `max` bytes, before reading any of it.
+    ///
+    /// For [`stream_from_str`][Self::stream_from_str] this limits the whole
+    /// stream, and for [`iter_from_str`][Self::iter_from_str] each line.
+    pub const fn max_input_len(mut self, max: usize) -> Self {
+        self.max_input_len = Some(max);
+        self
+    }
+
+    /// Fail on a number written with more than `max` bytes, counting its
+    /// sign, decimal point and exponent.
+    ///
+    /// Long numbers are read in linear time and never overflow, but this
+    /// rejects input that has no business containing them, such as a number
+    /// thousands of digits long.
+    pub const fn max_number_len(mut self, max: usize) -> Self {
+        self.max_number_len = Some(max);
+        self
+    }
+
+    /// Fail on a missing struct field even if it has a `#[serde(default)]`
+    /// attribute, for checking that a document is fully specified.
+    ///
+    /// This applies to derived structs. A field of type `Option` without a
+    /// default attribute may still be left out, as usual.
+    pub const fn require_all_fields(mut self, require: bool) -> Self {
+        self.require_all_fields = require;
+        self
+    }
+
+    /// Deserialize a JSON string into any deserializable type.
+    pub fn from_str<T>(&self, j: &str) -> Result<T>
+    where
+        T: Deserialize,
+    {
+        let mut out = None;
+        from_slice_impl(j.as_bytes(), false, *self, &mut Vec::new(), T::begin(&mut out))?;
+        out.ok_or(Error)
+    }
+
+    /// Deserialize a JSON string into a type that borrows strings from it, as
+    /// in [`from_str_borrowed`].
+    pub fn from_str_borrowed<'de, T>(&self, j: &'de str) -> Result<T>
+    where
+        T: DeserializeBorrowed<'de>,
+    {
+        let mut out = None;
+        let mut de = Scanner {
+            input: j.as_bytes(),
+            pos: 0,
+            token_start: 0,
+            buffer: Vec::new(),
+            stack: Vec::new(),
+            validate_utf8: false,
+            borrow: true,
+            config: *self,
+        };
+        // The scanner borrows only strings from `j`, which lives for 'de.
+        let visitor = unsafe { T::begin_borrowed(&mut out) };
+        de.parse(visitor).map_err(|error| error.at_offset(de.pos))?;
+        drop(de);
+        out.ok_or(Error)
+    }
+
+    /// Deserialize a stream of newline-delimited JSON values, as in
+    /// [`iter_from_str`].
+    pub fn iter_from_str<'a, T>(&self, j: &'a str) -> JsonLines<'a, T>
+    where
+        T: Deserialize,
+    {
+        JsonLines {
+            rest: j,
+            offset: 0,
+            config: *self,
+            marker: PhantomData,
+        }
+    }
+
+    /// Deserialize a sequence of concatenated JSON values, as in
+    /// [`stream_from_str`].
+    pub fn stream_from_str<'a, T>(&self, j: &'a str) -> ValueStream<'a, T>
+    where
+        T: Deserialize,
+    {
+        ValueStream {
+            input: j,
+            pos: 0,
+            config: *self,
+            failed: false,
+            marker: PhantomData,
+        }
+    }
+
+    /// Read a JSON document one token at a time, as in [`reader`].
+    ///
+    /// Settings about numbers and object keys apply to the tokens read.
+    /// `require_all_fields` has no effect, since no type is deserialized.
+    pub fn reader<'a>(&self, j: &'a str) -> Reader<'a> {
+        Reader {
+            input: j,
+            scanner: Scanner {
+                input: j.as_bytes(),
+                pos: 0,
+                token_start: 0,
+                buffer: Vec::new(),
+                stack: Vec::new(),
+                validate_utf8: false,
+                borrow: false,
+                config: *self,
+            },
+            frames: Vec::new(),
+            expect: Expect::Start,
+            string: None,
+            skipping: false,
+        }
+    }
+
+    /// Deserialize JSON bytes into any deserializable type.
+    ///
+    /// A leading UTF-8 byte order mark is skipped.
+    pub fn from_slice<T>(&self, j: &[u8]) -> Result<T>
+    where
+        T: Deserialize,
+    {
+        let mut out = None;
+        from_slice_impl(j, true, *self, &mut Vec::new(), T::begin(&mut out))?;
+        out.ok_or(Error)
+    }
+
+    /// Deserialize the JSON value at the start of some bytes, as in
+    /// [`from_slice_partial`].
+    pub fn from_slice_partial<T>(&self, j: &[u8]) -> Result<(T, usize)>
+    where
+        T: Deserialize,
+    {
+        let mut out = None;
+        let len = from_slice_partial_impl(j, *self, T::begin(&mut out))?;
+        Ok((out.ok_or(Error)?, len))
+    }
+
+    /// Deserialize an already parsed [`Value`], as in [`from_value`].
+    ///
+    /// Only `require_all_fields` and `coerce_integral_floats` apply, since the
+    /// other settings are about the JSON text.
+    pub fn from_value<T>(&self, value: Value) -> Result<T>
+    where
+        T: Deserialize,
+    {
+        let mut out = None;
+        from_value_impl(value, T::begin(&mut out), self)?;
+        out.ok_or(Error)
+    }
 }
 
-pub fn from_value<T>(value: Value) -> Result<T>
-where
-    T: Deserialize,
-{
-    let mut out = None;
-    from_value_impl(value, T::begin(&mut out))?;
-    out.ok_or(Error)
+/// JSON deserializer that keeps its scratch space from one document to the
+/// next.
+///
+/// Strings containing escape sequences are decoded into a scratch buffer
+/// before being handed to the deserialized type. The free functions allocate
+/// a new buffer for every document, while a `Parser` clears and reuses the same
+/// one, which saves allocations when parsing many small documents.
+///
+/// ```rust
+/// use miniserde::json;
+///
+/// fn main() -> miniserde::Result<()> {
+///     let mut parser = json::Parser::new();
+///     for line in ["\"a\\tb\"", "\"c\\nd\""] {
+///         let s: String = parser.parse(line)?;
+///         assert_eq!(s.len(), 3);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Parser {
+    config: Deserializer,
+    buffer: Vec<u8>,
+}
+
+impl Parser {
+    pub const fn new() -> Self {
+        Self::with_config(Deserializer::new())
+    }
+
+    /// Parse with the settings of a configured [`Deserializer`].
+    pub const fn with_config(config: Deserializer) -> Self {
+        Self {
+            config,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Deserialize a JSON string into any deserializable type, as in
+    /// [`from_str`].
+    pub fn parse<T>(&mut self, j: &str) -> Result<T>
+    where
+        T: Deserialize,
+    {
+        let mut out = None;
+        let visitor = T::begin(&mut out);
+        from_slice_impl(j.as_bytes(), false, self.config, &mut self.buffer, visitor)?;
+        out.ok_or(Error)
+    }
+
+    /// Deserialize JSON bytes into any deserializable type, as in
+    /// [`from_slice`].
+    pub fn parse_slice<T>(&mut self, j: &[u8]) -> Result<T>
+    where
+        T: Deserialize,
+    {
+        let mut out = None;
+        from_slice_impl(j, true, self.config, &mut self.buffer, T::begin(&mut out))?;
+        out.ok_or(Error)
+    }
 }
 
-struct Deserializer<'a, 'b> {
+struct Scanner<'a, 'b> {
     input: &'a [u8],
     pos: usize,
+    /// Where the last token read by `event` starts. For a number this is the
+    /// start of its literal, which some floats are parsed again from and whose
+    /// length is limited by `max_number_len`. For a string it is the opening
+    /// quote, which `Reader` uses to find strings without escapes in the input.
+    token_start: usize,
     buffer: Vec<u8>,
     stack: Vec<(NonNull<dyn Visitor>, Layer<'b>)>,
     /// If true, string segments from the input must be validated as UTF-8.
     /// This is true for `from_slice` and false for `from_str`.
     validate_utf8: bool,
+    /// If true, strings read straight from the input are passed to visitors
+    /// as borrowed. This is true only for `from_str_borrowed`.
+    borrow: bool,
+    config: Deserializer,
 }
 
 enum Layer<'a> {
-    Seq(NonuniqueBox<dyn Seq + 'a>),
-    Map(NonuniqueBox<dyn Map + 'a>),
+    /// The sequence and the number of its elements read so far.
+    Seq(NonuniqueBox<dyn Seq + 'a>, usize),
+    Map(NonuniqueBox<dyn Map + 'a>, MapKeys),
+}
+
+/// Keys of the object currently being parsed at one level of nesting.
+#[derive(Default)]
+struct MapKeys {
+    count: usize,
+    /// Only recorded with `deny_duplicate_keys`.
+    seen: BTreeSet<String>,
 }
 
-impl<'a, 'b> Drop for Deserializer<'a, 'b> {
+impl<'a, 'b> Drop for Scanner<'a, 'b> {
     fn drop(&mut self) {
         // Drop layers in reverse order.
         while !self.stack.is_empty() {
@@ -96,7 +1023,7 @@ enum CharClass {
     Comma,       // ','
     Colon,       // ':'
     Minus,       // '-'
-    Ident,       // 't', 'f', 'n'
+    Ident,       // 't', 'f', 'n', and 'N', 'I' for non-finite floats
     Error,       // Any other byte that is invalid in JSON
 }
 
@@ -126,7 +1053,7 @@ const CLASSIFY: [CharClass; 256] = {
             b'-' => CharClass::Minus,
 
             // Identifiers
-            b't' | b'f' | b'n' => CharClass::Ident,
+            b't' | b'f' | b'n' | b'N' | b'I' => CharClass::Ident,
 
             _ => CharClass::Error,
         };
@@ -148,7 +1075,8 @@ impl<'a> EventExt<'a> for Event<'a> {
     }
 }
 
-fn from_value_impl(value: Value, visitor: &mut dyn Visitor) -> Result<()> {
+#[allow(clippy::cast_sign_loss, clippy::items_after_statements)]
+fn from_value_impl(value: Value, visitor: &mut dyn Visitor, config: &Deserializer) -> Result<()> {
     let visitor = NonNull::from(visitor);
     let mut visitor = unsafe { extend_lifetime!(visitor as NonNull<dyn Visitor>) };
 
@@ -219,11 +1147,20 @@ fn from_value_impl(value: Value, visitor: &mut dyn Visitor) -> Result<()> {
                             visitor_mut.negative(i)?;
                         }
                     }
-                    Number::F64(f) => visitor_mut.float(f)?,
+                    Number::F64(f) => match integral(f) {
+                        Some(Nonnegative(n)) if config.coerce_integral_floats => {
+                            visitor_mut.nonnegative(n)?;
+                        }
+                        Some(Negative(n)) if config.coerce_integral_floats => {
+                            visitor_mut.negative(n)?;
+                        }
+                        _ => visitor_mut.float(f)?,
+                    },
                 },
                 Value::String(s) => visitor_mut.string(&s)?,
                 Value::Array(arr) => {
-                    let seq = visitor_mut.seq()?;
+                    let mut seq = visitor_mut.seq()?;
+                    seq.size_hint(arr.len());
                     let seq = unsafe {
                         extend_lifetime!(NonuniqueBox::from(seq) as NonuniqueBox<dyn Seq>)
                     };
@@ -233,7 +1170,10 @@ fn from_value_impl(value: Value, visitor: &mut dyn Visitor) -> Result<()> {
                     continue 'outer;
                 }
                 Value::Object(obj) => {
-                    let map = visitor_mut.map()?;
+                    let mut map = visitor_mut.map()?;
+                    if config.require_all_fields {
+                        map.__require_all_fields();
+                    }
                     let map = unsafe {
                         extend_lifetime!(NonuniqueBox::from(map) as NonuniqueBox<dyn Map>)
                     };
@@ -254,130 +1194,222 @@ fn from_value_impl(value: Value, visitor: &mut dyn Visitor) -> Result<()> {
 fn from_slice_impl(
     j: &[u8],
     validate_utf8: bool,
+    config: Deserializer,
+    buffer: &mut Vec<u8>,
     visitor: &mut dyn Visitor,
 ) -> Result<()> {
-    let visitor = NonNull::from(visitor);
-    let mut visitor = unsafe { extend_lifetime!(visitor as NonNull<dyn Visitor>) };
-    let mut de = Deserializer {
+    // Only byte input can carry a byte order mark.
+    let pos = if validate_utf8 { byte_order_mark(j)? } else { 0 };
+
+    buffer.clear();
+    let mut de = Scanner {
         input: j,
-        pos: 0,
-        buffer: Vec::new(),
+        pos,
+        token_start: 0,
+        buffer: mem::take(buffer),
         stack: Vec::new(),
         validate_utf8,
+        borrow: false,
+        config,
     };
+    let result = de.parse(visitor);
+    *buffer = mem::take(&mut de.buffer);
+    result.map_err(|error| error.at_offset(de.pos))
+}
 
-    'outer: loop {
-        let visitor_mut = unsafe { &mut *visitor.as_ptr() };
-        let layer = match de.event()? {
-            Null => {
-                visitor_mut.null()?;
-                None
-            }
-            Bool(b) => {
-                visitor_mut.boolean(b)?;
-                None
-            }
-            Negative(n) => {
-                visitor_mut.negative(n)?;
-                None
-            }
-            Nonnegative(n) => {
-                visitor_mut.nonnegative(n)?;
-                None
-            }
-            Float(n) => {
-                visitor_mut.float(n)?;
-                None
-            }
-            Str(s) => {
-                visitor_mut.string(s)?;
-                None
-            }
-            SeqStart => {
-                let seq = visitor_mut.seq()?;
-                Some(Layer::Seq(NonuniqueBox::from(seq)))
-            }
-            MapStart => {
-                let map = visitor_mut.map()?;
-                Some(Layer::Map(NonuniqueBox::from(map)))
-            }
-        };
+fn from_slice_partial_impl(j: &[u8], config: Deserializer, visitor: &mut dyn Visitor) -> Result<usize> {
+    let mut de = Scanner {
+        input: j,
+        pos: byte_order_mark(j)?,
+        token_start: 0,
+        buffer: Vec::new(),
+        stack: Vec::new(),
+        validate_utf8: true,
+        borrow: false,
+        config,
+    };
+    match de.parse_value(visitor) {
+        Ok(()) => Ok(de.pos),
+        Err(error) => Err(error.at_offset(de.pos)),
+    }
+}
+
+/// Length of a UTF-8 byte order mark at the start of the input, which is
+/// skipped. Other byte order marks are an error.
+fn byte_order_mark(j: &[u8]) -> Result<usize> {
+    if j.starts_with(b"\xEF\xBB\xBF") {
+        Ok(3)
+    } else if j.starts_with(b"\xFE\xFF")
+        || j.starts_with(b"\xFF\xFE")
+        || j.starts_with(b"\0\0\xFE\xFF")
+    {
+        Err(Error.at_offset(0))
+    } else {
+        Ok(0)
+    }
+}
+
+impl<'a, 'b> Scanner<'a, 'b> {
+    fn parse(&mut self, visitor: &mut dyn Visitor) -> Result<()> {
+        self.parse_value(visitor)?;
+        match self.skip_whitespace_and_peek_class() {
+            Some(_) => Err(Error),
+            None => Ok(()),
+        }
+    }
 
-        let mut accept_comma;
-        let mut layer = match layer {
-            Some(layer) => {
-                accept_comma = false;
-                layer
+    /// Parse one complete value, leaving `pos` right after its last byte.
+    fn parse_value(&mut self, visitor: &mut dyn Visitor) -> Result<()> {
+        if let Some(max) = self.config.max_input_len {
+            if self.input.len() > max {
+                return Err(Error);
             }
-            None => match de.stack.pop() {
-                Some(frame) => {
-                    accept_comma = true;
-                    visitor = frame.0;
-                    frame.1
-                }
-                None => break 'outer,
-            },
-        };
+        }
 
-        loop {
-            match de.skip_whitespace_and_peek_class().map(|(b, _)| b) {
-                Some(b',') if accept_comma => {
-                    de.bump();
-                    break;
-                }
-                Some(close @ (b']' | b'}')) => {
-                    de.bump();
-                    match &mut layer {
-                        Layer::Seq(seq) if close == b']' => seq.finish()?,
-                        Layer::Map(map) if close == b'}' => map.finish()?,
-                        _ => return Err(Error),
+        let visitor = NonNull::from(visitor);
+        let mut visitor = unsafe { extend_lifetime!(visitor as NonNull<dyn Visitor>) };
+        let borrow = self.borrow;
+        let input = self.input.as_ptr_range();
+
+        'outer: loop {
+            let visitor_mut = unsafe { &mut *visitor.as_ptr() };
+            let layer = match self.event()? {
+                Null => {
+                    visitor_mut.null()?;
+                    None
+                }
+                Bool(b) => {
+                    visitor_mut.boolean(b)?;
+                    None
+                }
+                Negative(n) => {
+                    visitor_mut.negative(n)?;
+                    None
+                }
+                Nonnegative(n) => {
+                    visitor_mut.nonnegative(n)?;
+                    None
+                }
+                Float(n) => {
+                    match integral(n) {
+                        Some(Nonnegative(n)) if self.config.coerce_integral_floats => {
+                            visitor_mut.nonnegative(n)?;
+                        }
+                        Some(Negative(n)) if self.config.coerce_integral_floats => {
+                            visitor_mut.negative(n)?;
+                        }
+                        _ => visitor_mut.float(n)?,
                     }
-                    let Some(frame) = de.stack.pop() else {
-                        break 'outer;
-                    };
-                    accept_comma = true;
-                    visitor = frame.0;
-                    layer = frame.1;
+                    None
                 }
-                _ => {
-                    if accept_comma {
-                        return Err(Error);
+                Str(s) => {
+                    if borrow && input.contains(&s.as_ptr()) {
+                        // Not copied into the scratch space, so it lives as
+                        // long as the input.
+                        unsafe { visitor_mut.__borrowed_string(s)? };
                     } else {
+                        visitor_mut.string(s)?;
+                    }
+                    None
+                }
+                SeqStart => {
+                    let seq = visitor_mut.seq()?;
+                    Some(Layer::Seq(NonuniqueBox::from(seq), 0))
+                }
+                MapStart => {
+                    let mut map = visitor_mut.map()?;
+                    if self.config.require_all_fields {
+                        map.__require_all_fields();
+                    }
+                    Some(Layer::Map(NonuniqueBox::from(map), MapKeys::default()))
+                }
+            };
+
+            let mut accept_comma;
+            let mut layer = match layer {
+                Some(layer) => {
+                    accept_comma = false;
+                    layer
+                }
+                None => match self.stack.pop() {
+                    Some(frame) => {
+                        accept_comma = true;
+                        visitor = frame.0;
+                        frame.1
+                    }
+                    None => break 'outer,
+                },
+            };
+
+            loop {
+                match self.skip_whitespace_and_peek_class().map(|(b, _)| b) {
+                    Some(b',') if accept_comma => {
+                        self.bump();
                         break;
                     }
+                    Some(close @ (b']' | b'}')) => {
+                        self.bump();
+                        match &mut layer {
+                            Layer::Seq(seq, _) if close == b']' => seq.finish()?,
+                            Layer::Map(map, _) if close == b'}' => map.finish()?,
+                            _ => return Err(Error),
+                        }
+                        let Some(frame) = self.stack.pop() else {
+                            break 'outer;
+                        };
+                        accept_comma = true;
+                        visitor = frame.0;
+                        layer = frame.1;
+                    }
+                    _ => {
+                        if accept_comma {
+                            return Err(Error);
+                        } else {
+                            break;
+                        }
+                    }
                 }
             }
-        }
 
-        let outer = visitor;
-        match layer {
-            Layer::Seq(mut seq) => {
-                let element = seq.element()?;
-                let next = NonNull::from(element);
-                visitor = unsafe { extend_lifetime!(next as NonNull<dyn Visitor>) };
-                de.stack.push((outer, Layer::Seq(seq)));
-            }
-            Layer::Map(mut map) => {
-                match de.skip_whitespace_and_peek_class() {
-                    Some((b'"', _)) => {}
-                    _ => return Err(Error),
+            let outer = visitor;
+            match layer {
+                Layer::Seq(mut seq, mut count) => {
+                    count += 1;
+                    if matches!(self.config.max_array_len, Some(max) if count > max) {
+                        return Err(Error);
+                    }
+                    let element = seq.element()?;
+                    let next = NonNull::from(element);
+                    visitor = unsafe { extend_lifetime!(next as NonNull<dyn Visitor>) };
+                    self.stack.push((outer, Layer::Seq(seq, count)));
                 }
-                let key = de.event()?.str()?; // Optimized event call
-                let entry = map.key(key)?;
-                let next = NonNull::from(entry);
-                visitor = unsafe { extend_lifetime!(next as NonNull<dyn Visitor>) };
-                match de.skip_whitespace_and_peek_class() {
-                    Some((b':', _)) => de.bump(),
-                    _ => return Err(Error),
+                Layer::Map(mut map, mut keys) => {
+                    match self.skip_whitespace_and_peek_class() {
+                        Some((b'"', _)) => {}
+                        _ => return Err(Error),
+                    }
+                    keys.count += 1;
+                    if matches!(self.config.max_object_keys, Some(max) if keys.count > max) {
+                        return Err(Error);
+                    }
+                    let deny_duplicate_keys = self.config.deny_duplicate_keys;
+                    let key = self.event()?.str()?; // Optimized event call
+                    if deny_duplicate_keys && !keys.seen.insert(key.to_string()) {
+                        return Err(Error);
+                    }
+                    let entry = map.key(key)?;
+                    let next = NonNull::from(entry);
+                    visitor = unsafe { extend_lifetime!(next as NonNull<dyn Visitor>) };
+                    match self.skip_whitespace_and_peek_class() {
+                        Some((b':', _)) => self.bump(),
+                        _ => return Err(Error),
+                    }
+                    self.stack.push((outer, Layer::Map(map, keys)));
                 }
-                de.stack.push((outer, Layer::Map(map)));
             }
         }
-    }
 
-    match de.skip_whitespace_and_peek_class() {
-        Some(_) => Err(Error),
-        None => Ok(()),
+        Ok(())
     }
 }
 
@@ -392,6 +1424,8 @@ enum Event<'a> {
     MapStart,
 }
 
+const INVALID_NUMBER: Error = Error.with_kind(ErrorKind::InvalidNumber);
+
 macro_rules! overflow {
     ($a:ident * 10 + $b:ident, $c:expr) => {
         match $c {
@@ -401,7 +1435,7 @@ macro_rules! overflow {
 }
 
 
-impl<'a, 'b> Deserializer<'a, 'b> {
+impl<'a, 'b> Scanner<'a, 'b> {
     fn next(&mut self) -> Option<u8> {
         if self.pos < self.input.len() {
             let ch = self.input[self.pos];
@@ -493,6 +1527,77 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         }
     }
 
+    /// Move past the rest of a string, after its opening quote, checking that
+    /// it is well formed but without decoding its escapes.
+    fn skip_str(&mut self) -> Result<()> {
+        let start = self.pos;
+        loop {
+            self.pos += find_next_special_character(&self.input[self.pos..]);
+            match self.next() {
+                Some(b'"') => break,
+                Some(b'\\') => match self.next_or_eof()? {
+                    b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't' => {}
+                    b'u' => {
+                        for _ in 0..4 {
+                            if !self.next_or_eof()?.is_ascii_hexdigit() {
+                                return Err(Error);
+                            }
+                        }
+                    }
+                    _ => return Err(Error),
+                },
+                _ => return Err(Error),
+            }
+        }
+        if self.validate_utf8 {
+            str::from_utf8(&self.input[start..self.pos]).map_err(|_| Error)?;
+        }
+        Ok(())
+    }
+
+    /// Move past a number, checking its syntax and length without computing
+    /// its value.
+    fn skip_number(&mut self) -> Result<()> {
+        let start = self.pos;
+        self.token_start = start;
+        if self.peek_or_nul() == b'-' {
+            self.bump();
+        }
+        match self.peek_or_nul() {
+            b'0' => {
+                self.bump_digit()?;
+                if self.peek_or_nul().is_ascii_digit() {
+                    return Err(INVALID_NUMBER);
+                }
+            }
+            b'1'..=b'9' => self.skip_digits()?,
+            _ => return Err(INVALID_NUMBER),
+        }
+        if self.peek_or_nul() == b'.' {
+            self.bump();
+            self.skip_digits()?;
+        }
+        if let b'e' | b'E' = self.peek_or_nul() {
+            self.bump();
+            if let b'+' | b'-' = self.peek_or_nul() {
+                self.bump();
+            }
+            self.skip_digits()?;
+        }
+        self.check_number_len(start)
+    }
+
+    /// Move past one or more digits.
+    fn skip_digits(&mut self) -> Result<()> {
+        if !self.peek_or_nul().is_ascii_digit() {
+            return Err(INVALID_NUMBER);
+        }
+        while self.peek_or_nul().is_ascii_digit() {
+            self.bump_digit()?;
+        }
+        Ok(())
+    }
+
     fn next_or_eof(&mut self) -> Result<u8> {
         self.next().ok_or(Error)
     }
@@ -512,25 +1617,26 @@ impl<'a, 'b> Deserializer<'a, 'b> {
             b'r' => self.buffer.push(b'\r'),
             b't' => self.buffer.push(b'\t'),
             b'u' => {
+                let surrogate = Error.with_kind(ErrorKind::InvalidSurrogate);
                 let c = match self.decode_hex_escape()? {
                     0xDC00..=0xDFFF => {
-                        return Err(Error);
+                        return Err(surrogate);
                     }
 
                     // Non-BMP characters are encoded as a sequence of
                     // two hex escapes, representing UTF-16 surrogates.
                     n1 @ 0xD800..=0xDBFF => {
                         if self.next_or_eof()? != b'\\' {
-                            return Err(Error);
+                            return Err(surrogate);
                         }
                         if self.next_or_eof()? != b'u' {
-                            return Err(Error);
+                            return Err(surrogate);
                         }
 
                         let n2 = self.decode_hex_escape()?;
 
                         if n2 < 0xDC00 || n2 > 0xDFFF {
-                            return Err(Error);
+                            return Err(surrogate);
                         }
 
                         let n =
@@ -610,12 +1716,12 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         Ok(())
     }
 
-    fn parse_integer(&mut self, nonnegative: bool, first_digit: u8) -> Result<Event> {
+    fn parse_integer(&mut self, nonnegative: bool, first_digit: u8) -> Result<Event<'static>> {
         match first_digit {
             b'0' => {
                 // There can be only one leading '0'.
                 match self.peek_or_nul() {
-                    b'0'..=b'9' => Err(Error),
+                    b'0'..=b'9' => Err(INVALID_NUMBER),
                     _ => self.parse_number(nonnegative, 0),
                 }
             }
@@ -625,7 +1731,7 @@ impl<'a, 'b> Deserializer<'a, 'b> {
                 loop {
                     match self.peek_or_nul() {
                         c @ b'0'..=b'9' => {
-                            self.bump();
+                            self.bump_digit()?;
                             let digit = u64::from(c - b'0');
 
                             // We need to be careful with overflow. If we can, try to keep the
@@ -649,7 +1755,7 @@ impl<'a, 'b> Deserializer<'a, 'b> {
                     }
                 }
             }
-            _ => Err(Error),
+            _ => Err(INVALID_NUMBER),
         }
     }
 
@@ -662,10 +1768,10 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         loop {
             match self.peek_or_nul() {
                 b'0'..=b'9' => {
-                    self.bump();
-                    // This could overflow... if your integer is gigabytes long.
-                    // Ignore that possibility.
-                    exponent += 1;
+                    self.bump_digit()?;
+                    // Saturate rather than overflow if the integer is
+                    // gigabytes long. It is infinite either way.
+                    exponent = exponent.saturating_add(1);
                 }
                 b'.' => {
                     return self.parse_decimal(nonnegative, significand, exponent);
@@ -674,18 +1780,19 @@ impl<'a, 'b> Deserializer<'a, 'b> {
                     return self.parse_exponent(nonnegative, significand, exponent);
                 }
                 _ => {
-                    return f64_from_parts(nonnegative, significand, exponent);
+                    return self.f64_from_parts(nonnegative, significand, exponent);
                 }
             }
         }
     }
 
-    fn parse_number(&mut self, nonnegative: bool, significand: u64) -> Result<Event> {
+    fn parse_number(&mut self, nonnegative: bool, significand: u64) -> Result<Event<'static>> {
         match self.peek_or_nul() {
             b'.' => self.parse_decimal(nonnegative, significand, 0).map(Float),
             b'e' | b'E' => self.parse_exponent(nonnegative, significand, 0).map(Float),
             _ => {
-                Ok(if nonnegative {
+                // No integer has a sign on zero, so `-0` is plain zero.
+                Ok(if nonnegative || significand == 0 {
                     Nonnegative(significand)
                 } else {
                     let neg = (significand as i64).wrapping_neg();
@@ -711,7 +1818,7 @@ impl<'a, 'b> Deserializer<'a, 'b> {
 
         let mut at_least_one_digit = false;
         while let c @ b'0'..=b'9' = self.peek_or_nul() {
-            self.bump();
+            self.bump_digit()?;
             let digit = u64::from(c - b'0');
             at_least_one_digit = true;
 
@@ -719,7 +1826,7 @@ impl<'a, 'b> Deserializer<'a, 'b> {
                 // The next multiply/add would overflow, so just ignore all
                 // further digits.
                 while let b'0'..=b'9' = self.peek_or_nul() {
-                    self.bump();
+                    self.bump_digit()?;
                 }
                 break;
             }
@@ -729,12 +1836,12 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         }
 
         if !at_least_one_digit {
-            return Err(Error);
+            return Err(INVALID_NUMBER);
         }
 
         match self.peek_or_nul() {
             b'e' | b'E' => self.parse_exponent(nonnegative, significand, exponent),
-            _ => f64_from_parts(nonnegative, significand, exponent),
+            _ => self.f64_from_parts(nonnegative, significand, exponent),
         }
     }
 
@@ -759,15 +1866,18 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         };
 
         // Make sure a digit follows the exponent place.
-        let mut exp = match self.next_or_nul() {
-            c @ b'0'..=b'9' => i32::from(c - b'0'),
+        let mut exp = match self.peek_or_nul() {
+            c @ b'0'..=b'9' => {
+                self.bump_digit()?;
+                i32::from(c - b'0')
+            }
             _ => {
-                return Err(Error);
+                return Err(INVALID_NUMBER);
             }
         };
 
         while let c @ b'0'..=b'9' = self.peek_or_nul() {
-            self.bump();
+            self.bump_digit()?;
             let digit = i32::from(c - b'0');
 
             if overflow!(exp * 10 + digit, i32::MAX) {
@@ -783,7 +1893,7 @@ impl<'a, 'b> Deserializer<'a, 'b> {
             starting_exp.saturating_sub(exp)
         };
 
-        f64_from_parts(nonnegative, significand, final_exp)
+        self.f64_from_parts(nonnegative, significand, final_exp)
     }
 
     // This cold code should not be inlined into the middle of the hot
@@ -802,24 +1912,90 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         }
 
         while let b'0'..=b'9' = self.peek_or_nul() {
-            self.bump();
+            self.bump_digit()?;
         }
         Ok(if nonnegative { 0.0 } else { -0.0 })
     }
 
+    /// The `f64` nearest to `significand * 10^exponent`.
+    ///
+    /// If both the significand and the power of ten are exactly representable
+    /// as `f64`, a single multiplication or division rounds correctly. Anything
+    /// else, including a significand that dropped digits, is parsed again
+    /// from the literal by the standard library, which always rounds
+    /// correctly. JSON's number syntax is a subset of what it accepts.
+    fn f64_from_parts(&self, nonnegative: bool, significand: u64, exponent: i32) -> Result<f64> {
+        if significand <= 1 << 53 {
+            if let Some(&pow) = POW10.get(exponent.unsigned_abs() as usize) {
+                let f = significand as f64;
+                let f = if exponent >= 0 { f * pow } else { f / pow };
+                return Ok(if nonnegative { f } else { -f });
+            }
+        }
+        if self.config.imprecise_floats {
+            return f64_scaled(nonnegative, significand, exponent);
+        }
+        self.parse_float_literal()
+    }
+
+    fn parse_float_literal(&self) -> Result<f64> {
+        let literal = &self.input[self.token_start..self.pos];
+        let literal = unsafe { str::from_utf8_unchecked(literal) };
+        match literal.parse::<f64>() {
+            Ok(f) if f.is_finite() => Ok(f),
+            _ => Err(Error),
+        }
+    }
+
+    /// Consumes the next digit of the number being parsed, or fails without
+    /// consuming it if that would make the number longer than
+    /// `max_number_len`. This stops a gigabyte-long literal at the limit
+    /// instead of scanning all of it first.
+    fn bump_digit(&mut self) -> Result<()> {
+        if let Some(max) = self.config.max_number_len {
+            if self.pos - self.token_start >= max {
+                return Err(Error);
+            }
+        }
+        self.bump();
+        Ok(())
+    }
+
+    const fn check_number_len(&self, start: usize) -> Result<()> {
+        match self.config.max_number_len {
+            Some(max) if self.pos - start > max => Err(Error),
+            _ => Ok(()),
+        }
+    }
+
     fn event(&mut self) -> Result<Event> {
         let Some((peek, _)) = self.skip_whitespace_and_peek_class() else {
             return Err(Error);
         };
 
+        let start = self.pos;
+        self.token_start = start;
         self.bump();
         match peek {
             b'"' => self.parse_str().map(Str),
-            digit @ b'0'..=b'9' => self.parse_integer(true, digit),
-            b'-' => {
-                let first_digit = self.next_or_nul();
-                self.parse_integer(false, first_digit)
+            digit @ b'0'..=b'9' => {
+                let event = self.parse_integer(true, digit);
+                self.check_number_len(start)?;
+                event
             }
+            b'-' => match self.next_or_nul() {
+                b'I' if self.config.allow_non_finite => {
+                    self.parse_ident(b"nfinity")?;
+                    Ok(Float(f64::NEG_INFINITY))
+                }
+                first_digit => {
+                    let event = self.parse_integer(false, first_digit);
+                    self.check_number_len(start)?;
+                    event
+                }
+            },
+            // JSON numbers have no leading plus sign and no leading dot.
+            b'+' | b'.' => Err(INVALID_NUMBER.at_offset(start)),
             b'{' => Ok(MapStart),
             b'[' => Ok(SeqStart),
             b'n' => {
@@ -834,81 +2010,78 @@ impl<'a, 'b> Deserializer<'a, 'b> {
                 self.parse_ident(b"alse")?;
                 Ok(Bool(false))
             }
+            b'N' if self.config.allow_non_finite => {
+                self.parse_ident(b"aN")?;
+                Ok(Float(f64::NAN))
+            }
+            b'I' if self.config.allow_non_finite => {
+                self.parse_ident(b"nfinity")?;
+                Ok(Float(f64::INFINITY))
+            }
             _ => Err(Error),
         }
     }
 }
 
-fn f64_from_parts(nonnegative: bool, significand: u64, mut exponent: i32) -> Result<f64> {
+/// Approximately `significand * 10^exponent`, rounding once per power of ten
+/// applied, for `precise_floats(false)`.
+fn f64_scaled(nonnegative: bool, significand: u64, mut exponent: i32) -> Result<f64> {
     let mut f = significand as f64;
-    loop {
-        match POW10.get(exponent.unsigned_abs() as usize) {
-            Some(&pow) => {
-                if exponent >= 0 {
-                    f *= pow;
-                    if f.is_infinite() {
-                        return Err(Error);
-                    }
-                } else {
-                    f /= pow;
-                }
-                break;
-            }
-            None => {
-                if f == 0.0 {
-                    break;
-                }
-                if exponent >= 0 {
-                    return Err(Error);
-                }
-                f /= 1e308;
-                exponent += 308;
-            }
+    if f != 0.0 {
+        // The exponent may be huge, but the loops end as soon as `f` runs
+        // out of range.
+        while exponent > 22 && f.is_finite() {
+            f *= 1e22;
+            exponent -= 22;
+        }
+        while exponent < -22 && f != 0.0 {
+            f /= 1e22;
+            exponent += 22;
+        }
+        let pow = POW10[exponent.unsigned_abs().min(22) as usize];
+        f = if exponent >= 0 { f * pow } else { f / pow };
+        if f.is_infinite() {
+            return Err(Error);
         }
     }
     Ok(if nonnegative { f } else { -f })
 }
 
-// Clippy bug: https://github.com/rust-lang/rust-clippy/issues/5201
-#[allow(clippy::excessive_precision)]
-static POW10: [f64; 309] = [
-    1e000, 1e001, 1e002, 1e003, 1e004, 1e005, 1e006, 1e007, 1e008, 1e009, //
-    1e010, 1e011, 1e012, 1e013, 1e014, 1e015, 1e016, 1e017, 1e018, 1e019, //
-    1e020, 1e021, 1e022, 1e023, 1e024, 1e025, 1e026, 1e027, 1e028, 1e029, //
-    1e030, 1e031, 1e032, 1e033, 1e034, 1e035, 1e036, 1e037, 1e038, 1e039, //
-    1e040, 1e041, 1e042, 1e043, 1e044, 1e045, 1e046, 1e047, 1e048, 1e049, //
-    1e050, 1e051, 1e052, 1e053, 1e054, 1e055, 1e056, 1e057, 1e058, 1e059, //
-    1e060, 1e061, 1e062, 1e063, 1e064, 1e065, 1e066, 1e067, 1e068, 1e069, //
-    1e070, 1e071, 1e072, 1e073, 1e074, 1e075, 1e076, 1e077, 1e078, 1e079, //
-    1e080, 1e081, 1e082, 1e083, 1e084, 1e085, 1e086, 1e087, 1e088, 1e089, //
-    1e090, 1e091, 1e092, 1e093, 1e094, 1e095, 1e096, 1e097, 1e098, 1e099, //
-    1e100, 1e101, 1e102, 1e103, 1e104, 1e105, 1e106, 1e107, 1e108, 1e109, //
-    1e110, 1e111, 1e112, 1e113, 1e114, 1e115, 1e116, 1e117, 1e118, 1e119, //
-    1e120, 1e121, 1e122, 1e123, 1e124, 1e125, 1e126, 1e127, 1e128, 1e129, //
-    1e130, 1e131, 1e132, 1e133, 1e134, 1e135, 1e136, 1e137, 1e138, 1e139, //
-    1e140, 1e141, 1e142, 1e143, 1e144, 1e145, 1e146, 1e147, 1e148, 1e149, //
-    1e150, 1e151, 1e152, 1e153, 1e154, 1e155, 1e156, 1e157, 1e158, 1e159, //
-    1e160, 1e161, 1e162, 1e163, 1e164, 1e165, 1e166, 1e167, 1e168, 1e169, //
-    1e170, 1e171, 1e172, 1e173, 1e174, 1e175, 1e176, 1e177, 1e178, 1e179, //
-    1e180, 1e181, 1e182, 1e183, 1e184, 1e185, 1e186, 1e187, 1e188, 1e189, //
-    1e190, 1e191, 1e192, 1e193, 1e194, 1e195, 1e196, 1e197, 1e198, 1e199, //
-    1e200, 1e201, 1e202, 1e203, 1e204, 1e205, 1e206, 1e207, 1e208, 1e209, //
-    1e210, 1e211, 1e212, 1e213, 1e214, 1e215, 1e216, 1e217, 1e218, 1e219, //
-    1e220, 1e221, 1e222, 1e223, 1e224, 1e225, 1e226, 1e227, 1e228, 1e229, //
-    1e230, 1e231, 1e232, 1e233, 1e234, 1e235, 1e236, 1e237, 1e238, 1e239, //
-    1e240, 1e241, 1e242, 1e243, 1e244, 1e245, 1e246, 1e247, 1e248, 1e249, //
-    1e250, 1e251, 1e252, 1e253, 1e254, 1e255, 1e256, 1e257, 1e258, 1e259, //
-    1e260, 1e261, 1e262, 1e263, 1e264, 1e265, 1e266, 1e267, 1e268, 1e269, //
-    1e270, 1e271, 1e272, 1e273, 1e274, 1e275, 1e276, 1e277, 1e278, 1e279, //
-    1e280, 1e281, 1e282, 1e283, 1e284, 1e285, 1e286, 1e287, 1e288, 1e289, //
-    1e290, 1e291, 1e292, 1e293, 1e294, 1e295, 1e296, 1e297, 1e298, 1e299, //
-    1e300, 1e301, 1e302, 1e303, 1e304, 1e305, 1e306, 1e307, 1e308,
+// Powers of ten that are exactly representable as f64.
+static POW10: [f64; 23] = [
+    1e00, 1e01, 1e02, 1e03, 1e04, 1e05, 1e06, 1e07, 1e08, 1e09, 1e10, 1e11, //
+    1e12, 1e13, 1e14, 1e15, 1e16, 1e17, 1e18, 1e19, 1e20, 1e21, 1e22, //
 ];
 
+/// The integer event for a float without a fractional part, if it is in range.
+#[allow(clippy::float_cmp)] // exact comparison is the point
+#[allow(clippy::cast_sign_loss)] // only positive floats are cast to u64
+fn integral(n: f64) -> Option<Event<'static>> {
+    // Both bounds are powers of two, so they are exact.
+    const U64_END: f64 = 18_446_744_073_709_551_616.0;
+    const I64_MIN: f64 = -9_223_372_036_854_775_808.0;
+
+    if n == 0.0 {
+        // Keep -0.0 a float, since no integer has its sign.
+        n.is_sign_positive().then_some(Nonnegative(0))
+    } else if n > 0.0 && n < U64_END && (n as u64) as f64 == n {
+        Some(Nonnegative(n as u64))
+    } else if n < 0.0 && n >= I64_MIN && (n as i64) as f64 == n {
+        Some(Negative(n as i64))
+    } else {
+        None
+    }
+}
+
 // -------------- SIMD --------------
 
+// With the portable_simd feature, one implementation serves every target.
+// Otherwise runtime feature detection needs std. Without it, the SIMD versions
+// are used only if the target is compiled with those features enabled.
 fn find_next_special_character(slice: &[u8]) -> usize {
-    #[cfg(target_arch = "x86_64")]
+    #[cfg(feature = "portable_simd")]
+    return find_special_char_portable(slice);
+    #[cfg(all(feature = "std", target_arch = "x86_64", not(feature = "portable_simd")))]
     {
         if is_x86_feature_detected!("avx2") {
             return unsafe { find_special_char_avx2(slice) };
@@ -917,6 +2090,28 @@ fn find_next_special_character(slice: &[u8]) -> usize {
             return unsafe { find_special_char_sse2(slice) };
         }
     }
+    #[cfg(all(
+        not(feature = "std"),
+        target_arch = "x86_64",
+        target_feature = "avx2",
+        not(feature = "portable_simd"),
+    ))]
+    return unsafe { find_special_char_avx2(slice) };
+    #[cfg(all(
+        not(feature = "std"),
+        target_arch = "x86_64",
+        target_feature = "sse2",
+        not(target_feature = "avx2"),
+        not(feature = "portable_simd"),
+    ))]
+    return unsafe { find_special_char_sse2(slice) };
+    #[cfg(all(
+        target_arch = "wasm32",
+        target_feature = "simd128",
+        not(feature = "portable_simd"),
+    ))]
+    return find_special_char_simd128(slice);
+    #[allow(unreachable_code)]
     find_special_char_scalar(slice)
 }
 
@@ -928,12 +2123,16 @@ fn find_special_char_scalar(slice: &[u8]) -> usize {
         .unwrap_or(slice.len())
 }
 
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(
+    target_arch = "x86_64",
+    any(feature = "std", target_feature = "avx2"),
+    not(feature = "portable_simd"),
+))]
 #[target_feature(enable = "avx2")]
 #[inline]
 #[allow(clippy::cast_ptr_alignment)]
 unsafe fn find_special_char_avx2(slice: &[u8]) -> usize {
-    use std::arch::x86_64::{_mm256_set1_epi8, _mm256_loadu_si256, _mm256_cmpeq_epi8, _mm256_movemask_epi8, _mm256_or_si256, __m256i};
+    use core::arch::x86_64::{_mm256_set1_epi8, _mm256_loadu_si256, _mm256_cmpeq_epi8, _mm256_movemask_epi8, _mm256_or_si256, __m256i};
 
     let mut i = 0;
     let len = slice.len();
@@ -942,7 +2141,7 @@ unsafe fn find_special_char_avx2(slice: &[u8]) -> usize {
     let escape_v = _mm256_set1_epi8(b'\\' as i8);
 
     while i + 32 <= len {
-        let chunk = _mm256_loadu_si256(slice.as_ptr().add(i) as *const __m256i);
+        let chunk = _mm256_loadu_si256(slice.as_ptr().add(i).cast::<__m256i>());
 
         let eq_quote = _mm256_cmpeq_epi8(chunk, quote_v);
         let eq_escape = _mm256_cmpeq_epi8(chunk, escape_v);
@@ -963,12 +2162,19 @@ unsafe fn find_special_char_avx2(slice: &[u8]) -> usize {
     i
 }
 
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(
+    target_arch = "x86_64",
+    any(
+        feature = "std",
+        all(target_feature = "sse2", not(target_feature = "avx2")),
+    ),
+    not(feature = "portable_simd"),
+))]
 #[target_feature(enable = "sse2")]
 #[inline]
 #[allow(clippy::cast_ptr_alignment)]
 unsafe fn find_special_char_sse2(slice: &[u8]) -> usize {
-    use std::arch::x86_64::{_mm_set1_epi8, _mm_loadu_si128, _mm_cmpeq_epi8, _mm_movemask_epi8, _mm_or_si128, __m128i};
+    use core::arch::x86_64::{_mm_set1_epi8, _mm_loadu_si128, _mm_cmpeq_epi8, _mm_movemask_epi8, _mm_or_si128, __m128i};
 
     let mut i = 0;
     let len = slice.len();
@@ -977,7 +2183,7 @@ unsafe fn find_special_char_sse2(slice: &[u8]) -> usize {
     let escape_v = _mm_set1_epi8(b'\\' as i8);
 
     while i + 16 <= len {
-        let chunk = _mm_loadu_si128(slice.as_ptr().add(i) as *const __m128i);
+        let chunk = _mm_loadu_si128(slice.as_ptr().add(i).cast::<__m128i>());
 
         let eq_quote = _mm_cmpeq_epi8(chunk, quote_v);
         let eq_escape = _mm_cmpeq_epi8(chunk, escape_v);
@@ -996,4 +2202,73 @@ unsafe fn find_special_char_sse2(slice: &[u8]) -> usize {
     }
 
     i
-}
\ No newline at end of file
+}
+
+#[cfg(all(
+    target_arch = "wasm32",
+    target_feature = "simd128",
+    not(feature = "portable_simd"),
+))]
+#[inline]
+#[allow(clippy::cast_ptr_alignment)]
+fn find_special_char_simd128(slice: &[u8]) -> usize {
+    use core::arch::wasm32::{u8x16_bitmask, u8x16_eq, u8x16_splat, v128, v128_load, v128_or};
+
+    let mut i = 0;
+    let len = slice.len();
+
+    let quote_v = u8x16_splat(b'"');
+    let escape_v = u8x16_splat(b'\\');
+
+    while i + 16 <= len {
+        // v128_load has no alignment requirement.
+        let chunk = unsafe { v128_load(slice.as_ptr().add(i).cast::<v128>()) };
+
+        let eq_quote = u8x16_eq(chunk, quote_v);
+        let eq_escape = u8x16_eq(chunk, escape_v);
+
+        let mask = u8x16_bitmask(v128_or(eq_quote, eq_escape));
+
+        if mask != 0 {
+            return i + mask.trailing_zeros() as usize;
+        }
+
+        i += 16;
+    }
+
+    if i < len {
+        i += find_special_char_scalar(&slice[i..]);
+    }
+
+    i
+}
+
+#[cfg(feature = "portable_simd")]
+#[inline]
+fn find_special_char_portable(slice: &[u8]) -> usize {
+    use core::simd::prelude::{u8x32, SimdPartialEq};
+
+    let mut i = 0;
+    let len = slice.len();
+
+    let quote_v = u8x32::splat(b'"');
+    let escape_v = u8x32::splat(b'\\');
+
+    while i + 32 <= len {
+        let chunk = u8x32::from_slice(&slice[i..i + 32]);
+
+        let mask = chunk.simd_eq(quote_v) | chunk.simd_eq(escape_v);
+
+        if let Some(index) = mask.first_set() {
+            return i + index;
+        }
+
+        i += 32;
+    }
+
+    if i < len {
+        i += find_special_char_scalar(&slice[i..]);
+    }
+
+    i
+}
@@ -0,0 +1,70 @@
+use crate::ser::{Fragment, Seq, Serialize};
+use alloc::boxed::Box;
+use core::cell::{RefCell, RefMut};
+
+/// Serialize the items of an iterator as a JSON array, without collecting them
+/// first.
+///
+/// Items are pulled from the iterator one at a time as the serializer reaches
+/// them, so a long computed sequence never needs to be held in memory.
+///
+/// The iterator is consumed by serializing; serializing the same `Iter` again
+/// produces whatever items remain, typically none.
+///
+/// ```rust
+/// use miniserde::json;
+///
+/// let squares = json::iter((1..5).map(|i| i * i));
+/// assert_eq!(json::to_string(&squares), "[1,4,9,16]");
+/// ```
+pub fn iter<I>(iter: I) -> Iter<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Serialize,
+{
+    Iter {
+        iter: RefCell::new(iter.into_iter()),
+    }
+}
+
+/// An iterator that serializes as a JSON array.
+///
+/// This struct is created by [`iter`].
+pub struct Iter<I> {
+    iter: RefCell<I>,
+}
+
+impl<I> Iter<I> {
+    pub fn into_inner(self) -> I {
+        self.iter.into_inner()
+    }
+}
+
+impl<I> Serialize for Iter<I>
+where
+    I: Iterator,
+    I::Item: Serialize,
+{
+    fn begin(&self) -> Fragment {
+        Fragment::Seq(Box::new(IterSeq {
+            iter: self.iter.borrow_mut(),
+            current: None,
+        }))
+    }
+}
+
+struct IterSeq<'a, I: Iterator> {
+    iter: RefMut<'a, I>,
+    current: Option<I::Item>,
+}
+
+impl<'a, I> Seq for IterSeq<'a, I>
+where
+    I: Iterator,
+    I::Item: Serialize,
+{
+    fn next(&mut self) -> Option<&dyn Serialize> {
+        self.current = self.iter.next();
+        self.current.as_ref().map(|item| item as &dyn Serialize)
+    }
+}
@@ -5,13 +5,40 @@
 
 mod ser;
 pub use self::ser::to_string;
+pub use self::ser::to_string_ascii;
+pub use self::ser::to_string_pretty;
 pub use self::ser::to_value;
 pub use self::ser::to_vec;
+pub use self::ser::to_vec_ascii;
+pub use self::ser::to_vec_pretty;
+pub use self::ser::ObjectSerializer;
+pub use self::ser::Serializer;
 
 mod de;
 pub use self::de::from_str;
+pub use self::de::from_str_borrowed;
 pub use self::de::from_slice;
+pub use self::de::from_slice_partial;
 pub use self::de::from_value;
+pub use self::de::iter_from_str;
+pub use self::de::needs_escape_scratch;
+pub use self::de::reader;
+pub use self::de::stream_from_str;
+pub use self::de::JsonLines;
+pub use self::de::Parser;
+pub use self::de::Reader;
+pub use self::de::Token;
+pub use self::de::ValueStream;
+pub use self::de::Deserializer;
+
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "std")]
+pub use self::stream::StreamString;
+
+mod iter;
+pub use self::iter::iter;
+pub use self::iter::Iter;
 
 mod value;
 pub use self::value::Value;
@@ -25,4 +52,7 @@ pub use self::array::Array;
 mod object;
 pub use self::object::Object;
 
+#[cfg(feature = "serde_json")]
+mod serde_json;
+
 mod drop;
\ No newline at end of file
@@ -4,13 +4,85 @@ use crate::ser::{Fragment, Serialize};
 use core::fmt::{self, Display};
 
 /// A JSON number represented by some Rust primitive.
-#[derive(Clone, Debug)]
+///
+/// When parsing, `-0` is the integer zero, `U64(0)`, since no integer has a
+/// sign on zero. A zero with a fraction or exponent, such as `-0.0` or
+/// `-0e-9999`, is a float and keeps its sign, as does a negative number too
+/// small for `f64`, such as `-1e-400`. `F64(-0.0)` serializes as `-0.0`, so it
+/// survives a round trip.
+#[derive(Clone, Debug, PartialEq)]
 pub enum Number {
     U64(u64),
     I64(i64),
     F64(f64),
 }
 
+impl Number {
+    /// A number from a float, or `None` if it is NaN or infinite, which JSON
+    /// cannot represent.
+    ///
+    /// ```rust
+    /// use miniserde::json::Number;
+    ///
+    /// assert!(Number::from_f64(1.5).is_some());
+    /// assert!(Number::from_f64(f64::NAN).is_none());
+    /// ```
+    pub fn from_f64(n: f64) -> Option<Self> {
+        if n.is_finite() {
+            Some(Self::F64(n))
+        } else {
+            None
+        }
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($ty:ident)*) => {
+        $(
+            impl From<$ty> for Number {
+                fn from(n: $ty) -> Self {
+                    Self::U64(n as u64)
+                }
+            }
+        )*
+    };
+}
+
+// Like the deserializer, this uses I64 only for negative numbers.
+macro_rules! from_signed {
+    ($($ty:ident)*) => {
+        $(
+            impl From<$ty> for Number {
+                #[allow(clippy::cast_sign_loss)] // only nonnegative values are cast
+                fn from(n: $ty) -> Self {
+                    if n < 0 {
+                        Self::I64(n as i64)
+                    } else {
+                        Self::U64(n as u64)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_unsigned!(u8 u16 u32 u64 usize);
+from_signed!(i8 i16 i32 i64 isize);
+
+/// Non-finite floats are kept as they are. Use [`Number::from_f64`] to reject
+/// them.
+impl From<f64> for Number {
+    fn from(n: f64) -> Self {
+        Self::F64(n)
+    }
+}
+
+impl From<f32> for Number {
+    fn from(n: f32) -> Self {
+        Self::F64(f64::from(n))
+    }
+}
+
 impl Display for Number {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         match self {
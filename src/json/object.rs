@@ -13,7 +13,16 @@ use core::ptr;
 use core::str;
 
 /// A `BTreeMap<String, Value>` with a non-recursive drop impl.
-#[derive(Clone, Default)]
+///
+/// Keys are always kept sorted by their bytes, regardless of the order they
+/// were inserted or parsed in. Iteration and serialization follow that order,
+/// so a [`Value`] serializes canonically at every level of nesting without a
+/// separate sorting step.
+///
+/// Unlike [`Array`](crate::json::Array), there is no `with_capacity`: a
+/// B-tree allocates its nodes as entries are inserted and has no capacity to
+/// reserve up front.
+#[derive(Clone, Default, PartialEq)]
 pub struct Object {
     inner: BTreeMap<String, Value>,
 }
@@ -37,6 +46,51 @@ impl Object {
             inner: BTreeMap::new(),
         }
     }
+
+    /// Get the entry for a key, to insert or update its value with a single
+    /// lookup.
+    ///
+    /// ```rust
+    /// use miniserde::json::{Number, Object, Value};
+    ///
+    /// let mut object = Object::new();
+    /// object
+    ///     .entry("count".to_owned())
+    ///     .or_insert(Value::Number(Number::U64(0)));
+    /// ```
+    pub fn entry(&mut self, key: String) -> btree_map::Entry<String, Value> {
+        self.inner.entry(key)
+    }
+
+    /// Remove a key, returning its value if it was present.
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        self.inner.remove(key)
+    }
+
+    /// Whether the object has a value for the key.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.inner.contains_key(key)
+    }
+
+    /// Iterate over the entries in key order.
+    pub fn iter(&self) -> btree_map::Iter<String, Value> {
+        self.inner.iter()
+    }
+
+    /// Iterate over the entries in key order, with mutable values.
+    pub fn iter_mut(&mut self) -> btree_map::IterMut<String, Value> {
+        self.inner.iter_mut()
+    }
+
+    /// Iterate over the keys in order.
+    pub fn keys(&self) -> btree_map::Keys<String, Value> {
+        self.inner.keys()
+    }
+
+    /// Iterate over the values in key order.
+    pub fn values(&self) -> btree_map::Values<String, Value> {
+        self.inner.values()
+    }
 }
 
 impl Deref for Object {
@@ -1,9 +1,13 @@
+use crate::error::{Error, Result};
 use crate::json::{Array, Number, Object, Value};
-use crate::ser::{Fragment, Map, Seq, Serialize};
+use crate::ser::{Fragment, Map, Numbers, Seq, Serialize, StrChunks};
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::fmt::{self, Write as _};
+use core::mem;
+use core::str;
 
 mod writer {
     use alloc::string::String;
@@ -40,6 +44,16 @@ mod writer {
 
 /// Convert any serializable type into a `miniserde::json::Value`.
 ///
+/// Arrays and objects are filled in one element at a time as the input
+/// produces them, and no intermediate copy of any of them is kept. So when a
+/// sequence comes from [`json::iter`][crate::json::iter], each item is turned
+/// into a `Value` and dropped before the next is pulled from the iterator.
+/// Peak memory is then the returned `Value` plus a single item, however long
+/// the iterator.
+///
+/// A `Value` anywhere in the input, including the input itself, is cloned
+/// rather than serialized and rebuilt.
+///
 /// ```rust
 /// use miniserde::{json, Serialize};
 /// use miniserde::json::Value;
@@ -60,50 +74,90 @@ mod writer {
 ///     println!("{:?}", value);
 /// }
 /// ```
+#[allow(clippy::items_after_statements, clippy::missing_panics_doc)]
 pub fn to_value<T>(value: &T) -> Value
 where
     T: ?Sized + Serialize,
 {
+    // A Value, at the top or anywhere inside, is cloned as a whole rather
+    // than taken apart into fragments and put back together.
+    if let Some(value) = value.__value() {
+        return value.clone();
+    }
+
     let mut stack = Vec::new();
-    let mut fragment = value.begin();
+    let mut next = begin(&value);
 
     enum Layer<'a> {
         Seq(Box<dyn Seq + 'a>, Array),
-        Map(Box<dyn Map + 'a>, Object, Option<String>),
+        Map(Box<dyn Map + 'a>, Object, String),
+    }
+
+    enum Next<'a> {
+        Fragment(Fragment<'a>),
+        Value(&'a Value),
+        Chunks(Box<dyn StrChunks + 'a>),
+    }
+
+    #[allow(clippy::option_if_let_else)]
+    fn begin(element: &dyn Serialize) -> Next {
+        if let Some(value) = element.__value() {
+            Next::Value(value)
+        } else if let Some(chunks) = element.__str_chunks() {
+            Next::Chunks(chunks)
+        } else {
+            Next::Fragment(element.begin())
+        }
     }
 
     loop {
-        let val = match fragment {
-            Fragment::Null => Value::Null,
-            Fragment::Bool(b) => Value::Bool(b),
-            Fragment::Str(s) => Value::String(s.into_owned()),
-            Fragment::U64(n) => Value::Number(Number::U64(n)),
-            Fragment::I64(n) => Value::Number(Number::I64(n)),
-            Fragment::F64(n) => Value::Number(Number::F64(n)),
-            Fragment::Seq(mut seq) => {
-                let next = unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) };
-                match next {
-                    Some(first) => {
-                        stack.push(Layer::Seq(seq, Array::new()));
-                        fragment = first.begin();
-                        continue;
-                    }
-                    None => Value::Array(Array::new()),
+        let val = match next {
+            Next::Value(value) => value.clone(),
+            Next::Chunks(mut chunks) => {
+                let mut string = String::new();
+                while let Some(chunk) = chunks.next() {
+                    string.push_str(chunk);
                 }
+                Value::String(string)
             }
-            Fragment::Map(mut map) => {
-                let next = unsafe {
-                    extend_lifetime!(map.next() as Option<(Cow<str>, &dyn Serialize)>)
-                };
-                match next {
-                    Some((key, first)) => {
-                        stack.push(Layer::Map(map, Object::new(), Some(key.into_owned())));
-                        fragment = first.begin();
-                        continue;
+            Next::Fragment(fragment) => match fragment {
+                Fragment::Null => Value::Null,
+                Fragment::Bool(b) => Value::Bool(b),
+                // Cow::into_owned moves an owned string without copying it,
+                // so only borrowed strings allocate here. Keys work the same.
+                Fragment::Str(s) => Value::String(s.into_owned()),
+                Fragment::U64(n) => Value::Number(Number::U64(n)),
+                Fragment::I64(n) => Value::Number(Number::I64(n)),
+                Fragment::F64(n) => Value::Number(Number::F64(n)),
+                Fragment::Seq(mut seq) => {
+                    if let Some(numbers) = seq.__numbers() {
+                        Value::Array(numbers_to_array(numbers))
+                    } else {
+                        let element = unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) };
+                        match element {
+                            Some(first) => {
+                                stack.push(Layer::Seq(seq, Array::new()));
+                                next = begin(first);
+                                continue;
+                            }
+                            None => Value::Array(Array::new()),
+                        }
                     }
-                    None => Value::Object(Object::new()),
                 }
-            }
+                Fragment::Map(mut map) => {
+                    let entry = unsafe {
+                        extend_lifetime!(map.next() as Option<(Cow<str>, &dyn Serialize)>)
+                    };
+                    match entry {
+                        Some((key, first)) => {
+                            stack.push(Layer::Map(map, Object::new(), key.into_owned()));
+                            next = begin(first);
+                            continue;
+                        }
+                        None => Value::Object(Object::new()),
+                    }
+                }
+            },
         };
 
         let mut current_val = val;
@@ -112,37 +166,34 @@ where
                 None => return current_val,
                 Some(Layer::Seq(seq, arr)) => {
                     arr.push(current_val);
-                    let next = unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) };
-                    match next {
+                    let element = unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) };
+                    match element {
                         Some(next_elem) => {
-                            fragment = next_elem.begin();
+                            next = begin(next_elem);
                             break;
                         }
                         None => {
-                            let arr = match stack.pop() {
-                                Some(Layer::Seq(_, a)) => a,
-                                _ => unreachable!(),
+                            let Some(Layer::Seq(_, arr)) = stack.pop() else {
+                                unreachable!()
                             };
                             current_val = Value::Array(arr);
                         }
                     }
                 }
-                Some(Layer::Map(map, obj, key_opt)) => {
-                    let key = key_opt.take().expect("Map layer without pending key");
-                    obj.insert(key, current_val);
-                    let next = unsafe {
+                Some(Layer::Map(map, obj, pending_key)) => {
+                    obj.insert(mem::take(pending_key), current_val);
+                    let entry = unsafe {
                         extend_lifetime!(map.next() as Option<(Cow<str>, &dyn Serialize)>)
                     };
-                    match next {
+                    match entry {
                         Some((key, next_elem)) => {
-                            *key_opt = Some(key.into_owned());
-                            fragment = next_elem.begin();
+                            *pending_key = key.into_owned();
+                            next = begin(next_elem);
                             break;
                         }
                         None => {
-                            let obj = match stack.pop() {
-                                Some(Layer::Map(_, o, _)) => o,
-                                _ => unreachable!(),
+                            let Some(Layer::Map(_, obj, _)) = stack.pop() else {
+                                unreachable!()
                             };
                             current_val = Value::Object(obj);
                         }
@@ -153,6 +204,29 @@ where
     }
 }
 
+// A numeric slice becomes an array allocated once at its final size, with no
+// trait object call per element.
+fn numbers_to_array(numbers: Numbers) -> Array {
+    fn collect<T: Copy>(slice: &[T], number: fn(T) -> Number) -> Array {
+        slice.iter().map(|&n| Value::Number(number(n))).collect()
+    }
+
+    match numbers {
+        Numbers::U8(slice) => collect(slice, |n| Number::U64(u64::from(n))),
+        Numbers::U16(slice) => collect(slice, |n| Number::U64(u64::from(n))),
+        Numbers::U32(slice) => collect(slice, |n| Number::U64(u64::from(n))),
+        Numbers::U64(slice) => collect(slice, Number::U64),
+        Numbers::Usize(slice) => collect(slice, |n| Number::U64(n as u64)),
+        Numbers::I8(slice) => collect(slice, |n| Number::I64(i64::from(n))),
+        Numbers::I16(slice) => collect(slice, |n| Number::I64(i64::from(n))),
+        Numbers::I32(slice) => collect(slice, |n| Number::I64(i64::from(n))),
+        Numbers::I64(slice) => collect(slice, Number::I64),
+        Numbers::Isize(slice) => collect(slice, |n| Number::I64(n as i64)),
+        Numbers::F32(slice) => collect(slice, |n| Number::F64(f64::from(n))),
+        Numbers::F64(slice) => collect(slice, Number::F64),
+    }
+}
+
 /// Serialize any serializable type into a JSON string.
 ///
 /// ```rust
@@ -179,7 +253,8 @@ where
     T: ?Sized + Serialize,
 {
     let mut out = String::with_capacity(128);
-    to_writer_impl(&value, &mut out);
+    // Serialization with the default settings never fails.
+    let _ = to_writer_impl(&value, &mut out, Serializer::new());
     out
 }
 
@@ -188,12 +263,334 @@ where
     T: ?Sized + Serialize,
 {
     let mut out = Vec::with_capacity(128);
-    to_writer_impl(&value, &mut out);
+    let _ = to_writer_impl(&value, &mut out, Serializer::new());
+    out
+}
+
+/// Serialize any serializable type into a JSON string containing only ASCII.
+///
+/// Every non-ASCII character in strings and map keys is written as a `\uXXXX`
+/// escape, using a UTF-16 surrogate pair for characters outside the Basic
+/// Multilingual Plane. The output parses back to the same data as the output
+/// of [`to_string`].
+///
+/// ```rust
+/// use miniserde::json;
+///
+/// let j = json::to_string_ascii("café 🦀");
+/// assert_eq!(j, r#""caf\u00e9 \ud83e\udd80""#);
+/// ```
+pub fn to_string_ascii<T>(value: &T) -> String
+where
+    T: ?Sized + Serialize,
+{
+    let mut out = String::with_capacity(128);
+    let _ = to_writer_impl(&value, &mut out, Serializer::new().ascii(true));
+    out
+}
+
+/// Like [`to_string_ascii`] but produces the output as bytes.
+pub fn to_vec_ascii<T>(value: &T) -> Vec<u8>
+where
+    T: ?Sized + Serialize,
+{
+    let mut out = Vec::with_capacity(128);
+    let _ = to_writer_impl(&value, &mut out, Serializer::new().ascii(true));
+    out
+}
+
+/// Serialize any serializable type into a JSON string with newlines and
+/// two-space indentation.
+///
+/// ```rust
+/// use miniserde::json;
+///
+/// let j = json::to_string_pretty(&vec![vec![1, 2], vec![]]);
+/// assert_eq!(j, "[\n  [\n    1,\n    2\n  ],\n  []\n]");
+/// ```
+pub fn to_string_pretty<T>(value: &T) -> String
+where
+    T: ?Sized + Serialize,
+{
+    let mut out = String::with_capacity(128);
+    let _ = to_writer_impl(&value, &mut out, Serializer::new().pretty(true));
+    out
+}
+
+/// Like [`to_string_pretty`] but produces the output as bytes.
+pub fn to_vec_pretty<T>(value: &T) -> Vec<u8>
+where
+    T: ?Sized + Serialize,
+{
+    let mut out = Vec::with_capacity(128);
+    let _ = to_writer_impl(&value, &mut out, Serializer::new().pretty(true));
     out
 }
 
-struct Serializer<'a> {
-    stack: Vec<Layer<'a>>,
+/// JSON serializer with non-default settings.
+///
+/// The plain [`to_string`] and [`to_vec`] functions are equivalent to
+/// serializing with `Serializer::new()`.
+///
+/// ```rust
+/// use miniserde::json;
+///
+/// let result = json::Serializer::new()
+///     .reject_non_finite(true)
+///     .to_string(&f64::NAN);
+/// assert!(result.is_err());
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Serializer {
+    ascii: bool,
+    escape_slash: bool,
+    escape_html: bool,
+    reject_non_finite: bool,
+    sig_digits: Option<usize>,
+    decimal_places: Option<usize>,
+    pretty: bool,
+    max_pretty_depth: Option<usize>,
+}
+
+#[allow(clippy::return_self_not_must_use)]
+impl Serializer {
+    pub const fn new() -> Self {
+        Self {
+            ascii: false,
+            escape_slash: false,
+            escape_html: false,
+            reject_non_finite: false,
+            sig_digits: None,
+            decimal_places: None,
+            pretty: false,
+            max_pretty_depth: None,
+        }
+    }
+
+    /// Escape every non-ASCII character, as in [`to_string_ascii`].
+    pub const fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Escape every `/` in strings as `\/`.
+    ///
+    /// Plain JSON does not need this, but it keeps a serialized `</script>`
+    /// from ending an HTML script element the JSON is embedded in.
+    ///
+    /// ```rust
+    /// use miniserde::json;
+    ///
+    /// let serializer = json::Serializer::new().escape_slash(true);
+    /// assert_eq!(serializer.to_string("a/b").unwrap(), r#""a\/b""#);
+    /// ```
+    pub const fn escape_slash(mut self, escape: bool) -> Self {
+        self.escape_slash = escape;
+        self
+    }
+
+    /// Escape every `<`, `>` and `&` in strings as `\u003c`, `\u003e` and
+    /// `\u0026`.
+    ///
+    /// The output is then safe to place inside an HTML page, where a string
+    /// containing markup could otherwise end the surrounding element or
+    /// inject a new one. It still parses back to the same strings.
+    ///
+    /// ```rust
+    /// use miniserde::json;
+    ///
+    /// let serializer = json::Serializer::new().escape_html(true);
+    /// let j = serializer.to_string("<b>&</b>").unwrap();
+    /// assert_eq!(j, r#""\u003cb\u003e\u0026\u003c/b\u003e""#);
+    /// ```
+    pub const fn escape_html(mut self, escape: bool) -> Self {
+        self.escape_html = escape;
+        self
+    }
+
+    /// Fail on NaN and infinite floats instead of writing them as `null`.
+    ///
+    /// JSON has no representation for non-finite numbers. By default they are
+    /// written as `null`, which is lossy and may hide bugs.
+    pub const fn reject_non_finite(mut self, reject: bool) -> Self {
+        self.reject_non_finite = reject;
+        self
+    }
+
+    /// Round every float to the given number of significant decimal digits
+    /// before formatting it.
+    ///
+    /// Rounding is done on the decimal representation, so the same value
+    /// always produces the same output. The tradeoff is precision: unless
+    /// `digits` is at least 17, the written number may not parse back to the
+    /// exact same `f64`. The number of digits is clamped to the range 1..=17.
+    ///
+    /// ```rust
+    /// use miniserde::json;
+    ///
+    /// let serializer = json::Serializer::new().round_sig_digits(3);
+    /// assert_eq!(serializer.to_string(&1234.5678).unwrap(), "1230.0");
+    /// assert_eq!(serializer.to_string(&(2.0 / 3.0)).unwrap(), "0.667");
+    /// ```
+    pub const fn round_sig_digits(mut self, digits: usize) -> Self {
+        self.sig_digits = Some(if digits < 1 {
+            1
+        } else if digits > 17 {
+            17
+        } else {
+            digits
+        });
+        self
+    }
+
+    /// Write every float with exactly `places` digits after the decimal point,
+    /// instead of the shortest form that parses back to the same `f64`.
+    ///
+    /// Floats with no fractional part get one too, so `3.0` is written as `3.00`
+    /// with 2 places. With 0 places there is no decimal point at all: `3.0` is
+    /// written as `3`, which is a JSON integer and so reads back into a
+    /// [`Value`][crate::json::Value] as a `u64` or `i64` number rather than an
+    /// `f64`. Integers are not affected. A float of magnitude 1e16 or more is
+    /// written in the usual shortest form, which may use an exponent, since its
+    /// fixed notation would be a long run of digits that `f64` does not hold
+    /// anyway. This applies after [`round_sig_digits`][Self::round_sig_digits] if
+    /// both are set.
+    ///
+    /// ```rust
+    /// use miniserde::json;
+    ///
+    /// let serializer = json::Serializer::new().fixed_decimal_places(2);
+    /// assert_eq!(serializer.to_string(&3.14159).unwrap(), "3.14");
+    /// assert_eq!(serializer.to_string(&vec![1.0, -0.005]).unwrap(), "[1.00,-0.01]");
+    /// ```
+    pub const fn fixed_decimal_places(mut self, places: usize) -> Self {
+        self.decimal_places = Some(places);
+        self
+    }
+
+    /// Put every array element and object entry on its own line, indented by
+    /// two spaces per level of nesting, as in [`to_string_pretty`].
+    pub const fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Only indent the outermost `depth` levels of nesting when pretty
+    /// printing. Arrays and objects nested deeper than that are written on a
+    /// single line in compact form.
+    ///
+    /// A depth of 0 produces the same output as not pretty printing at all.
+    /// This has no effect unless [`pretty`][Self::pretty] is enabled.
+    ///
+    /// ```rust
+    /// use miniserde::json;
+    ///
+    /// let serializer = json::Serializer::new().pretty(true).max_pretty_depth(1);
+    /// let j = serializer.to_string(&vec![vec![1, 2], vec![3]]).unwrap();
+    /// assert_eq!(j, "[\n  [1,2],\n  [3]\n]");
+    /// ```
+    pub const fn max_pretty_depth(mut self, depth: usize) -> Self {
+        self.max_pretty_depth = Some(depth);
+        self
+    }
+
+    /// Whether an array or object nested inside `depth` others is indented.
+    const fn is_pretty(&self, depth: usize) -> bool {
+        self.pretty
+            && match self.max_pretty_depth {
+                Some(max) => depth < max,
+                None => true,
+            }
+    }
+
+    /// Serialize any serializable type into a JSON string.
+    pub fn to_string<T>(&self, value: &T) -> Result<String>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut out = String::with_capacity(128);
+        to_writer_impl(&value, &mut out, *self)?;
+        Ok(out)
+    }
+
+    /// Serialize any serializable type into JSON bytes.
+    pub fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut out = Vec::with_capacity(128);
+        to_writer_impl(&value, &mut out, *self)?;
+        Ok(out)
+    }
+}
+
+/// Write a JSON object into a string one entry at a time, for output whose
+/// shape is not known until runtime.
+///
+/// The opening brace is written by the constructor, each call to
+/// [`field`][Self::field] writes one entry, and [`end`][Self::end] writes the
+/// closing brace. Values are serialized straight into the string.
+///
+/// ```rust
+/// use miniserde::json;
+///
+/// fn main() -> miniserde::Result<()> {
+///     let mut out = String::new();
+///     let mut object = json::ObjectSerializer::new(&mut out);
+///     for (key, value) in [("a", 1), ("b", 2)] {
+///         object.field(key, &value)?;
+///     }
+///     object.end();
+///     assert_eq!(out, r#"{"a":1,"b":2}"#);
+///
+///     Ok(())
+/// }
+/// ```
+pub struct ObjectSerializer<'a> {
+    out: &'a mut String,
+    config: Serializer,
+    empty: bool,
+}
+
+impl<'a> ObjectSerializer<'a> {
+    /// Start an object at the end of `out`.
+    pub fn new(out: &'a mut String) -> Self {
+        Self::with_config(out, Serializer::new())
+    }
+
+    /// Start an object at the end of `out`, serializing values with the
+    /// settings of a configured [`Serializer`]. Pretty printing is not
+    /// supported and is ignored.
+    pub fn with_config(out: &'a mut String, config: Serializer) -> Self {
+        out.push('{');
+        Self {
+            out,
+            config: config.pretty(false),
+            empty: true,
+        }
+    }
+
+    /// Write one entry.
+    ///
+    /// On error, the output is left with a partially written entry.
+    pub fn field<T>(&mut self, key: &str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        if !self.empty {
+            self.out.push(',');
+        }
+        self.empty = false;
+        escape_str(key, self.out, &self.config);
+        self.out.push(':');
+        to_writer_impl(&value, self.out, self.config)
+    }
+
+    /// Write the closing brace.
+    pub fn end(self) {
+        self.out.push('}');
+    }
 }
 
 enum Layer<'a> {
@@ -201,49 +598,75 @@ enum Layer<'a> {
     Map(Box<dyn Map + 'a>),
 }
 
-fn to_writer_impl<W>(value: &dyn Serialize, out: &mut W)
+fn to_writer_impl<W>(value: &dyn Serialize, out: &mut W, config: Serializer) -> Result<()>
 where
     W: ?Sized + writer::Write,
 {
-    let mut serializer = Serializer { stack: Vec::new() };
-    let mut fragment = value.begin();
+    enum Next<'a> {
+        Fragment(Fragment<'a>),
+        Chunks(Box<dyn StrChunks + 'a>),
+    }
+
+    #[allow(clippy::option_if_let_else)]
+    fn begin(element: &dyn Serialize) -> Next {
+        match element.__str_chunks() {
+            Some(chunks) => Next::Chunks(chunks),
+            None => Next::Fragment(element.begin()),
+        }
+    }
+
+    let mut stack = Vec::new();
+    let mut fragment = begin(value);
 
     'outer: loop {
         match fragment {
-            Fragment::Null => out.write_str("null"),
-            Fragment::Bool(b) => out.write_str(if b { "true" } else { "false" }),
-            Fragment::Str(s) => escape_str(&s, out),
-            Fragment::U64(n) => out.write_str(itoa::Buffer::new().format(n)),
-            Fragment::I64(n) => out.write_str(itoa::Buffer::new().format(n)),
-            Fragment::F64(n) => {
-                if n.is_finite() {
-                    out.write_str(ryu::Buffer::new().format_finite(n));
-                } else {
-                    out.write_str("null");
+            Next::Chunks(mut chunks) => {
+                out.write_char('"');
+                while let Some(chunk) = chunks.next() {
+                    escape_str_contents(chunk, out, &config);
                 }
+                out.write_char('"');
             }
-            Fragment::Seq(mut seq) => {
-                out.write_char('[');
-                // invariant: `seq` must outlive `first`
-                match unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) } {
-                    Some(first) => {
-                        serializer.stack.push(Layer::Seq(seq));
-                        fragment = first.begin();
-                        continue 'outer;
+            Next::Fragment(Fragment::Null) => out.write_str("null"),
+            Next::Fragment(Fragment::Bool(b)) => out.write_str(if b { "true" } else { "false" }),
+            Next::Fragment(Fragment::Str(s)) => escape_str(&s, out, &config),
+            Next::Fragment(Fragment::U64(n)) => out.write_str(itoa::Buffer::new().format(n)),
+            Next::Fragment(Fragment::I64(n)) => out.write_str(itoa::Buffer::new().format(n)),
+            Next::Fragment(Fragment::F64(n)) => write_f64(n, out, &mut ryu::Buffer::new(), config)?,
+            Next::Fragment(Fragment::Seq(mut seq)) => {
+                let pretty = config.is_pretty(stack.len());
+                if let (false, Some(numbers)) = (pretty, seq.__numbers()) {
+                    write_numbers(numbers, out, config)?;
+                } else {
+                    out.write_char('[');
+                    // invariant: `seq` must outlive `first`
+                    match unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) } {
+                        Some(first) => {
+                            if pretty {
+                                write_indent(out, stack.len() + 1);
+                            }
+                            stack.push(Layer::Seq(seq));
+                            fragment = begin(first);
+                            continue 'outer;
+                        }
+                        None => out.write_char(']'),
                     }
-                    None => out.write_char(']'),
                 }
             }
-            Fragment::Map(mut map) => {
+            Next::Fragment(Fragment::Map(mut map)) => {
                 out.write_char('{');
                 // invariant: `map` must outlive `first`
                 match unsafe { extend_lifetime!(map.next() as Option<(Cow<str>, &dyn Serialize)>) }
                 {
                     Some((key, first)) => {
-                        escape_str(&key, out);
-                        out.write_char(':');
-                        serializer.stack.push(Layer::Map(map));
-                        fragment = first.begin();
+                        let pretty = config.is_pretty(stack.len());
+                        if pretty {
+                            write_indent(out, stack.len() + 1);
+                        }
+                        escape_str(&key, out, &config);
+                        out.write_str(if pretty { ": " } else { ":" });
+                        stack.push(Layer::Map(map));
+                        fragment = begin(first);
                         continue 'outer;
                     }
                     None => out.write_char('}'),
@@ -252,18 +675,26 @@ where
         }
 
         loop {
-            match serializer.stack.last_mut() {
+            let depth = stack.len().saturating_sub(1);
+            let pretty = config.is_pretty(depth);
+            match stack.last_mut() {
                 Some(Layer::Seq(seq)) => {
                     // invariant: `seq` must outlive `next`
                     match unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) } {
                         Some(next) => {
                             out.write_char(',');
-                            fragment = next.begin();
+                            if pretty {
+                                write_indent(out, depth + 1);
+                            }
+                            fragment = begin(next);
                             break;
                         }
                         None => {
+                            if pretty {
+                                write_indent(out, depth);
+                            }
                             out.write_char(']');
-                            serializer.stack.pop();
+                            stack.pop();
                         }
                     }
                 }
@@ -274,40 +705,214 @@ where
                     } {
                         Some((key, next)) => {
                             out.write_char(',');
-                            escape_str(&key, out);
-                            out.write_char(':');
-                            fragment = next.begin();
+                            if pretty {
+                                write_indent(out, depth + 1);
+                            }
+                            escape_str(&key, out, &config);
+                            out.write_str(if pretty { ": " } else { ":" });
+                            fragment = begin(next);
                             break;
                         }
                         None => {
+                            if pretty {
+                                write_indent(out, depth);
+                            }
                             out.write_char('}');
-                            serializer.stack.pop();
+                            stack.pop();
                         }
                     }
                 }
-                None => return,
+                None => return Ok(()),
             }
         }
     }
 }
 
-fn escape_str<W>(value: &str, out: &mut W)
+#[allow(clippy::option_if_let_else)]
+fn write_f64<W>(n: f64, out: &mut W, buffer: &mut ryu::Buffer, config: Serializer) -> Result<()>
 where
     W: ?Sized + writer::Write,
 {
+    if n.is_finite() {
+        let n = match config.sig_digits {
+            Some(digits) => round_sig_digits(n, digits),
+            None => n,
+        };
+        match config.decimal_places {
+            Some(places) if n.abs() < 1e16 => write_fixed(n, places, out),
+            _ => out.write_str(buffer.format_finite(n)),
+        }
+    } else if config.reject_non_finite {
+        return Err(Error);
+    } else {
+        out.write_str("null");
+    }
+    Ok(())
+}
+
+fn write_fixed<W>(n: f64, places: usize, out: &mut W)
+where
+    W: ?Sized + writer::Write,
+{
+    struct Adapter<'a, W: ?Sized>(&'a mut W);
+
+    impl<'a, W> fmt::Write for Adapter<'a, W>
+    where
+        W: ?Sized + writer::Write,
+    {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0.write_str(s);
+            Ok(())
+        }
+    }
+
+    // Writing to a string cannot fail.
+    let _ = write!(Adapter(out), "{:.*}", places, n);
+}
+
+/// Write a whole array of primitive numbers with a single formatting buffer,
+/// rather than one `Fragment` per element.
+fn write_numbers<W>(numbers: Numbers, out: &mut W, config: Serializer) -> Result<()>
+where
+    W: ?Sized + writer::Write,
+{
+    fn integers<T, W>(slice: &[T], out: &mut W)
+    where
+        T: itoa::Integer + Copy,
+        W: ?Sized + writer::Write,
+    {
+        let mut buffer = itoa::Buffer::new();
+        for (i, n) in slice.iter().enumerate() {
+            if i > 0 {
+                out.write_char(',');
+            }
+            out.write_str(buffer.format(*n));
+        }
+    }
+
+    fn floats<T, W>(slice: &[T], out: &mut W, config: Serializer) -> Result<()>
+    where
+        T: Into<f64> + Copy,
+        W: ?Sized + writer::Write,
+    {
+        let mut buffer = ryu::Buffer::new();
+        for (i, n) in slice.iter().enumerate() {
+            if i > 0 {
+                out.write_char(',');
+            }
+            write_f64((*n).into(), out, &mut buffer, config)?;
+        }
+        Ok(())
+    }
+
+    out.write_char('[');
+    match numbers {
+        Numbers::U8(slice) => integers(slice, out),
+        Numbers::U16(slice) => integers(slice, out),
+        Numbers::U32(slice) => integers(slice, out),
+        Numbers::U64(slice) => integers(slice, out),
+        Numbers::Usize(slice) => integers(slice, out),
+        Numbers::I8(slice) => integers(slice, out),
+        Numbers::I16(slice) => integers(slice, out),
+        Numbers::I32(slice) => integers(slice, out),
+        Numbers::I64(slice) => integers(slice, out),
+        Numbers::Isize(slice) => integers(slice, out),
+        Numbers::F32(slice) => floats(slice, out, config)?,
+        Numbers::F64(slice) => floats(slice, out, config)?,
+    }
+    out.write_char(']');
+    Ok(())
+}
+
+/// Start a new line indented to the given depth.
+fn write_indent<W>(out: &mut W, depth: usize)
+where
+    W: ?Sized + writer::Write,
+{
+    out.write_char('\n');
+    for _ in 0..depth {
+        out.write_str("  ");
+    }
+}
+
+fn round_sig_digits(n: f64, digits: usize) -> f64 {
+    struct Buffer {
+        bytes: [u8; 32],
+        len: usize,
+    }
+
+    impl fmt::Write for Buffer {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    // Formatting with an exponent and parsing are both correctly rounded by
+    // core, so this is exact and platform independent. At most 17 digits plus
+    // sign, point and a 4 character exponent fit in the buffer.
+    let mut buffer = Buffer {
+        bytes: [0; 32],
+        len: 0,
+    };
+    if write!(buffer, "{:.*e}", digits - 1, n).is_err() {
+        return n;
+    }
+    str::from_utf8(&buffer.bytes[..buffer.len])
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(n)
+}
+
+fn escape_str<W>(value: &str, out: &mut W, config: &Serializer)
+where
+    W: ?Sized + writer::Write,
+{
+    out.write_char('"');
+    escape_str_contents(value, out, config);
     out.write_char('"');
+}
 
-    let mut start = 0;
+/// Write the escaped contents of a string, without the surrounding quotes.
+fn escape_str_contents<W>(value: &str, out: &mut W, config: &Serializer)
+where
+    W: ?Sized + writer::Write,
+{
+    let table = if config.ascii {
+        &ESCAPE_ASCII
+    } else {
+        &ESCAPE
+    };
     let bytes = value.as_bytes();
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        #[cfg(feature = "portable_simd")]
+        {
+            i += unescaped_prefix_len(&bytes[i..], config);
+            if i == bytes.len() {
+                break;
+            }
+        }
 
-    for (i, &byte) in bytes.iter().enumerate() {
-        let escape = ESCAPE[byte as usize];
-        if escape == 0 {
+        let byte = bytes[i];
+        let escape = table[byte as usize];
+        if escape == 0
+            || (escape == SL && !config.escape_slash)
+            || (escape == HT && !config.escape_html)
+        {
+            i += 1;
             continue;
         }
 
         if start < i {
-            out.write_str(unsafe { core::str::from_utf8_unchecked(&bytes[start..i]) });
+            out.write_str(unsafe { str::from_utf8_unchecked(&bytes[start..i]) });
         }
 
         let escaped_char = match escape {
@@ -318,32 +923,91 @@ where
             RR => "\\r",
             QU => "\\\"",
             BS => "\\\\",
-            U => {
-                static HEX_DIGITS: [u8; 16] = *b"0123456789abcdef";
-                let mut buf = [0u8; 6];
-                buf[0] = b'\\';
-                buf[1] = b'u';
-                buf[2] = b'0';
-                buf[3] = b'0';
-                buf[4] = HEX_DIGITS[(byte >> 4) as usize];
-                buf[5] = HEX_DIGITS[(byte & 0xF) as usize];
-
-                out.write_str(unsafe { core::str::from_utf8_unchecked(&buf) });
-                start = i + 1;
+            SL => "\\/",
+            U | HT => {
+                write_unicode_escape(u16::from(byte), out);
+                i += 1;
+                start = i;
+                continue;
+            }
+            UC => {
+                // Only reachable in ASCII mode, at the first byte of a
+                // non-ASCII character. Continuation bytes are consumed here
+                // together with their leading byte.
+                let ch = value[i..].chars().next().unwrap();
+                let mut buf = [0u16; 2];
+                for &unit in ch.encode_utf16(&mut buf).iter() {
+                    write_unicode_escape(unit, out);
+                }
+                i += ch.len_utf8();
+                start = i;
                 continue;
             }
             _ => unreachable!(),
         };
         out.write_str(escaped_char);
 
-        start = i + 1;
+        i += 1;
+        start = i;
     }
 
     if start < bytes.len() {
-        out.write_str(unsafe { core::str::from_utf8_unchecked(&bytes[start..]) });
+        out.write_str(unsafe { str::from_utf8_unchecked(&bytes[start..]) });
     }
+}
 
-    out.write_char('"');
+/// The number of leading bytes that need no escaping with these settings,
+/// looking only at whole 32-byte chunks. The first byte after them may or may
+/// not need escaping.
+#[cfg(feature = "portable_simd")]
+fn unescaped_prefix_len(bytes: &[u8], config: &Serializer) -> usize {
+    use core::simd::prelude::{u8x32, SimdPartialEq, SimdPartialOrd};
+
+    let splat = u8x32::splat;
+    let mut i = 0;
+
+    while i + 32 <= bytes.len() {
+        let chunk = u8x32::from_slice(&bytes[i..i + 32]);
+
+        let mut mask = chunk.simd_lt(splat(0x20))
+            | chunk.simd_eq(splat(b'"'))
+            | chunk.simd_eq(splat(b'\\'));
+        if config.ascii {
+            mask |= chunk.simd_ge(splat(0x80));
+        }
+        if config.escape_slash {
+            mask |= chunk.simd_eq(splat(b'/'));
+        }
+        if config.escape_html {
+            mask |= chunk.simd_eq(splat(b'<'))
+                | chunk.simd_eq(splat(b'>'))
+                | chunk.simd_eq(splat(b'&'));
+        }
+
+        if let Some(index) = mask.first_set() {
+            return i + index;
+        }
+
+        i += 32;
+    }
+
+    i
+}
+
+fn write_unicode_escape<W>(unit: u16, out: &mut W)
+where
+    W: ?Sized + writer::Write,
+{
+    static HEX_DIGITS: [u8; 16] = *b"0123456789abcdef";
+    let buf = [
+        b'\\',
+        b'u',
+        HEX_DIGITS[(unit >> 12) as usize],
+        HEX_DIGITS[(unit >> 8 & 0xF) as usize],
+        HEX_DIGITS[(unit >> 4 & 0xF) as usize],
+        HEX_DIGITS[(unit & 0xF) as usize],
+    ];
+    out.write_str(unsafe { str::from_utf8_unchecked(&buf) });
 }
 
 const BB: u8 = b'b'; // \x08
@@ -353,17 +1017,20 @@ const FF: u8 = b'f'; // \x0C
 const RR: u8 = b'r'; // \x0D
 const QU: u8 = b'"'; // \x22
 const BS: u8 = b'\\'; // \x5C
+const SL: u8 = b'/'; // \x2F, only if escape_slash is set
+const HT: u8 = b'h'; // \x26 \x3C \x3E, only if escape_html is set
 const U: u8 = b'u'; // \x00...\x1F except the ones above
+const UC: u8 = b'U'; // \x80...\xFF, only in ASCII mode
 
 // Lookup table of escape sequences. A value of b'x' at index i means that byte
 // i is escaped as "\x" in JSON. A value of 0 means that byte i is not escaped.
 #[rustfmt::skip]
-static ESCAPE: [u8; 256] = [
+const ESCAPE: [u8; 256] = [
     //  1   2   3   4   5   6   7   8   9   A   B   C   D   E   F
     U,  U,  U,  U,  U,  U,  U,  U, BB, TT, NN,  U, FF, RR,  U,  U, // 0
     U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U, // 1
-    0,  0, QU,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 2
-    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 3
+    0,  0, QU,  0,  0,  0, HT,  0,  0,  0,  0,  0,  0,  0,  0, SL, // 2
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, HT,  0, HT,  0, // 3
     0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 4
     0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, BS,  0,  0,  0, // 5
     0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 6
@@ -376,4 +1043,16 @@ static ESCAPE: [u8; 256] = [
     0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // D
     0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // E
     0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // F
-];
\ No newline at end of file
+];
+
+// Same as ESCAPE but additionally escaping every non-ASCII byte, for output
+// that must survive systems which mishandle raw UTF-8.
+static ESCAPE_ASCII: [u8; 256] = {
+    let mut table = ESCAPE;
+    let mut i = 0x80;
+    while i < 256 {
+        table[i] = UC;
+        i += 1;
+    }
+    table
+};
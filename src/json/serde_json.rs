@@ -0,0 +1,161 @@
+//! Conversions to and from `serde_json::Value`, with the `serde_json` feature.
+//!
+//! Both directions are lossless except for numbers:
+//!
+//! - A `serde_json` number that fits in `u64` or `i64` becomes `Number::U64`
+//!   or `Number::I64`. Any other number becomes the nearest `Number::F64`. With
+//!   `serde_json`'s `arbitrary_precision` feature this drops digits that `f64`
+//!   cannot hold, and a number too large for `f64` becomes `Value::Null`.
+//! - A `Number::F64` that is NaN or infinite has no `serde_json`
+//!   representation and becomes `serde_json::Value::Null`, the same as when it
+//!   is serialized.
+//!
+//! Neither conversion recurses, so arbitrarily deep documents are fine.
+
+// Spelling out both Value types reads better than Self for one of them.
+#![allow(clippy::use_self)]
+
+use crate::json::{Array, Number, Object, Value};
+use alloc::string::String;
+use alloc::vec::{self, Vec};
+use core::mem;
+use serde_json::map::{self, Map};
+
+impl From<serde_json::Value> for Value {
+    fn from(value: serde_json::Value) -> Self {
+        enum Layer {
+            Array(Array, vec::IntoIter<serde_json::Value>),
+            Object(Object, map::IntoIter, String),
+        }
+
+        let mut stack = Vec::new();
+        let mut next = value;
+
+        loop {
+            let depth = stack.len();
+            let mut done = match next {
+                serde_json::Value::Null => Value::Null,
+                serde_json::Value::Bool(b) => Value::Bool(b),
+                serde_json::Value::Number(n) => match (n.as_u64(), n.as_i64(), n.as_f64()) {
+                    (Some(n), _, _) => Value::Number(Number::U64(n)),
+                    (None, Some(n), _) => Value::Number(Number::I64(n)),
+                    (None, None, Some(n)) => Value::Number(Number::F64(n)),
+                    (None, None, None) => Value::Null,
+                },
+                serde_json::Value::String(s) => Value::String(s),
+                serde_json::Value::Array(vec) => {
+                    stack.push(Layer::Array(Array::new(), vec.into_iter()));
+                    Value::Null
+                }
+                serde_json::Value::Object(map) => {
+                    stack.push(Layer::Object(Object::new(), map.into_iter(), String::new()));
+                    Value::Null
+                }
+            };
+            // Nothing to add to a container that was just started.
+            let mut have_child = stack.len() == depth;
+
+            loop {
+                match stack.last_mut() {
+                    None => return done,
+                    Some(Layer::Array(array, rest)) => {
+                        if have_child {
+                            array.push(done);
+                        }
+                        if let Some(element) = rest.next() {
+                            next = element;
+                            break;
+                        }
+                    }
+                    Some(Layer::Object(object, rest, key)) => {
+                        if have_child {
+                            object.insert(mem::take(key), done);
+                        }
+                        if let Some((k, v)) = rest.next() {
+                            *key = k;
+                            next = v;
+                            break;
+                        }
+                    }
+                }
+                done = match stack.pop() {
+                    Some(Layer::Array(array, _)) => Value::Array(array),
+                    Some(Layer::Object(object, _, _)) => Value::Object(object),
+                    None => unreachable!(),
+                };
+                have_child = true;
+            }
+        }
+    }
+}
+
+impl From<Value> for serde_json::Value {
+    fn from(value: Value) -> Self {
+        enum Layer {
+            Array(Vec<serde_json::Value>, <Array as IntoIterator>::IntoIter),
+            Object(
+                Map<String, serde_json::Value>,
+                <Object as IntoIterator>::IntoIter,
+                String,
+            ),
+        }
+
+        let mut stack = Vec::new();
+        let mut next = value;
+
+        loop {
+            let depth = stack.len();
+            let mut done = match next {
+                Value::Null => serde_json::Value::Null,
+                Value::Bool(b) => serde_json::Value::Bool(b),
+                Value::Number(Number::U64(n)) => serde_json::Value::from(n),
+                Value::Number(Number::I64(n)) => serde_json::Value::from(n),
+                Value::Number(Number::F64(n)) => serde_json::Number::from_f64(n)
+                    .map_or(serde_json::Value::Null, serde_json::Value::Number),
+                Value::String(s) => serde_json::Value::String(s),
+                Value::Array(array) => {
+                    let vec = Vec::with_capacity(array.len());
+                    stack.push(Layer::Array(vec, array.into_iter()));
+                    serde_json::Value::Null
+                }
+                Value::Object(object) => {
+                    stack.push(Layer::Object(Map::new(), object.into_iter(), String::new()));
+                    serde_json::Value::Null
+                }
+            };
+            // Nothing to add to a container that was just started.
+            let mut have_child = stack.len() == depth;
+
+            loop {
+                match stack.last_mut() {
+                    None => return done,
+                    Some(Layer::Array(vec, rest)) => {
+                        if have_child {
+                            vec.push(done);
+                        }
+                        if let Some(element) = rest.next() {
+                            next = element;
+                            break;
+                        }
+                    }
+                    Some(Layer::Object(map, rest, key)) => {
+                        if have_child {
+                            map.insert(mem::take(key), done);
+                        }
+                        if let Some((k, v)) = rest.next() {
+                            *key = k;
+                            next = v;
+                            break;
+                        }
+                    }
+                }
+                done = match stack.pop() {
+                    Some(Layer::Array(vec, _)) => serde_json::Value::Array(vec),
+                    Some(Layer::Object(map, _, _)) => serde_json::Value::Object(map),
+                    None => unreachable!(),
+                };
+                have_child = true;
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::ser::{Fragment, Serialize, StrChunks};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::{RefCell, RefMut};
+use core::str;
+use std::io::{ErrorKind, Read};
+
+/// A string serialized by reading its UTF-8 contents from a reader.
+///
+/// The contents are read and escaped a piece at a time straight into the
+/// serializer's output, so the whole string never needs to be held in memory
+/// apart from the output itself. This is meant for large text blobs, such as
+/// a file embedded as a string field.
+///
+/// The reader is consumed by serializing; serializing the same `StreamString`
+/// again produces whatever the reader returns next, typically an empty string.
+///
+/// Serialization cannot fail, so invalid UTF-8 in the contents is replaced by
+/// U+FFFD as in [`String::from_utf8_lossy`], and a read error ends the string
+/// early with whatever was read before it.
+///
+/// ```rust
+/// use miniserde::json::{self, StreamString};
+///
+/// let file: &[u8] = b"line 1\nline 2\n";
+/// let j = json::to_string(&StreamString::new(file));
+/// assert_eq!(j, r#""line 1\nline 2\n""#);
+/// ```
+pub struct StreamString<R> {
+    reader: RefCell<R>,
+}
+
+impl<R> StreamString<R>
+where
+    R: Read,
+{
+    pub const fn new(reader: R) -> Self {
+        Self {
+            reader: RefCell::new(reader),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+}
+
+impl<R> Serialize for StreamString<R>
+where
+    R: Read,
+{
+    fn begin(&self) -> Fragment {
+        let mut chunks = self.chunks();
+        let mut string = String::new();
+        while let Some(chunk) = chunks.next() {
+            string.push_str(chunk);
+        }
+        Fragment::Str(Cow::Owned(string))
+    }
+
+    fn __str_chunks(&self) -> Option<Box<dyn StrChunks + '_>> {
+        Some(Box::new(self.chunks()))
+    }
+}
+
+impl<R> StreamString<R> {
+    fn chunks(&self) -> ReaderChunks<R> {
+        ReaderChunks {
+            reader: self.reader.borrow_mut(),
+            buf: vec![0; 8192],
+            valid: 0,
+            filled: 0,
+        }
+    }
+}
+
+struct ReaderChunks<'a, R> {
+    reader: RefMut<'a, R>,
+    buf: Vec<u8>,
+    /// End of the bytes returned or replaced by the previous call to `next`.
+    valid: usize,
+    /// End of the bytes read so far. Anything between `valid` and `filled` has
+    /// not been returned yet, such as the start of a character split across
+    /// reads.
+    filled: usize,
+}
+
+impl<'a, R> StrChunks for ReaderChunks<'a, R>
+where
+    R: Read,
+{
+    fn next(&mut self) -> Option<&str> {
+        self.buf.copy_within(self.valid..self.filled, 0);
+        self.filled -= self.valid;
+        self.valid = 0;
+
+        loop {
+            if self.filled > 0 {
+                match str::from_utf8(&self.buf[..self.filled]) {
+                    Ok(_) => self.valid = self.filled,
+                    Err(error) if error.valid_up_to() > 0 => self.valid = error.valid_up_to(),
+                    Err(error) => {
+                        if let Some(len) = error.error_len() {
+                            self.valid = len;
+                            return Some("\u{FFFD}");
+                        }
+                    }
+                }
+                if self.valid > 0 {
+                    let chunk = &self.buf[..self.valid];
+                    return Some(unsafe { str::from_utf8_unchecked(chunk) });
+                }
+            }
+
+            let n = match self.reader.read(&mut self.buf[self.filled..]) {
+                Ok(n) => n,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(_) => 0,
+            };
+            if n == 0 {
+                // A character cut off by the end of input is invalid UTF-8.
+                if self.filled == 0 {
+                    return None;
+                }
+                self.valid = self.filled;
+                return Some("\u{FFFD}");
+            }
+            self.filled += n;
+        }
+    }
+}
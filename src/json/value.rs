@@ -1,19 +1,23 @@
 use crate::de::{Deserialize, Map, Seq, Visitor};
-use crate::error::Result;
-use crate::json::{Array, Number, Object};
+use crate::error::{Error, Result};
+use crate::json::{from_str, to_string, to_string_pretty, Array, Number, Object};
 use crate::ser::{Fragment, Serialize};
 use alloc::borrow::{Cow, ToOwned};
 use alloc::boxed::Box;
-use alloc::string::String;
-use core::fmt::{self, Debug};
+use alloc::collections::{btree_map, BTreeMap};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display};
 use core::mem;
 use core::ops::Index;
+use core::slice;
 use core::str;
 
 /// Any valid JSON value.
 ///
-/// This type has a non-recursive drop implementation so it is safe to build
-/// arbitrarily deeply nested instances.
+/// This type has non-recursive drop and clone implementations so it is safe to
+/// build arbitrarily deeply nested instances.
 ///
 /// ```rust
 /// use miniserde::json::{Array, Value};
@@ -27,7 +31,6 @@ use core::str;
 /// }
 /// // no stack overflow when `value` goes out of scope
 /// ```
-#[derive(Clone)]
 pub enum Value {
     Null,
     Bool(bool),
@@ -38,60 +41,387 @@ pub enum Value {
 }
 
 impl Value {
-    pub fn as_bool(&self) -> Option<bool> {
+    /// `Some(())` if this is `Null`, for checking for an explicit null in the
+    /// same way as the other `as_*` methods.
+    ///
+    /// ```rust
+    /// use miniserde::json::{self, Value};
+    ///
+    /// let value: Value = json::from_str(r#"{"a":null,"b":0}"#).unwrap();
+    /// assert_eq!(value.as_object().and_then(|o| o.get("a")?.as_null()), Some(()));
+    /// assert_eq!(value["b"].as_null(), None);
+    /// ```
+    pub const fn as_null(&self) -> Option<()> {
         match self {
-            Value::Bool(b) => Some(*b),
+            Self::Null => Some(()),
+            _ => None,
+        }
+    }
+
+    pub const fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(b) => Some(*b),
             _ => None,
         }
     }
 
     pub fn as_str(&self) -> Option<&str> {
         match self {
-            Value::String(s) => Some(s),
+            Self::String(s) => Some(s),
             _ => None,
         }
     }
 
-    pub fn as_u64(&self) -> Option<u64> {
+    #[allow(clippy::cast_sign_loss)] // only nonnegative values are cast
+    pub const fn as_u64(&self) -> Option<u64> {
         match self {
-            Value::Number(Number::U64(n)) => Some(*n),
-            Value::Number(Number::I64(n)) if *n >= 0 => Some(*n as u64),
+            Self::Number(Number::U64(n)) => Some(*n),
+            Self::Number(Number::I64(n)) if *n >= 0 => Some(*n as u64),
             _ => None,
         }
     }
 
-    pub fn as_i64(&self) -> Option<i64> {
+    pub const fn as_i64(&self) -> Option<i64> {
         match self {
-            Value::Number(Number::I64(n)) => Some(*n),
-            Value::Number(Number::U64(n)) if *n <= i64::MAX as u64 => Some(*n as i64),
+            Self::Number(Number::I64(n)) => Some(*n),
+            Self::Number(Number::U64(n)) if *n <= i64::MAX as u64 => Some(*n as i64),
             _ => None,
         }
     }
 
-    pub fn as_f64(&self) -> Option<f64> {
+    pub const fn as_f64(&self) -> Option<f64> {
         match self {
-            Value::Number(Number::F64(n)) => Some(*n),
-            Value::Number(Number::U64(n)) => Some(*n as f64),
-            Value::Number(Number::I64(n)) => Some(*n as f64),
+            Self::Number(Number::F64(n)) => Some(*n),
+            Self::Number(Number::U64(n)) => Some(*n as f64),
+            Self::Number(Number::I64(n)) => Some(*n as f64),
             _ => None,
         }
     }
 
-    pub fn as_array(&self) -> Option<&Array> {
+    pub const fn as_array(&self) -> Option<&Array> {
         match self {
-            Value::Array(array) => Some(array),
+            Self::Array(array) => Some(array),
             _ => None,
         }
     }
 
-    pub fn as_object(&self) -> Option<&Object> {
+    pub const fn as_object(&self) -> Option<&Object> {
         match self {
-            Value::Object(object) => Some(object),
+            Self::Object(object) => Some(object),
             _ => None,
         }
     }
 }
 
+impl Value {
+    /// Flatten into a map from dotted paths to stringified leaf values.
+    ///
+    /// Object keys and array indices on the way to each leaf are joined with
+    /// `.`, as in `a.b.0.c`. String leaves are stored as is, without quotes.
+    /// Other leaves are stored as their JSON text: `null`, `true`, `1.5`, or
+    /// `[]` and `{}` for empty arrays and objects. A value that is not an array
+    /// or object is stored under the empty path.
+    ///
+    /// ```rust
+    /// use miniserde::json::{self, Value};
+    ///
+    /// let value: Value = json::from_str(r#"{"a":{"b":[{"c":"x"},true]}}"#).unwrap();
+    /// let map = value.flatten_to_map();
+    /// assert_eq!(map["a.b.0.c"], "x");
+    /// assert_eq!(map["a.b.1"], "true");
+    /// ```
+    pub fn flatten_to_map(&self) -> BTreeMap<String, String> {
+        let mut map = BTreeMap::new();
+        let mut stack = vec![(String::new(), self)];
+
+        while let Some((path, value)) = stack.pop() {
+            let leaf = match value {
+                Self::Null => "null".to_owned(),
+                Self::Bool(b) => b.to_string(),
+                Self::Number(number) => number.to_string(),
+                Self::String(string) => string.clone(),
+                Self::Array(array) if array.is_empty() => "[]".to_owned(),
+                Self::Object(object) if object.is_empty() => "{}".to_owned(),
+                Self::Array(array) => {
+                    for (index, child) in array.iter().enumerate() {
+                        stack.push((join_path(&path, &index.to_string()), child));
+                    }
+                    continue;
+                }
+                Self::Object(object) => {
+                    for (key, child) in object {
+                        stack.push((join_path(&path, key), child));
+                    }
+                    continue;
+                }
+            };
+            map.insert(path, leaf);
+        }
+
+        map
+    }
+
+    /// Rebuild a value from the output of [`flatten_to_map`][Self::flatten_to_map].
+    ///
+    /// Path segments consisting of decimal digits index into arrays, other
+    /// segments into objects. A leaf that is the JSON text of a null, boolean,
+    /// number, or empty array or object becomes that value; any other leaf
+    /// becomes a string. This means a string such as `"true"` or `"1"` does not
+    /// survive the round trip as a string, and neither does an object key that
+    /// contains `.` or consists of digits, since it is read back as several
+    /// segments or as an array index. Where paths disagree about the type of a
+    /// container, the one that sorts last wins.
+    ///
+    /// An array index at least as large as the number of entries in the map is
+    /// an error, since no flattened array has that many elements. Missing
+    /// elements below it are filled with `null`.
+    pub fn unflatten(map: BTreeMap<String, String>) -> Result<Self> {
+        let max_len = map.len();
+        let mut root = Self::Null;
+
+        for (path, leaf) in map {
+            let mut node = &mut root;
+            if !path.is_empty() {
+                for segment in path.split('.') {
+                    node = node.child_mut(segment, max_len)?;
+                }
+            }
+            *node = match from_str(&leaf) {
+                Ok(value @ (Self::Null | Self::Bool(_) | Self::Number(_))) => value,
+                Ok(Self::Array(array)) if array.is_empty() => Self::Array(array),
+                Ok(Self::Object(object)) if object.is_empty() => Self::Object(object),
+                _ => Self::String(leaf),
+            };
+        }
+
+        Ok(root)
+    }
+
+    /// Merge `patch` into this value, recursively through objects.
+    ///
+    /// Where both sides are objects, keys only in `patch` are added and keys in
+    /// both are merged. Where both sides are arrays, the elements of `patch`
+    /// are appended if `concat_arrays` is true; otherwise the array is
+    /// replaced. Anything else, including a conflict between types such as an
+    /// object and an array, takes the value from `patch`. A `null` in `patch`
+    /// is a value like any other and does not remove the key.
+    ///
+    /// ```rust
+    /// use miniserde::json::{self, Value};
+    ///
+    /// let mut config: Value = json::from_str(r#"{"log":{"level":"info"},"paths":["/etc"]}"#).unwrap();
+    /// let layer: Value = json::from_str(r#"{"log":{"file":"out.log"},"paths":["/home"]}"#).unwrap();
+    /// config.deep_merge(layer, true);
+    /// assert_eq!(
+    ///     json::to_string(&config),
+    ///     r#"{"log":{"file":"out.log","level":"info"},"paths":["/etc","/home"]}"#,
+    /// );
+    /// ```
+    pub fn deep_merge(&mut self, patch: Self, concat_arrays: bool) {
+        let mut stack = vec![(self, patch)];
+
+        while let Some((target, patch)) = stack.pop() {
+            match (target, patch) {
+                (Self::Object(target), Self::Object(patch)) => {
+                    let mut shared = Vec::new();
+                    for (key, value) in patch {
+                        if target.contains_key(&key) {
+                            shared.push((key, value));
+                        } else {
+                            target.insert(key, value);
+                        }
+                    }
+                    // Both are sorted by key, so one pass pairs them up.
+                    let mut shared = shared.into_iter().peekable();
+                    for (key, child) in target.iter_mut() {
+                        if let Some((_, value)) = shared.next_if(|(k, _)| k == key) {
+                            stack.push((child, value));
+                        }
+                    }
+                }
+                (Self::Array(target), Self::Array(patch)) if concat_arrays => {
+                    target.extend(patch);
+                }
+                (target, patch) => *target = patch,
+            }
+        }
+    }
+
+    /// Compare like `==`, except that numbers are compared by value rather
+    /// than by representation.
+    ///
+    /// Strict equality treats `Number::U64(1)`, `Number::I64(1)` and
+    /// `Number::F64(1.0)` as three different values. This treats them as the
+    /// same number, at every level of nesting. An integer equals a float only
+    /// if the float is exactly that integer, so `9007199254740993` is not
+    /// loosely equal to `9007199254740992.0` even though they convert to the
+    /// same `f64`.
+    ///
+    /// ```rust
+    /// use miniserde::json::{self, Value};
+    ///
+    /// let a: Value = json::from_str(r#"{"n":[1]}"#).unwrap();
+    /// let b: Value = json::from_str(r#"{"n":[1.0]}"#).unwrap();
+    /// assert!(a != b);
+    /// assert!(a.loose_eq(&b));
+    /// ```
+    pub fn loose_eq(&self, other: &Self) -> bool {
+        equal(self, other, numbers_loosely_equal)
+    }
+
+    fn child_mut(&mut self, segment: &str, max_len: usize) -> Result<&mut Self> {
+        let is_index = !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit());
+        if is_index {
+            let index = match segment.parse::<usize>() {
+                Ok(index) if index < max_len => index,
+                _ => return Err(Error),
+            };
+            if !matches!(self, Self::Array(_)) {
+                *self = Self::Array(Array::new());
+            }
+            let Self::Array(array) = self else {
+                unreachable!()
+            };
+            if array.len() <= index {
+                array.resize(index + 1, Self::Null);
+            }
+            Ok(&mut array[index])
+        } else {
+            if !matches!(self, Self::Object(_)) {
+                *self = Self::Object(Object::new());
+            }
+            let Self::Object(object) = self else {
+                unreachable!()
+            };
+            Ok(object.entry(segment.to_owned()).or_insert(Self::Null))
+        }
+    }
+}
+
+fn equal(a: &Value, b: &Value, numbers_equal: fn(&Number, &Number) -> bool) -> bool {
+    let mut stack = vec![(a, b)];
+
+    while let Some(pair) = stack.pop() {
+        match pair {
+            (Value::Null, Value::Null) => {}
+            (Value::Bool(a), Value::Bool(b)) if a == b => {}
+            (Value::Number(a), Value::Number(b)) if numbers_equal(a, b) => {}
+            (Value::String(a), Value::String(b)) if a == b => {}
+            (Value::Array(a), Value::Array(b)) if a.len() == b.len() => {
+                stack.extend(a.iter().zip(b.iter()));
+            }
+            (Value::Object(a), Value::Object(b)) if a.len() == b.len() => {
+                // Both are sorted by key, so equal objects line up entry by entry.
+                for ((ka, va), (kb, vb)) in a.iter().zip(b.iter()) {
+                    if ka != kb {
+                        return false;
+                    }
+                    stack.push((va, vb));
+                }
+            }
+            _ => return false,
+        }
+    }
+
+    true
+}
+
+#[allow(clippy::float_cmp)] // exact comparison is the point
+fn numbers_loosely_equal(a: &Number, b: &Number) -> bool {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn integer(n: &Number) -> Option<i128> {
+        match *n {
+            Number::U64(n) => Some(i128::from(n)),
+            Number::I64(n) => Some(i128::from(n)),
+            // Truncates, so this round trips only for floats with no fraction.
+            Number::F64(n) => Some(n as i128).filter(|&i| i as f64 == n),
+        }
+    }
+
+    match (integer(a), integer(b)) {
+        (Some(a), Some(b)) => a == b,
+        // An integer is never equal to a float with a fraction.
+        (Some(_), None) | (None, Some(_)) => false,
+        (None, None) => matches!((a, b), (Number::F64(a), Number::F64(b)) if a == b),
+    }
+}
+
+fn join_path(prefix: &str, segment: &str) -> String {
+    if prefix.is_empty() {
+        segment.to_owned()
+    } else {
+        let mut path = String::with_capacity(prefix.len() + 1 + segment.len());
+        path.push_str(prefix);
+        path.push('.');
+        path.push_str(segment);
+        path
+    }
+}
+
+/// Like drop, this does not recurse, so it is safe on deeply nested values.
+impl Clone for Value {
+    fn clone(&self) -> Self {
+        enum Layer<'a> {
+            Array(Array, slice::Iter<'a, Value>),
+            Object(Object, btree_map::Iter<'a, String, Value>, Option<&'a String>),
+        }
+
+        let mut stack = Vec::new();
+        let mut next = self;
+
+        loop {
+            let depth = stack.len();
+            let mut done = match next {
+                Self::Null => Self::Null,
+                Self::Bool(b) => Self::Bool(*b),
+                Self::Number(n) => Self::Number(n.clone()),
+                Self::String(s) => Self::String(s.clone()),
+                Self::Array(array) => {
+                    stack.push(Layer::Array(Array::with_capacity(array.len()), array.iter()));
+                    Self::Null
+                }
+                Self::Object(object) => {
+                    stack.push(Layer::Object(Object::new(), object.iter(), None));
+                    Self::Null
+                }
+            };
+            // Nothing to add to a container that was just started.
+            let mut have_child = stack.len() == depth;
+
+            loop {
+                match stack.last_mut() {
+                    None => return done,
+                    Some(Layer::Array(array, rest)) => {
+                        if have_child {
+                            array.push(done);
+                        }
+                        if let Some(element) = rest.next() {
+                            next = element;
+                            break;
+                        }
+                    }
+                    Some(Layer::Object(object, rest, key)) => {
+                        if let (true, Some(key)) = (have_child, key.take()) {
+                            object.insert(key.clone(), done);
+                        }
+                        if let Some((k, v)) = rest.next() {
+                            *key = Some(k);
+                            next = v;
+                            break;
+                        }
+                    }
+                }
+                done = match stack.pop() {
+                    Some(Layer::Array(array, _)) => Self::Array(array),
+                    Some(Layer::Object(object, _, _)) => Self::Object(object),
+                    None => unreachable!(),
+                };
+                have_child = true;
+            }
+        }
+    }
+}
+
 impl Default for Value {
     /// The default value is null.
     fn default() -> Self {
@@ -101,23 +431,40 @@ impl Default for Value {
 
 static NULL: Value = Value::Null;
 
+/// Exact equality: numbers are equal only if they are the same variant with
+/// the same value, so `1` and `1.0` differ. See [`Value::loose_eq`] to compare
+/// numbers by value.
+///
+/// Like drop, this does not recurse, so it is safe on deeply nested values.
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        equal(self, other, Number::eq)
+    }
+}
+
+impl From<Number> for Value {
+    fn from(n: Number) -> Self {
+        Self::Number(n)
+    }
+}
+
 impl Index<usize> for Value {
-    type Output = Value;
+    type Output = Self;
 
-    fn index(&self, index: usize) -> &Value {
+    fn index(&self, index: usize) -> &Self {
         match self {
-            Value::Array(arr) => arr.get(index).unwrap_or(&NULL),
+            Self::Array(arr) => arr.get(index).unwrap_or(&NULL),
             _ => &NULL,
         }
     }
 }
 
 impl Index<&str> for Value {
-    type Output = Value;
+    type Output = Self;
 
-    fn index(&self, index: &str) -> &Value {
+    fn index(&self, index: &str) -> &Self {
         match self {
-            Value::Object(obj) => obj.get(index).unwrap_or(&NULL),
+            Self::Object(obj) => obj.get(index).unwrap_or(&NULL),
             _ => &NULL,
         }
     }
@@ -136,6 +483,25 @@ impl Debug for Value {
     }
 }
 
+/// Formats the value as compact JSON, or as pretty-printed JSON with `{:#}`.
+///
+/// ```rust
+/// use miniserde::json::{self, Value};
+///
+/// let value: Value = json::from_str(r#"{"a":[1,2]}"#).unwrap();
+/// assert_eq!(format!("{}", value), r#"{"a":[1,2]}"#);
+/// assert_eq!(format!("{:#}", value), "{\n  \"a\": [\n    1,\n    2\n  ]\n}");
+/// ```
+impl Display for Value {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(&if formatter.alternate() {
+            to_string_pretty(self)
+        } else {
+            to_string(self)
+        })
+    }
+}
+
 impl Serialize for Value {
     fn begin(&self) -> Fragment {
         match self {
@@ -147,6 +513,9 @@ impl Serialize for Value {
             Self::Object(object) => Serialize::begin(object),
         }
     }
+    fn __value(&self) -> Option<&Self> {
+        Some(self)
+    }
 }
 
 impl Deserialize for Value {
@@ -227,6 +596,10 @@ impl Deserialize for Value {
                 *self.out = Some(Value::Array(mem::replace(&mut self.array, Array::new())));
                 Ok(())
             }
+
+            fn size_hint(&mut self, len: usize) {
+                self.array.reserve(len);
+            }
         }
 
         struct ObjectBuilder<'a> {
@@ -247,6 +620,10 @@ impl Deserialize for Value {
         impl<'a> Map for ObjectBuilder<'a> {
             fn key(&mut self, k: &str) -> Result<&mut dyn Visitor> {
                 self.shift();
+                // Object keys are `String`, so each key costs one allocation
+                // of exactly its length. Short keys are not inlined and equal
+                // keys are not shared because either would need a different
+                // key type in Object's public map.
                 self.key = Some(k.to_owned());
                 Ok(Deserialize::begin(&mut self.value))
             }
@@ -96,13 +96,16 @@
 //!
 //! ## <font color="#C0C0C0">Different:</font> No deserialization error messages
 //!
-//! When deserialization fails, the error type is a unit struct containing no
-//! information. This is a legit strategy and not just laziness. If your use
-//! case does not require error messages, good, you save on compiling and having
-//! your instruction cache polluted by error handling code. If you do need error
-//! messages, then upon error you can pass the same input to serde\_json to
-//! receive a line, column, and helpful description of the failure. This keeps
-//! error handling logic out of caches along the performance-critical codepath.
+//! When deserialization fails, the error type contains no information other
+//! than the byte offset at which the JSON parser gave up, when known, and for a
+//! few specific failures an [`ErrorKind`] such as an unpaired surrogate or a
+//! malformed number. This is a legit strategy and not just laziness. If your
+//! use case does not require error messages, good, you save on compiling and
+//! having your instruction cache polluted by error handling code. If you do
+//! need error messages, then upon error you can pass the same input to
+//! serde\_json to receive a line, column, and helpful description of the
+//! failure. This keeps error handling logic out of caches along the
+//! performance-critical codepath.
 //!
 //! ## <font color="#C0C0C0">Different:</font> Infallible serialization
 //!
@@ -117,11 +120,19 @@
 //! formats, but it is not a goal to enable that through what this library
 //! exposes.
 //!
-//! ## <font color="#C0C0C0">Different:</font> Structs and unit variants only
+//! ## <font color="#C0C0C0">Different:</font> Structs and simple variants only
 //!
 //! The miniserde derive macros will refuse anything other than a braced struct
-//! with named fields or an enum with C-style variants. Tuple structs are not
-//! supported, and enums with data in their variants are not supported.
+//! with named fields, a tuple struct (represented as an array), a unit struct
+//! (represented as `null`), or an enum. A unit variant is represented as its
+//! name, `"A"`, and a variant with a single unnamed field as an object with
+//! the name as its only key, `{"B":1}`. Variants with named fields are
+//! supported only in an enum with `#[serde(tag = "...")]`, and tuple variants
+//! with more than one field are not supported.
+//!
+//! A tuple struct with a single field is an array too, so `struct Id(u32)` is
+//! represented as `[1]` where Serde would write just `1`. Serde's
+//! representation takes handwritten impls.
 //!
 //! ## <font color="#C0C0C0">Different:</font> No customization
 //!
@@ -130,9 +141,68 @@
 //! configurability you can handwrite arbitrarily complicated implementations of
 //! its traits.
 //!
-//! Miniserde provides just one attribute which is `rename`, and severely
-//! restricts the kinds of on-the-fly manipulation that are possible in custom
-//! impls. If you need any of this, use Serde -- it's a great library.
+//! Miniserde provides only a handful of attributes, such as `rename` and
+//! `default`, and severely restricts the kinds of on-the-fly manipulation
+//! that are possible in custom impls. If you need any of this, use Serde --
+//! it's a great library.
+//!
+//! One attribute with no Serde equivalent is `#[serde(name_or_code)]` on a
+//! C-style enum, which makes it deserialize from either the name of a variant
+//! or its integer discriminant, explicit or implicit. It is still serialized
+//! by name.
+//!
+//! ```rust
+//! use miniserde::{json, Deserialize};
+//!
+//! #[derive(Deserialize, PartialEq, Debug)]
+//! #[serde(name_or_code)]
+//! enum Level {
+//!     Low,
+//!     Mid,
+//!     High,
+//! }
+//!
+//! assert_eq!(json::from_str::<Level>("2").unwrap(), Level::High);
+//! assert_eq!(json::from_str::<Level>(r#""Mid""#).unwrap(), Level::Mid);
+//! ```
+//!
+//! Another is `#[serde(map_key)]` on a C-style enum, which lets it be the key
+//! type of a `HashMap` or `BTreeMap` by implementing [`ser::MapKey`] and
+//! `FromStr` in terms of the variant names.
+//!
+//! A field marked `#[serde(borrow)]` borrows from the input instead of
+//! allocating. Such a field may be a `&str`, a `Cow<str>`, an `Option` of
+//! either, or another struct with borrowed fields, and the struct is then
+//! deserialized with [`json::from_str_borrowed`] rather than `from_str`. A
+//! `&str` fails on a string containing escape sequences, which cannot be
+//! borrowed as written; a `Cow<str>` copies just those strings.
+//!
+//! ```rust
+//! use miniserde::{json, Deserialize};
+//!
+//! #[derive(Deserialize)]
+//! struct User<'a> {
+//!     #[serde(borrow)]
+//!     name: &'a str,
+//! }
+//!
+//! let j = r#"{"name":"Ann"}"#;
+//! let user: User = json::from_str_borrowed(j).unwrap();
+//! assert_eq!(user.name, "Ann");
+//! ```
+//!
+//! # Cargo features
+//!
+//! - `std` (enabled by default) — impls for `std` types such as `HashMap`,
+//!   and runtime detection of SIMD instructions for scanning strings.
+//! - `bitflags` — the `with::bitflags` module.
+//! - `serde_json` — conversions between [`json::Value`] and
+//!   `serde_json::Value`.
+//! - `time` — `Serialize` and `Deserialize` for `time::OffsetDateTime` as an
+//!   RFC 3339 string.
+//! - `portable_simd` (nightly only) — scan strings with `core::simd` instead
+//!   of per-architecture intrinsics, which vectorizes on every target the
+//!   compiler supports.
 
 #![doc(html_root_url = "https://docs.rs/miniserde/0.1.43")]
 #![allow(
@@ -161,6 +231,7 @@
     clippy::vec_init_then_push,
 )]
 #![allow(unknown_lints, mismatched_lifetime_syntaxes)]
+#![cfg_attr(feature = "portable_simd", feature(portable_simd))]
 #![no_std]
 
 extern crate alloc;
@@ -192,14 +263,18 @@ mod place {
 mod error;
 mod ignore;
 mod ptr;
+mod tagged;
+#[cfg(feature = "time")]
+mod time;
 
 pub mod de;
 pub mod json;
 pub mod ser;
+pub mod with;
 
 #[doc(inline)]
 pub use crate::de::Deserialize;
-pub use crate::error::{Error, Result};
+pub use crate::error::{Error, ErrorKind, Result};
 #[doc(inline)]
 pub use crate::ser::Serialize;
 
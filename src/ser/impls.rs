@@ -1,16 +1,29 @@
+use crate::json::Value;
 use crate::private;
-use crate::ser::{Fragment, Map, Seq, Serialize};
+use crate::ser::{Fragment, Map, Numbers, Seq, Serialize, StrChunks};
 use alloc::borrow::{Cow, ToOwned};
 use alloc::boxed::Box;
-use alloc::collections::{btree_map, BTreeMap};
+use alloc::collections::{btree_map, BTreeMap, LinkedList, VecDeque};
+use alloc::rc::Rc;
 use alloc::string::{String, ToString};
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
 use alloc::vec::Vec;
+use core::marker::PhantomData;
+#[cfg(not(no_core_net))]
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+#[cfg(not(no_saturating))]
+use core::num::Saturating;
+use core::num::Wrapping;
+use core::ops::{Range, RangeInclusive};
 use core::slice;
 use core::str;
 #[cfg(feature = "std")]
 use std::collections::{hash_map, HashMap};
 #[cfg(feature = "std")]
 use std::hash::{BuildHasher, Hash};
+#[cfg(all(no_core_net, feature = "std"))]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
 impl Serialize for () {
     fn begin(&self) -> Fragment {
@@ -37,46 +50,96 @@ impl Serialize for String {
 }
 
 macro_rules! unsigned {
-    ($ty:ident) => {
+    ($ty:ident, $numbers:ident) => {
         impl Serialize for $ty {
             fn begin(&self) -> Fragment {
                 Fragment::U64(*self as u64)
             }
+
+            fn __numbers(slice: &[Self]) -> Option<Numbers> {
+                Some(Numbers::$numbers(slice))
+            }
         }
     };
 }
-unsigned!(u8);
-unsigned!(u16);
-unsigned!(u32);
-unsigned!(u64);
-unsigned!(usize);
+unsigned!(u8, U8);
+unsigned!(u16, U16);
+unsigned!(u32, U32);
+unsigned!(u64, U64);
+unsigned!(usize, Usize);
 
 macro_rules! signed {
-    ($ty:ident) => {
+    ($ty:ident, $numbers:ident) => {
         impl Serialize for $ty {
             fn begin(&self) -> Fragment {
                 Fragment::I64(*self as i64)
             }
+
+            fn __numbers(slice: &[Self]) -> Option<Numbers> {
+                Some(Numbers::$numbers(slice))
+            }
         }
     };
 }
-signed!(i8);
-signed!(i16);
-signed!(i32);
-signed!(i64);
-signed!(isize);
+signed!(i8, I8);
+signed!(i16, I16);
+signed!(i32, I32);
+signed!(i64, I64);
+signed!(isize, Isize);
 
 macro_rules! float {
-    ($ty:ident) => {
+    ($ty:ident, $numbers:ident) => {
         impl Serialize for $ty {
             fn begin(&self) -> Fragment {
                 Fragment::F64(*self as f64)
             }
+
+            fn __numbers(slice: &[Self]) -> Option<Numbers> {
+                Some(Numbers::$numbers(slice))
+            }
         }
     };
 }
-float!(f32);
-float!(f64);
+float!(f32, F32);
+float!(f64, F64);
+
+impl<T> Serialize for Wrapping<T>
+where
+    T: Serialize,
+{
+    fn begin(&self) -> Fragment {
+        self.0.begin()
+    }
+}
+
+#[cfg(not(no_saturating))]
+#[allow(clippy::incompatible_msrv)]
+impl<T> Serialize for Saturating<T>
+where
+    T: Serialize,
+{
+    fn begin(&self) -> Fragment {
+        self.0.begin()
+    }
+}
+
+// IP addresses are written in their usual string form, such as "127.0.0.1"
+// or "::1".
+macro_rules! serialize_display {
+    ($($ty:ident)*) => {
+        $(
+            #[cfg(any(not(no_core_net), feature = "std"))]
+            #[allow(clippy::incompatible_msrv)]
+            impl Serialize for $ty {
+                fn begin(&self) -> Fragment {
+                    Fragment::Str(Cow::Owned(self.to_string()))
+                }
+            }
+        )*
+    };
+}
+
+serialize_display!(IpAddr Ipv4Addr Ipv6Addr);
 
 impl<T> Serialize for &T
 where
@@ -85,6 +148,31 @@ where
     fn begin(&self) -> Fragment {
         (**self).begin()
     }
+
+    fn __value(&self) -> Option<&Value> {
+        (**self).__value()
+    }
+
+    fn __str_chunks(&self) -> Option<Box<dyn StrChunks + '_>> {
+        (**self).__str_chunks()
+    }
+}
+
+impl<T> Serialize for &mut T
+where
+    T: ?Sized + Serialize,
+{
+    fn begin(&self) -> Fragment {
+        (**self).begin()
+    }
+
+    fn __value(&self) -> Option<&Value> {
+        (**self).__value()
+    }
+
+    fn __str_chunks(&self) -> Option<Box<dyn StrChunks + '_>> {
+        (**self).__str_chunks()
+    }
 }
 
 impl<T> Serialize for Box<T>
@@ -94,6 +182,49 @@ where
     fn begin(&self) -> Fragment {
         (**self).begin()
     }
+
+    fn __value(&self) -> Option<&Value> {
+        (**self).__value()
+    }
+
+    fn __str_chunks(&self) -> Option<Box<dyn StrChunks + '_>> {
+        (**self).__str_chunks()
+    }
+}
+
+impl<T> Serialize for Rc<T>
+where
+    T: ?Sized + Serialize,
+{
+    fn begin(&self) -> Fragment {
+        (**self).begin()
+    }
+
+    fn __value(&self) -> Option<&Value> {
+        (**self).__value()
+    }
+
+    fn __str_chunks(&self) -> Option<Box<dyn StrChunks + '_>> {
+        (**self).__str_chunks()
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T> Serialize for Arc<T>
+where
+    T: ?Sized + Serialize,
+{
+    fn begin(&self) -> Fragment {
+        (**self).begin()
+    }
+
+    fn __value(&self) -> Option<&Value> {
+        (**self).__value()
+    }
+
+    fn __str_chunks(&self) -> Option<Box<dyn StrChunks + '_>> {
+        (**self).__str_chunks()
+    }
 }
 
 impl<T> Serialize for Option<T>
@@ -112,6 +243,14 @@ where
     fn begin(&self) -> Fragment {
         (**self).begin()
     }
+
+    fn __value(&self) -> Option<&Value> {
+        (**self).__value()
+    }
+
+    fn __str_chunks(&self) -> Option<Box<dyn StrChunks + '_>> {
+        (**self).__str_chunks()
+    }
 }
 
 impl<A, B> Serialize for (A, B)
@@ -146,6 +285,56 @@ where
     }
 }
 
+/// Serialized as `{"start":...,"end":...}`.
+impl<T> Serialize for Range<T>
+where
+    T: Serialize,
+{
+    fn begin(&self) -> Fragment {
+        private::stream_range(&self.start, &self.end)
+    }
+}
+
+/// Serialized as `{"start":...,"end":...}`, the same as `Range` except that
+/// `end` is included in the range. An exhausted iterator is not
+/// distinguished from a fresh one.
+impl<T> Serialize for RangeInclusive<T>
+where
+    T: Serialize,
+{
+    fn begin(&self) -> Fragment {
+        private::stream_range(self.start(), self.end())
+    }
+}
+
+/// Serialized as an object with the single key `Ok` or `Err`.
+impl<T, E> Serialize for Result<T, E>
+where
+    T: Serialize,
+    E: Serialize,
+{
+    fn begin(&self) -> Fragment {
+        struct ResultStream<'a> {
+            entry: Option<(&'static str, &'a dyn Serialize)>,
+        }
+
+        impl<'a> Map for ResultStream<'a> {
+            fn next(&mut self) -> Option<(Cow<str>, &dyn Serialize)> {
+                let (key, value) = self.entry.take()?;
+                Some((Cow::Borrowed(key), value))
+            }
+        }
+
+        let entry: (&str, &dyn Serialize) = match self {
+            Ok(value) => ("Ok", value),
+            Err(error) => ("Err", error),
+        };
+        Fragment::Map(Box::new(ResultStream { entry: Some(entry) }))
+    }
+}
+
+/// Serialized as an array. Slices of primitive numbers, such as `&[u8]`, are
+/// written in a single pass without going through [`Seq`] per element.
 impl<T> Serialize for [T]
 where
     T: Serialize,
@@ -173,6 +362,71 @@ where
     }
 }
 
+impl<T> Serialize for VecDeque<T>
+where
+    T: Serialize,
+{
+    fn begin(&self) -> Fragment {
+        private::stream_iter(self.iter())
+    }
+}
+
+impl<T> Serialize for LinkedList<T>
+where
+    T: Serialize,
+{
+    fn begin(&self) -> Fragment {
+        private::stream_iter(self.iter())
+    }
+}
+
+/// A type that can be written as the key of a JSON object.
+///
+/// JSON object keys are always strings, so a `HashMap` or `BTreeMap` is only
+/// serializable if its keys can be turned into one. This is implemented for
+/// the string types, and for `bool`, `char` and the primitive integers by way
+/// of their `Display` impl. Other key types can implement it the same way.
+///
+/// Deserializing such a map instead parses each key with `FromStr`, failing
+/// if any key does not parse. So a key type that round-trips implements both
+/// `MapKey` and `FromStr`, typically with `Display` and `FromStr` agreeing.
+///
+/// An enum whose variants are all unit variants gets both from the derive
+/// macros if it is marked `#[serde(map_key)]`, writing each key as the
+/// variant's name just as the enum itself is serialized. A key that names no
+/// variant fails to parse, even if the enum has a default variant.
+///
+/// ```rust
+/// use miniserde::json;
+/// use miniserde::ser::MapKey;
+/// use std::borrow::Cow;
+/// use std::collections::BTreeMap;
+/// use std::str::FromStr;
+///
+/// #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+/// struct Version(u32, u32);
+///
+/// impl MapKey for Version {
+///     fn serialize_key(&self) -> Cow<str> {
+///         Cow::Owned(format!("{}.{}", self.0, self.1))
+///     }
+/// }
+///
+/// impl FromStr for Version {
+///     type Err = ();
+///
+///     fn from_str(s: &str) -> Result<Self, ()> {
+///         let (major, minor) = s.split_once('.').ok_or(())?;
+///         Ok(Version(major.parse().map_err(drop)?, minor.parse().map_err(drop)?))
+///     }
+/// }
+///
+/// let mut released = BTreeMap::new();
+/// released.insert(Version(1, 2), 2019);
+/// let j = json::to_string(&released);
+/// assert_eq!(j, r#"{"1.2":2019}"#);
+/// assert_eq!(json::from_str::<BTreeMap<Version, u32>>(&j).unwrap(), released);
+/// ```
 pub trait MapKey {
     fn serialize_key(&self) -> Cow<str>;
 }
@@ -272,8 +526,59 @@ impl private {
                 let element = self.0.next()?;
                 Some(element)
             }
+
+            fn __numbers(&self) -> Option<Numbers> {
+                T::__numbers(self.0.as_slice())
+            }
         }
 
         Fragment::Seq(Box::new(SliceStream(slice.iter())))
     }
+
+    pub fn stream_iter<'a, I, T>(iter: I) -> Fragment<'a>
+    where
+        I: Iterator<Item = &'a T> + 'a,
+        T: Serialize + 'a,
+    {
+        struct IterStream<'a, T: 'a, I>(I, PhantomData<&'a T>);
+
+        impl<'a, T, I> Seq for IterStream<'a, T, I>
+        where
+            I: Iterator<Item = &'a T>,
+            T: Serialize + 'a,
+        {
+            fn next(&mut self) -> Option<&dyn Serialize> {
+                let element = self.0.next()?;
+                Some(element)
+            }
+        }
+
+        Fragment::Seq(Box::new(IterStream(iter, PhantomData)))
+    }
+
+    pub fn stream_range<'a>(start: &'a dyn Serialize, end: &'a dyn Serialize) -> Fragment<'a> {
+        struct RangeStream<'a> {
+            start: &'a dyn Serialize,
+            end: &'a dyn Serialize,
+            state: usize,
+        }
+
+        impl<'a> Map for RangeStream<'a> {
+            fn next(&mut self) -> Option<(Cow<str>, &dyn Serialize)> {
+                let state = self.state;
+                self.state += 1;
+                match state {
+                    0 => Some((Cow::Borrowed("start"), self.start)),
+                    1 => Some((Cow::Borrowed("end"), self.end)),
+                    _ => None,
+                }
+            }
+        }
+
+        Fragment::Map(Box::new(RangeStream {
+            start,
+            end,
+            state: 0,
+        }))
+    }
 }
\ No newline at end of file
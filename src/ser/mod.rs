@@ -94,6 +94,9 @@
 
 mod impls;
 
+pub use self::impls::MapKey;
+
+use crate::json::Value;
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
 
@@ -116,6 +119,30 @@ pub enum Fragment<'a> {
 /// [Refer to the module documentation for examples.][crate::ser]
 pub trait Serialize {
     fn begin(&self) -> Fragment;
+
+    /// View a slice of this type as a run of numbers, if it is one.
+    #[doc(hidden)]
+    fn __numbers(slice: &[Self]) -> Option<Numbers>
+    where
+        Self: Sized,
+    {
+        let _ = slice;
+        None
+    }
+
+    /// This value itself, if it is already a `json::Value`, which `to_value`
+    /// can clone in one go instead of serializing.
+    #[doc(hidden)]
+    fn __value(&self) -> Option<&Value> {
+        None
+    }
+
+    /// This value as a string produced a piece at a time, which the JSON
+    /// serializer escapes straight into its output instead of calling `begin`.
+    #[doc(hidden)]
+    fn __str_chunks(&self) -> Option<Box<dyn StrChunks + '_>> {
+        None
+    }
 }
 
 /// Trait that can iterate elements of a sequence.
@@ -123,6 +150,31 @@ pub trait Serialize {
 /// [Refer to the module documentation for examples.][crate::ser]
 pub trait Seq {
     fn next(&mut self) -> Option<&dyn Serialize>;
+
+    /// All of the remaining elements, if they are a run of numbers that can be
+    /// formatted in one go instead of one `Fragment` at a time.
+    #[doc(hidden)]
+    fn __numbers(&self) -> Option<Numbers> {
+        None
+    }
+}
+
+/// A contiguous run of primitive numbers in a sequence.
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub enum Numbers<'a> {
+    U8(&'a [u8]),
+    U16(&'a [u16]),
+    U32(&'a [u32]),
+    U64(&'a [u64]),
+    Usize(&'a [usize]),
+    I8(&'a [i8]),
+    I16(&'a [i16]),
+    I32(&'a [i32]),
+    I64(&'a [i64]),
+    Isize(&'a [isize]),
+    F32(&'a [f32]),
+    F64(&'a [f64]),
 }
 
 /// Trait that can iterate key-value entries of a map or struct.
@@ -131,3 +183,10 @@ pub trait Seq {
 pub trait Map {
     fn next(&mut self) -> Option<(Cow<str>, &dyn Serialize)>;
 }
+
+// Not public API. The pieces of a string whose contents are not all available
+// at once, such as json::StreamString. The string is their concatenation.
+#[doc(hidden)]
+pub trait StrChunks {
+    fn next(&mut self) -> Option<&str>;
+}
@@ -0,0 +1,74 @@
+//! Support for deserializing internally tagged enums from derived impls.
+
+use crate::de::{Deserialize, Map, Visitor};
+use crate::error::{Error, Result};
+use crate::json::{Deserializer, Object, Value};
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::mem;
+
+/// Buffer a whole object, then hand the value of its `tag` key and the rest of
+/// its entries to `finish` to build the enum.
+///
+/// The tag may appear anywhere in the object, which is why nothing can be
+/// deserialized into the variant until the object is complete.
+///
+/// The caller's `Deserializer` settings were applied while the object was
+/// parsed into the buffer, except for `require_all_fields`, which is passed on
+/// to `finish` in the config to deserialize the variant with.
+pub fn begin_tagged<'a, T>(
+    out: &'a mut Option<T>,
+    tag: &'static str,
+    finish: fn(&str, Object, &Deserializer) -> Result<T>,
+) -> Box<dyn Map + 'a> {
+    Box::new(TaggedBuilder {
+        out,
+        tag,
+        finish,
+        object: Object::new(),
+        key: None,
+        value: None,
+        config: Deserializer::new(),
+    })
+}
+
+struct TaggedBuilder<'a, T> {
+    out: &'a mut Option<T>,
+    tag: &'static str,
+    finish: fn(&str, Object, &Deserializer) -> Result<T>,
+    object: Object,
+    key: Option<String>,
+    value: Option<Value>,
+    config: Deserializer,
+}
+
+impl<'a, T> TaggedBuilder<'a, T> {
+    fn shift(&mut self) {
+        if let (Some(k), Some(v)) = (self.key.take(), self.value.take()) {
+            self.object.insert(k, v);
+        }
+    }
+}
+
+impl<'a, T> Map for TaggedBuilder<'a, T> {
+    fn key(&mut self, k: &str) -> Result<&mut dyn Visitor> {
+        self.shift();
+        self.key = Some(k.to_owned());
+        Ok(Deserialize::begin(&mut self.value))
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.shift();
+        let Some(Value::String(tag)) = self.object.remove(self.tag) else {
+            return Err(Error);
+        };
+        let object = mem::replace(&mut self.object, Object::new());
+        *self.out = Some((self.finish)(&tag, object, &self.config)?);
+        Ok(())
+    }
+
+    fn __require_all_fields(&mut self) {
+        self.config = self.config.require_all_fields(true);
+    }
+}
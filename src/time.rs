@@ -0,0 +1,76 @@
+//! Impls for `time::OffsetDateTime`, with the `time` feature.
+//!
+//! A timestamp is an RFC 3339 string such as `"1985-04-12T23:20:50.52Z"`.
+//! Serialization keeps the timestamp's offset, except that an offset with a
+//! seconds component cannot be written in RFC 3339, so such a timestamp is
+//! written as the same instant in UTC.
+//!
+//! RFC 3339 only has years 0000 through 9999. Other years are written in the
+//! expanded form of ISO 8601, with a sign and six digits, as in
+//! `"-000044-03-15T12:00:00Z"`, and are read back the same way.
+
+use crate::de::{Deserialize, Visitor};
+use crate::error::{Error, Result};
+use crate::ser::{Fragment, Serialize};
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use ::time::format_description::well_known::Rfc3339;
+use ::time::{OffsetDateTime, UtcOffset};
+
+// A leap year stands in for an expanded year while the rest of the timestamp
+// is formatted or parsed, so that February 29 is accepted.
+const STAND_IN_YEAR: i32 = 2000;
+
+impl Serialize for OffsetDateTime {
+    #[allow(clippy::option_if_let_else)]
+    fn begin(&self) -> Fragment {
+        let formatted = format(*self).or_else(|| format(self.checked_to_offset(UtcOffset::UTC)?));
+        match formatted {
+            Some(s) => Fragment::Str(Cow::Owned(s)),
+            // Only for an instant at the very edge of the supported range
+            // whose offset also cannot be written.
+            None => Fragment::Null,
+        }
+    }
+}
+
+fn format(at: OffsetDateTime) -> Option<String> {
+    let year = at.year();
+    if (0..=9999).contains(&year) {
+        return at.format(&Rfc3339).ok();
+    }
+    let mut s = at.replace_year(STAND_IN_YEAR).ok()?.format(&Rfc3339).ok()?;
+    s.replace_range(..4, &format!("{year:+07}"));
+    Some(s)
+}
+
+fn parse(s: &str) -> Option<OffsetDateTime> {
+    match s.as_bytes().first() {
+        Some(b'+' | b'-') => {
+            let year = s.get(..7)?;
+            if !year[1..].bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            let rest = format!("{STAND_IN_YEAR}{}", &s[7..]);
+            let at = OffsetDateTime::parse(&rest, &Rfc3339).ok()?;
+            at.replace_year(year.parse().ok()?).ok()
+        }
+        _ => OffsetDateTime::parse(s, &Rfc3339).ok(),
+    }
+}
+
+impl Deserialize for OffsetDateTime {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        make_place!(Place);
+
+        impl Visitor for Place<OffsetDateTime> {
+            fn string(&mut self, s: &str) -> Result<()> {
+                self.out = Some(parse(s).ok_or(Error)?);
+                Ok(())
+            }
+        }
+
+        Place::new(out)
+    }
+}
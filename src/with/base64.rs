@@ -0,0 +1,147 @@
+//! Bytes as a base64 string.
+//!
+//! Uses the standard alphabet from RFC 4648 with `=` padding. Deserialization
+//! fails on any other character, on missing or misplaced padding, and on
+//! nonzero bits after the last encoded byte, so every byte sequence has exactly
+//! one accepted encoding.
+//!
+//! ```rust
+//! use miniserde::with::base64;
+//! use miniserde::{json, Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Blob {
+//!     #[serde(with = "base64")]
+//!     data: Vec<u8>,
+//! }
+//!
+//! let blob = Blob { data: b"hi!".to_vec() };
+//! assert_eq!(json::to_string(&blob), r#"{"data":"aGkh"}"#);
+//!
+//! let blob: Blob = json::from_str(r#"{"data":"aGk="}"#).unwrap();
+//! assert_eq!(blob.data, b"hi");
+//! ```
+//!
+//! Bytes that are not in a struct field can be serialized by wrapping them in
+//! [`Base64`].
+
+use crate::de::Visitor;
+use crate::error::{Error, Result};
+use crate::ser::{Fragment, Serialize};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// Maps each byte to its 6-bit value, or 0xFF if it is not in the alphabet.
+const DECODE: [u8; 256] = {
+    let mut table = [0xFF; 256];
+    let mut i = 0;
+    while i < 64 {
+        table[ALPHABET[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+/// Borrowed bytes that serialize as a base64 string.
+///
+/// ```rust
+/// use miniserde::json;
+/// use miniserde::with::base64::Base64;
+///
+/// let bytes: &[u8] = &[0xDE, 0xAD, 0xBE, 0xEF];
+/// assert_eq!(json::to_string(&Base64(bytes)), r#""3q2+7w==""#);
+/// ```
+pub struct Base64<'a>(pub &'a [u8]);
+
+impl Serialize for Base64<'_> {
+    fn begin(&self) -> Fragment {
+        serialize(self.0)
+    }
+}
+
+pub fn serialize<T>(bytes: &T) -> Fragment
+where
+    T: ?Sized + AsRef<[u8]>,
+{
+    Fragment::Str(Cow::Owned(encode(bytes.as_ref())))
+}
+
+pub fn deserialize(out: &mut Option<Vec<u8>>) -> &mut dyn Visitor {
+    make_place!(Place);
+
+    impl Visitor for Place<Vec<u8>> {
+        fn string(&mut self, s: &str) -> Result<()> {
+            self.out = Some(decode(s.as_bytes())?);
+            Ok(())
+        }
+    }
+
+    Place::new(out)
+}
+
+fn encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    let mut chunks = bytes.chunks_exact(3);
+    for chunk in &mut chunks {
+        let n = u32::from(chunk[0]) << 16 | u32::from(chunk[1]) << 8 | u32::from(chunk[2]);
+        for shift in [18, 12, 6, 0] {
+            out.push(ALPHABET[(n >> shift) as usize & 0x3F] as char);
+        }
+    }
+    match *chunks.remainder() {
+        [a] => {
+            let n = u32::from(a) << 16;
+            out.push(ALPHABET[(n >> 18) as usize & 0x3F] as char);
+            out.push(ALPHABET[(n >> 12) as usize & 0x3F] as char);
+            out.push_str("==");
+        }
+        [a, b] => {
+            let n = u32::from(a) << 16 | u32::from(b) << 8;
+            out.push(ALPHABET[(n >> 18) as usize & 0x3F] as char);
+            out.push(ALPHABET[(n >> 12) as usize & 0x3F] as char);
+            out.push(ALPHABET[(n >> 6) as usize & 0x3F] as char);
+            out.push('=');
+        }
+        _ => {}
+    }
+    out
+}
+
+fn decode(input: &[u8]) -> Result<Vec<u8>> {
+    if input.len() % 4 != 0 {
+        return Err(Error);
+    }
+
+    let mut out = Vec::with_capacity(input.len() / 4 * 3);
+    let mut chunks = input.chunks_exact(4).peekable();
+    while let Some(chunk) = chunks.next() {
+        let padding = match chunk {
+            [.., b'=', b'='] => 2,
+            [.., b'='] => 1,
+            _ => 0,
+        };
+        if padding > 0 && chunks.peek().is_some() {
+            return Err(Error);
+        }
+
+        let mut n = 0u32;
+        for &byte in &chunk[..4 - padding] {
+            let value = DECODE[byte as usize];
+            if value == 0xFF {
+                return Err(Error);
+            }
+            n = n << 6 | u32::from(value);
+        }
+        n <<= 6 * padding;
+
+        let decoded = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        if decoded[3 - padding..].iter().any(|&byte| byte != 0) {
+            return Err(Error);
+        }
+        out.extend_from_slice(&decoded[..3 - padding]);
+    }
+    Ok(out)
+}
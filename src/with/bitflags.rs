@@ -0,0 +1,120 @@
+//! Flags from the `bitflags` crate as an array of flag names.
+//!
+//! Serialization writes the name of every named flag that is set, in the order
+//! the flags are declared. Bits that do not belong to any named flag are not
+//! written. Deserialization ORs together the flags named in the array and fails
+//! on a name that is not a declared flag.
+//!
+//! ```rust
+//! use bitflags::bitflags;
+//! use miniserde::with::bitflags as flags;
+//! use miniserde::{json, Deserialize, Serialize};
+//!
+//! bitflags! {
+//!     #[derive(Debug, PartialEq)]
+//!     struct Permissions: u8 {
+//!         const Read = 1;
+//!         const Write = 2;
+//!         const Execute = 4;
+//!     }
+//! }
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct File {
+//!     #[serde(with = "flags")]
+//!     mode: Permissions,
+//! }
+//!
+//! let file: File = json::from_str(r#"{"mode":["Read","Write"]}"#).unwrap();
+//! assert_eq!(file.mode, Permissions::Read | Permissions::Write);
+//! assert_eq!(json::to_string(&file), r#"{"mode":["Read","Write"]}"#);
+//! ```
+//!
+//! This module is available with the `bitflags` feature.
+
+use crate::de::{Seq as DeSeq, Visitor};
+use crate::error::{Error, Result};
+use crate::ser::{Fragment, Seq as SerSeq, Serialize};
+use ::bitflags::Flags;
+use alloc::boxed::Box;
+use alloc::vec::{self, Vec};
+use core::mem;
+
+pub fn serialize<T>(flags: &T) -> Fragment
+where
+    T: Flags,
+{
+    struct NameStream {
+        names: vec::IntoIter<&'static str>,
+        current: &'static str,
+    }
+
+    impl SerSeq for NameStream {
+        fn next(&mut self) -> Option<&dyn Serialize> {
+            self.current = self.names.next()?;
+            Some(&self.current)
+        }
+    }
+
+    let names: Vec<&'static str> = flags.iter_names().map(|(name, _)| name).collect();
+    Fragment::Seq(Box::new(NameStream {
+        names: names.into_iter(),
+        current: "",
+    }))
+}
+
+pub fn deserialize<T>(out: &mut Option<T>) -> &mut dyn Visitor
+where
+    T: Flags,
+{
+    make_place!(Place);
+
+    impl<T> Visitor for Place<T>
+    where
+        T: Flags,
+    {
+        fn seq(&mut self) -> Result<Box<dyn DeSeq + '_>> {
+            Ok(Box::new(FlagsBuilder {
+                out: &mut self.out,
+                element: Element { flags: T::empty() },
+            }))
+        }
+    }
+
+    struct FlagsBuilder<'a, T: 'a> {
+        out: &'a mut Option<T>,
+        element: Element<T>,
+    }
+
+    // Receives each name in the array and adds its flag to the ones so far.
+    struct Element<T> {
+        flags: T,
+    }
+
+    impl<T> Visitor for Element<T>
+    where
+        T: Flags,
+    {
+        fn string(&mut self, s: &str) -> Result<()> {
+            let flag = T::from_name(s).ok_or(Error)?;
+            self.flags.insert(flag);
+            Ok(())
+        }
+    }
+
+    impl<'a, T> DeSeq for FlagsBuilder<'a, T>
+    where
+        T: Flags,
+    {
+        fn element(&mut self) -> Result<&mut dyn Visitor> {
+            Ok(&mut self.element)
+        }
+
+        fn finish(&mut self) -> Result<()> {
+            *self.out = Some(mem::replace(&mut self.element.flags, T::empty()));
+            Ok(())
+        }
+    }
+
+    Place::new(out)
+}
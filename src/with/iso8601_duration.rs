@@ -0,0 +1,121 @@
+//! Durations as an ISO 8601 duration string, such as `"PT1.5S"`.
+//!
+//! Serialization always writes whole and fractional seconds, `"PT90S"` rather
+//! than `"PT1M30S"`, with no trailing zeros in the fraction. Deserialization
+//! also accepts days, hours and minutes, as in `"P1DT2H3M4.5S"`. Each part
+//! is optional but at least one must be present, only the seconds may have a
+//! fraction, and the fraction has at most 9 digits. Years, months and weeks
+//! are rejected, since they have no fixed length, as are negative durations.
+//!
+//! ```rust
+//! use miniserde::{json, Deserialize, Serialize};
+//! use std::time::Duration;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Timeout {
+//!     #[serde(with = "miniserde::with::iso8601_duration")]
+//!     after: Duration,
+//! }
+//!
+//! let timeout = Timeout { after: Duration::from_millis(1500) };
+//! assert_eq!(json::to_string(&timeout), r#"{"after":"PT1.5S"}"#);
+//!
+//! let timeout: Timeout = json::from_str(r#"{"after":"PT2M"}"#).unwrap();
+//! assert_eq!(timeout.after, Duration::from_secs(120));
+//! ```
+
+use crate::de::Visitor;
+use crate::error::{Error, Result};
+use crate::ser::Fragment;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use core::time::Duration;
+
+pub fn serialize(duration: &Duration) -> Fragment {
+    Fragment::Str(Cow::Owned(encode(*duration)))
+}
+
+pub fn deserialize(out: &mut Option<Duration>) -> &mut dyn Visitor {
+    make_place!(Place);
+
+    impl Visitor for Place<Duration> {
+        fn string(&mut self, s: &str) -> Result<()> {
+            self.out = Some(decode(s.as_bytes()).ok_or(Error)?);
+            Ok(())
+        }
+    }
+
+    Place::new(out)
+}
+
+fn encode(duration: Duration) -> String {
+    let mut out = format!("PT{}", duration.as_secs());
+    let nanos = duration.subsec_nanos();
+    if nanos != 0 {
+        let fraction = format!("{nanos:09}");
+        out.push('.');
+        out.push_str(fraction.trim_end_matches('0'));
+    }
+    out.push('S');
+    out
+}
+
+#[allow(clippy::option_if_let_else)]
+fn decode(input: &[u8]) -> Option<Duration> {
+    let rest = input.strip_prefix(b"P")?;
+    let (date, time) = match rest.iter().position(|&b| b == b'T') {
+        Some(t) => (&rest[..t], Some(&rest[t + 1..])),
+        None => (rest, None),
+    };
+    if date.is_empty() && time.is_none() {
+        return None;
+    }
+
+    let mut secs = 0u64;
+    let mut nanos = 0u32;
+    if !date.is_empty() {
+        secs = digits(date.strip_suffix(b"D")?)?.checked_mul(86400)?;
+    }
+    if let Some(mut time) = time {
+        if time.is_empty() {
+            return None;
+        }
+        for (unit, scale) in [(b'H', 3600), (b'M', 60)] {
+            if let Some(i) = time.iter().position(|&b| b == unit) {
+                secs = secs.checked_add(digits(&time[..i])?.checked_mul(scale)?)?;
+                time = &time[i + 1..];
+            }
+        }
+        if !time.is_empty() {
+            let number = time.strip_suffix(b"S")?;
+            let (whole, fraction) = match number.iter().position(|&b| b == b'.') {
+                Some(dot) => (&number[..dot], Some(&number[dot + 1..])),
+                None => (number, None),
+            };
+            secs = secs.checked_add(digits(whole)?)?;
+            if let Some(fraction) = fraction {
+                if fraction.len() > 9 {
+                    return None;
+                }
+                let scale = 10u64.pow(9 - fraction.len() as u32);
+                nanos = (digits(fraction)? * scale) as u32;
+            }
+        }
+    }
+    Some(Duration::new(secs, nanos))
+}
+
+/// A nonempty run of ASCII digits as an integer, if it fits.
+fn digits(input: &[u8]) -> Option<u64> {
+    if input.is_empty() {
+        return None;
+    }
+    input.iter().try_fold(0u64, |n, &b| {
+        if b.is_ascii_digit() {
+            n.checked_mul(10)?.checked_add(u64::from(b - b'0'))
+        } else {
+            None
+        }
+    })
+}
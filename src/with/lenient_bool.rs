@@ -0,0 +1,59 @@
+//! Booleans that may also be written as `0`/`1` or as a string.
+//!
+//! Deserialization accepts `true` and `false`, the integers `0` and `1`, and
+//! the strings `"true"`, `"false"`, `"yes"` and `"no"`. Serialization always
+//! writes a JSON boolean. Without this module, a `bool` field accepts only
+//! `true` and `false`.
+//!
+//! ```rust
+//! use miniserde::{json, Deserialize};
+//!
+//! #[derive(Deserialize)]
+//! struct Settings {
+//!     #[serde(with = "miniserde::with::lenient_bool")]
+//!     enabled: bool,
+//! }
+//!
+//! let settings: Settings = json::from_str(r#"{"enabled":"yes"}"#).unwrap();
+//! assert!(settings.enabled);
+//! ```
+
+use crate::de::Visitor;
+use crate::error::{Error, Result};
+use crate::ser::Fragment;
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+pub const fn serialize(b: &bool) -> Fragment {
+    Fragment::Bool(*b)
+}
+
+pub fn deserialize(out: &mut Option<bool>) -> &mut dyn Visitor {
+    make_place!(Place);
+
+    impl Visitor for Place<bool> {
+        fn boolean(&mut self, b: bool) -> Result<()> {
+            self.out = Some(b);
+            Ok(())
+        }
+
+        fn nonnegative(&mut self, n: u64) -> Result<()> {
+            self.out = Some(match n {
+                0 => false,
+                1 => true,
+                _ => return Err(Error),
+            });
+            Ok(())
+        }
+
+        fn string(&mut self, s: &str) -> Result<()> {
+            self.out = Some(match s {
+                "true" | "yes" => true,
+                "false" | "no" => false,
+                _ => return Err(Error),
+            });
+            Ok(())
+        }
+    }
+
+    Place::new(out)
+}
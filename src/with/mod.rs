@@ -0,0 +1,22 @@
+//! Alternative representations for use with `#[serde(with = "...")]`.
+//!
+//! A field attribute `#[serde(with = "module")]` makes the derived impls
+//! serialize and deserialize that field through functions in `module` instead
+//! of the field type's own `Serialize` and `Deserialize` impls. The module must
+//! provide two functions for the field type `T`:
+//!
+//! - `serialize(&T) -> Fragment`, shaped like
+//!   [`Serialize::begin`][crate::Serialize::begin], and
+//! - `deserialize(&mut Option<T>) -> &mut dyn Visitor`, shaped like
+//!   [`Deserialize::begin`][crate::Deserialize::begin].
+//!
+//! Either can be written the same way as a handwritten impl of the
+//! corresponding trait.
+
+pub mod base64;
+pub mod iso8601_duration;
+pub mod lenient_bool;
+pub mod rectangular;
+
+#[cfg(feature = "bitflags")]
+pub mod bitflags;
@@ -0,0 +1,93 @@
+//! Matrices as an array of rows that must all have the same length.
+//!
+//! Serialization is the same as for `Vec<Vec<T>>`. Deserialization fails on
+//! ragged input, where some row has a different length than the first.
+//!
+//! ```rust
+//! use miniserde::{json, Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Image {
+//!     #[serde(with = "miniserde::with::rectangular")]
+//!     pixels: Vec<Vec<f64>>,
+//! }
+//!
+//! let image: Image = json::from_str(r#"{"pixels":[[0,1],[1,0]]}"#).unwrap();
+//! assert_eq!(image.pixels, [[0.0, 1.0], [1.0, 0.0]]);
+//!
+//! let ragged = json::from_str::<Image>(r#"{"pixels":[[0,1],[1]]}"#);
+//! assert!(ragged.is_err());
+//! ```
+
+use crate::de::{Deserialize, Seq, Visitor};
+use crate::error::{Error, Result};
+use crate::ser::{Fragment, Serialize};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::mem;
+
+#[allow(clippy::ptr_arg)]
+pub fn serialize<T>(rows: &Vec<Vec<T>>) -> Fragment
+where
+    T: Serialize,
+{
+    rows.begin()
+}
+
+pub fn deserialize<T>(out: &mut Option<Vec<Vec<T>>>) -> &mut dyn Visitor
+where
+    T: Deserialize,
+{
+    make_place!(Place);
+
+    impl<T> Visitor for Place<Vec<Vec<T>>>
+    where
+        T: Deserialize,
+    {
+        fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+            Ok(Box::new(RowsBuilder {
+                out: &mut self.out,
+                rows: Vec::new(),
+                row: None,
+            }))
+        }
+    }
+
+    struct RowsBuilder<'a, T: 'a> {
+        out: &'a mut Option<Vec<Vec<T>>>,
+        rows: Vec<Vec<T>>,
+        row: Option<Vec<T>>,
+    }
+
+    impl<'a, T> RowsBuilder<'a, T> {
+        fn shift(&mut self) -> Result<()> {
+            if let Some(row) = self.row.take() {
+                if let Some(first) = self.rows.first() {
+                    if row.len() != first.len() {
+                        return Err(Error);
+                    }
+                }
+                self.rows.push(row);
+            }
+            Ok(())
+        }
+    }
+
+    impl<'a, T> Seq for RowsBuilder<'a, T>
+    where
+        T: Deserialize,
+    {
+        fn element(&mut self) -> Result<&mut dyn Visitor> {
+            self.shift()?;
+            Ok(Deserialize::begin(&mut self.row))
+        }
+
+        fn finish(&mut self) -> Result<()> {
+            self.shift()?;
+            *self.out = Some(mem::take(&mut self.rows));
+            Ok(())
+        }
+    }
+
+    Place::new(out)
+}
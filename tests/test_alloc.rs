@@ -0,0 +1,104 @@
+use miniserde::json::{self, Value};
+use miniserde::ser::{Fragment, Serialize};
+use miniserde::Deserialize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::borrow::Cow;
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    let value = f();
+    let after = ALLOCATIONS.with(Cell::get);
+    drop(value);
+    after - before
+}
+
+struct Owned;
+
+impl Serialize for Owned {
+    fn begin(&self) -> Fragment<'_> {
+        Fragment::Str(Cow::Owned("x".repeat(100)))
+    }
+}
+
+#[test]
+fn test_to_value_numeric_array() {
+    // One for the boxed Seq and one for the array, regardless of length.
+    let v: Vec<u32> = (0..1000).collect();
+    assert_eq!(allocations(|| json::to_value(&v)), 2);
+}
+
+#[test]
+fn test_to_value_owned_string() {
+    // The one allocation is the string itself, moved into the Value.
+    assert_eq!(allocations(|| json::to_value(&Owned)), 1);
+}
+
+#[test]
+fn test_from_str_scalar() {
+    assert_eq!(allocations(|| json::from_str::<u64>("42").unwrap()), 0);
+    assert_eq!(allocations(|| json::from_str::<bool>(" true ").unwrap()), 0);
+    assert_eq!(allocations(|| json::from_str::<Option<u8>>("null").unwrap()), 0);
+    // Only the String itself.
+    assert_eq!(allocations(|| json::from_str::<String>(r#""x""#).unwrap()), 1);
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Narrow {
+    a: u8,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Wide {
+    a: u8,
+    b: u8,
+    c: u8,
+    d: u8,
+    e: u8,
+    f: u8,
+    g: u8,
+    h: u8,
+}
+
+#[test]
+fn test_struct_keys_do_not_allocate() {
+    // Keys are matched against field names as borrowed strings.
+    let narrow = allocations(|| json::from_str::<Narrow>(r#"{"a":1}"#).unwrap());
+    let j = r#"{"a":1,"b":2,"c":3,"d":4,"e":5,"f":6,"g":7,"h":8,"unknown":9}"#;
+    assert_eq!(allocations(|| json::from_str::<Wide>(j).unwrap()), narrow);
+}
+
+#[test]
+fn test_to_value_of_value_clones() {
+    // Cloned directly, with no boxed Seq or Map per container.
+    let value: Value = json::from_str(r#"{"a":[1,{"b":"c"}],"d":[]}"#).unwrap();
+    let cloned = allocations(|| value.clone());
+    assert_eq!(allocations(|| json::to_value(&value)), cloned);
+}
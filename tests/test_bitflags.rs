@@ -0,0 +1,60 @@
+#![cfg(feature = "bitflags")]
+
+use bitflags::bitflags;
+use miniserde::with::bitflags as flags;
+use miniserde::{json, Deserialize, Serialize};
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct Permissions: u32 {
+        const Read = 0b0001;
+        const Write = 0b0010;
+        const Execute = 0b0100;
+        const Delete = 0b1000;
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct File {
+    #[serde(with = "flags")]
+    mode: Permissions,
+}
+
+#[test]
+fn test_round_trip() {
+    let cases = [
+        (Permissions::empty(), r#"{"mode":[]}"#),
+        (Permissions::Write, r#"{"mode":["Write"]}"#),
+        (
+            Permissions::Read | Permissions::Execute | Permissions::Delete,
+            r#"{"mode":["Read","Execute","Delete"]}"#,
+        ),
+        (Permissions::all(), r#"{"mode":["Read","Write","Execute","Delete"]}"#),
+    ];
+
+    for (mode, expected) in cases {
+        let file = File { mode };
+        let j = json::to_string(&file);
+        assert_eq!(j, expected);
+        assert_eq!(json::from_str::<File>(&j).unwrap(), file);
+    }
+}
+
+#[test]
+fn test_deserialize() {
+    let j = r#"{"mode":["Write","Read","Write"]}"#;
+    let file: File = json::from_str(j).unwrap();
+    assert_eq!(file.mode, Permissions::Read | Permissions::Write);
+
+    assert!(json::from_str::<File>(r#"{"mode":["Read","Admin"]}"#).is_err());
+    assert!(json::from_str::<File>(r#"{"mode":[1]}"#).is_err());
+    assert!(json::from_str::<File>(r#"{"mode":"Read"}"#).is_err());
+}
+
+#[test]
+fn test_unnamed_bits() {
+    let file = File {
+        mode: Permissions::from_bits_retain(0b1_0001),
+    };
+    assert_eq!(json::to_string(&file), r#"{"mode":["Read"]}"#);
+}
@@ -0,0 +1,724 @@
+#![allow(clippy::float_cmp)]
+
+use miniserde::json::{self, Value};
+use miniserde::Deserialize;
+use std::collections::BTreeMap;
+
+#[test]
+fn test_trailing_characters() {
+    let j = r#"{"a":1} garbage"#;
+    let error = json::from_str::<Value>(j).unwrap_err();
+    assert_eq!(error.offset(), Some(8));
+    assert_eq!(&j[8..9], "g");
+
+    let error = json::from_slice::<Value>(j.as_bytes()).unwrap_err();
+    assert_eq!(error.offset(), Some(8));
+}
+
+#[test]
+fn test_top_level_scalars() {
+    assert!(json::from_str::<bool>("true").unwrap());
+    assert_eq!(json::from_str::<u64>("42").unwrap(), 42);
+    assert_eq!(json::from_str::<String>(r#""x""#).unwrap(), "x");
+    assert_eq!(json::from_str::<Option<u8>>("null").unwrap(), None);
+    assert_eq!(json::from_str::<Option<u8>>(" 7 ").unwrap(), Some(7));
+
+    assert!(json::from_str::<bool>("1").is_err());
+    assert!(json::from_str::<u64>("-1").is_err());
+    assert!(json::from_str::<String>("null").is_err());
+    assert!(json::from_str::<u8>("256").is_err());
+}
+
+#[test]
+fn test_error_without_position() {
+    let error = json::from_value::<bool>(Value::Null).unwrap_err();
+    assert_eq!(error.offset(), None);
+    assert_eq!(error.to_string(), "miniserde error");
+}
+
+#[test]
+fn test_non_finite_literals() {
+    let j = "[NaN, Infinity, -Infinity]";
+
+    let relaxed = json::Deserializer::new().allow_non_finite(true);
+    let floats: Vec<f64> = relaxed.from_str(j).unwrap();
+    assert!(floats[0].is_nan());
+    assert_eq!(floats[1], f64::INFINITY);
+    assert_eq!(floats[2], f64::NEG_INFINITY);
+
+    let floats: Vec<f64> = relaxed.from_slice(j.as_bytes()).unwrap();
+    assert_eq!(floats.len(), 3);
+
+    let value: Value = relaxed.from_str("-Infinity").unwrap();
+    assert_eq!(value.as_f64(), Some(f64::NEG_INFINITY));
+
+    json::from_str::<Vec<f64>>(j).unwrap_err();
+    json::Deserializer::new().from_str::<Vec<f64>>(j).unwrap_err();
+    for j in ["NaN", "Infinity", "-Infinity"] {
+        json::from_str::<f64>(j).unwrap_err();
+    }
+    for j in ["nan", "Inf", "-Infinit", "NaNa"] {
+        relaxed.from_str::<f64>(j).unwrap_err();
+    }
+}
+
+#[test]
+fn test_utf8_bom() {
+    let j = b"\xEF\xBB\xBF{\"a\": [1, 2]}";
+    let value: Value = json::from_slice(j).unwrap();
+    assert_eq!(json::to_string(&value), r#"{"a":[1,2]}"#);
+
+    let error = json::from_slice::<Value>(b"\xEF\xBB\xBF{\"a\" 1}").unwrap_err();
+    assert_eq!(error.offset(), Some(8));
+
+    for j in [&b"\xFE\xFF\0[\0]"[..], b"\xFF\xFE[\0]\0", b"\0\0\xFE\xFF"] {
+        let error = json::from_slice::<Value>(j).unwrap_err();
+        assert_eq!(error.offset(), Some(0));
+    }
+}
+
+#[test]
+fn test_invalid_utf8() {
+    #[derive(Deserialize, Debug)]
+    struct Known {
+        #[allow(dead_code)]
+        known: Option<u8>,
+    }
+
+    // Truncated, overlong, surrogate, and stray continuation bytes, at every
+    // offset around the chunks scanned at once for quotes and backslashes.
+    let invalid: [&[u8]; 6] = [
+        b"\xC3",
+        b"\xE2\x82",
+        b"\xF0\x9F\x98",
+        b"\xC0\x80",
+        b"\xED\xA0\x80",
+        b"\x80",
+    ];
+    for len in 0..70 {
+        for bytes in invalid {
+            for at in [0, len / 2, len] {
+                let mut string = vec![b'x'; len];
+                string.splice(at..at, bytes.iter().copied());
+                let quoted = [&b"\""[..], &string, b"\""].concat();
+                let escaped = [&b"\"\\n"[..], &string, b"\""].concat();
+                let key = [&b"{\""[..], &string, b"\":1}"].concat();
+                let ignored = [&b"{\"unknown\":\""[..], &string, b"\"}"].concat();
+                for j in [&quoted, &escaped, &key, &ignored] {
+                    json::from_slice::<Value>(j).unwrap_err();
+                }
+                json::from_slice::<Known>(&ignored).unwrap_err();
+                json::from_slice::<Value>(&quoted[..quoted.len() - 1]).unwrap_err();
+            }
+        }
+
+        let plain = "x".repeat(len);
+        let j = format!(r#"["{plain}é€😀", "{plain}\/é"]"#);
+        let expected = [format!("{plain}é€😀"), format!("{plain}/é")];
+        assert_eq!(json::from_slice::<Vec<String>>(j.as_bytes()).unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_duplicate_keys() {
+    #[derive(Deserialize, Debug)]
+    struct Example {
+        a: u8,
+    }
+
+    let j = r#"{"a":1,"a":2}"#;
+
+    let example: Example = json::from_str(j).unwrap();
+    assert_eq!(example.a, 2);
+    let map: BTreeMap<String, u8> = json::from_str(j).unwrap();
+    assert_eq!(map["a"], 2);
+    let value: Value = json::from_str(j).unwrap();
+    assert_eq!(json::to_string(&value), r#"{"a":2}"#);
+
+    let strict = json::Deserializer::new().deny_duplicate_keys(true);
+    assert_eq!(strict.from_str::<Example>(j).unwrap_err().offset(), Some(10));
+    assert!(strict.from_str::<BTreeMap<String, u8>>(j).is_err());
+    assert!(strict.from_str::<Value>(j).is_err());
+
+    let nested = r#"{"a":1,"b":{"a":2},"c":[{"a":3}]}"#;
+    assert!(strict.from_str::<Value>(nested).is_ok());
+}
+
+#[test]
+fn test_json_lines() {
+    use miniserde::ErrorKind;
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Record {
+        id: u32,
+        name: String,
+    }
+
+    let j = concat!(
+        "{\"id\": 1, \"name\": \"first\"}\n",
+        "{\"id\": 2, \"name\": }\n",
+        "\n",
+        "  {\"id\": 3, \"name\": \"third\"}\r\n",
+    );
+
+    let mut records = json::iter_from_str::<Record>(j);
+    assert_eq!(
+        records.next().unwrap().unwrap(),
+        Record {
+            id: 1,
+            name: "first".to_owned(),
+        },
+    );
+    let error = records.next().unwrap().unwrap_err();
+    assert_eq!(error.offset(), Some(46));
+    assert_eq!(
+        records.next().unwrap().unwrap(),
+        Record {
+            id: 3,
+            name: "third".to_owned(),
+        },
+    );
+    assert!(records.next().is_none());
+
+    assert_eq!(json::iter_from_str::<Value>("").count(), 0);
+    assert_eq!(json::iter_from_str::<Value>("1\n2").count(), 2);
+
+    let mut values = json::iter_from_str::<Value>("1\n\"\\udc00\"\n+1\n2");
+    assert!(values.next().unwrap().is_ok());
+    let error = values.next().unwrap().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidSurrogate);
+    assert_eq!(error.offset(), Some(9));
+    let error = values.next().unwrap().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidNumber);
+    assert_eq!(error.offset(), Some(11));
+    assert!(values.next().unwrap().is_ok());
+}
+
+#[test]
+fn test_concatenated_values() {
+    let j = r#"{"a":1}{"a":2}[1,2,3] "x"null 4.5
+        true{}"#;
+    let values: Vec<String> = json::stream_from_str::<Value>(j)
+        .map(|value| json::to_string(&value.unwrap()))
+        .collect();
+    assert_eq!(
+        values,
+        [r#"{"a":1}"#, r#"{"a":2}"#, "[1,2,3]", r#""x""#, "null", "4.5", "true", "{}"],
+    );
+
+    let mut stream = json::stream_from_str::<Value>(r#"[1] {"a":} [2]"#);
+    assert!(stream.next().unwrap().is_ok());
+    assert_eq!(stream.byte_offset(), 3);
+    let error = stream.next().unwrap().unwrap_err();
+    assert_eq!(error.offset(), Some(10));
+    assert!(stream.next().is_none());
+
+    assert!(json::stream_from_str::<Value>(" \n ").next().is_none());
+}
+
+#[test]
+fn test_reader_tokens() {
+    use json::Token::*;
+
+    let j = r#" {"a": [1, -2, 3.5, true, null], "b\n": {"c": "x\ty"}, "d": []} "#;
+    let mut reader = json::reader(j);
+    let expected = [
+        MapStart,
+        Key("a"),
+        SeqStart,
+        Nonnegative(1),
+        Negative(-2),
+        Float(3.5),
+        Bool(true),
+        Null,
+        SeqEnd,
+        Key("b\n"),
+        MapStart,
+        Key("c"),
+        Str("x\ty"),
+        MapEnd,
+        Key("d"),
+        SeqStart,
+        SeqEnd,
+        MapEnd,
+    ];
+    for token in expected {
+        assert_eq!(reader.next_token().unwrap(), Some(token));
+    }
+    assert_eq!(reader.byte_offset(), j.len() - 1);
+    assert_eq!(reader.next_token().unwrap(), None);
+    assert_eq!(reader.next_token().unwrap(), None);
+
+    let mut reader = json::reader("\"top\"");
+    assert_eq!(reader.next_token().unwrap(), Some(Str("top")));
+    assert_eq!(reader.next_token().unwrap(), None);
+}
+
+#[test]
+fn test_reader_skip_value() {
+    use json::Token::*;
+
+    let mut big = String::from("[");
+    for i in 0..1000 {
+        if i > 0 {
+            big.push(',');
+        }
+        big.push_str(r#"{"k":[1,"s\"",{"n":null}],"m":{}}"#);
+    }
+    big.push(']');
+    let j = format!(r#"{{"first": {{"big": {big}, "x": 1.5}}, "second": [true, "x"]}}"#);
+
+    let mut reader = json::reader(&j);
+    assert_eq!(reader.next_token().unwrap(), Some(MapStart));
+    assert_eq!(reader.next_token().unwrap(), Some(Key("first")));
+    reader.skip_value().unwrap();
+    assert_eq!(reader.next_token().unwrap(), Some(Key("second")));
+    assert_eq!(reader.next_token().unwrap(), Some(SeqStart));
+    reader.skip_value().unwrap();
+    assert_eq!(reader.next_token().unwrap(), Some(Str("x")));
+    assert_eq!(reader.next_token().unwrap(), Some(SeqEnd));
+    assert_eq!(reader.next_token().unwrap(), Some(MapEnd));
+    assert_eq!(reader.next_token().unwrap(), None);
+
+    let mut reader = json::reader("[1]");
+    reader.skip_value().unwrap();
+    assert_eq!(reader.next_token().unwrap(), None);
+
+    // There is no value to skip before a key or at the end of an array.
+    let mut reader = json::reader(r#"{"a":1}"#);
+    reader.next_token().unwrap();
+    assert!(reader.skip_value().is_err());
+    let mut reader = json::reader("[]");
+    reader.next_token().unwrap();
+    assert!(reader.skip_value().is_err());
+    assert!(json::reader(r#"{"a":[1}}"#).skip_value().is_err());
+}
+
+#[test]
+fn test_reader_skip_value_scalars() {
+    let skips = |j: &str| {
+        let mut reader = json::reader(j);
+        reader.skip_value().and_then(|()| reader.next_token().map(|token| token.is_none()))
+    };
+
+    for j in [
+        r#""plain""#,
+        r#""esc\"\\\/\b\f\n\r\t\u00e9""#,
+        // Read, this lone surrogate would be an error.
+        r#""\uD800""#,
+        r#"{"k\n":["a",-0,1.5e-7,-12E+3]}"#,
+        "123456789012345678901234567890",
+    ] {
+        assert!(skips(j).unwrap(), "{j}");
+    }
+
+    for j in [
+        r#""unterminated"#,
+        r#""\q""#,
+        r#""\u12""#,
+        r#""\u12G4""#,
+        "01",
+        "-",
+        "1.",
+        "1e",
+        "1e+",
+        "-x",
+        r#"{"a" 1}"#,
+    ] {
+        assert!(skips(j).is_err(), "{j}");
+    }
+
+    // Settings about numbers and keys still apply to what is skipped.
+    let de = json::Deserializer::new().max_number_len(3).deny_duplicate_keys(true);
+    assert!(de.reader("[1234]").skip_value().is_err());
+    assert!(de.reader(r#"{"a":1,"a":2}"#).skip_value().is_err());
+    let mut reader = json::Deserializer::new().allow_non_finite(true).reader("[-Infinity,NaN]");
+    reader.skip_value().unwrap();
+}
+
+#[test]
+fn test_reader_errors() {
+    for j in ["[1,]", "[1 2]", "{\"a\" 1}", "{\"a\":1]", "[}", "{[]:1}", "[1] 2", "", "[1"] {
+        let mut reader = json::reader(j);
+        let error = loop {
+            match reader.next_token() {
+                Ok(Some(_)) => {}
+                Ok(None) => panic!("no error in {j}"),
+                Err(error) => break error,
+            }
+        };
+        assert!(error.offset().is_some());
+        assert_eq!(reader.next_token().unwrap(), None);
+    }
+
+    let strict = json::Deserializer::new().deny_duplicate_keys(true);
+    let mut reader = strict.reader(r#"{"a":1,"a":2}"#);
+    for _ in 0..3 {
+        reader.next_token().unwrap();
+    }
+    assert!(reader.next_token().is_err());
+}
+
+#[test]
+fn test_max_object_keys() {
+    let limited = json::Deserializer::new().max_object_keys(3);
+
+    let j = r#"{"a":{"x":1,"y":2,"z":3},"b":[{"p":1,"q":2,"r":3}],"c":{}}"#;
+    assert!(limited.from_str::<Value>(j).is_ok());
+
+    let j = r#"{"a":1,"b":2,"c":3,"d":4,"e":5}"#;
+    let error = limited.from_str::<Value>(j).unwrap_err();
+    assert_eq!(error.offset(), Some(19));
+    assert!(j[19..].starts_with(r#""d""#));
+    assert!(json::from_str::<Value>(j).is_ok());
+
+    let nested = r#"[{"inner":{"a":1,"b":2,"c":3,"d":4}}]"#;
+    assert!(limited.from_str::<Value>(nested).is_err());
+
+    let duplicates = r#"{"a":1,"a":2,"a":3,"a":4}"#;
+    assert!(limited.from_str::<BTreeMap<String, u8>>(duplicates).is_err());
+}
+
+#[test]
+fn test_max_array_len() {
+    let limited = json::Deserializer::new().max_array_len(10_000);
+
+    let j = json::to_string(&vec![0u8; 10_000]);
+    assert_eq!(limited.from_str::<Vec<u8>>(&j).unwrap().len(), 10_000);
+
+    let j = json::to_string(&vec![0u8; 10_001]);
+    let error = limited.from_str::<Value>(&j).unwrap_err();
+    assert_eq!(error.offset(), Some(j.len() - 2));
+    assert!(limited.from_str::<Vec<u8>>(&j).is_err());
+    assert!(json::from_str::<Vec<u8>>(&j).is_ok());
+
+    let limited = json::Deserializer::new().max_array_len(2);
+    assert!(limited.from_str::<Value>(r#"[[1,2],{"a":[3,4]},[]]"#).is_err());
+    assert!(limited.from_str::<Value>(r#"[[1,2],{"a":[3,4],"b":5,"c":6}]"#).is_ok());
+    assert!(limited.from_str::<Value>(r#"{"a":[1,2,3]}"#).is_err());
+
+    let mut reader = limited.reader("[1,2,3]");
+    for _ in 0..3 {
+        reader.next_token().unwrap();
+    }
+    assert!(reader.next_token().is_err());
+}
+
+#[test]
+fn test_needs_escape_scratch() {
+    assert!(!json::needs_escape_scratch(br#"{"a":"plain","b":[1,"two",null]}"#));
+    assert!(!json::needs_escape_scratch(b""));
+    assert!(json::needs_escape_scratch(br#"{"a":"line\nbreak"}"#));
+    assert!(json::needs_escape_scratch(br#"{"key\u00e9":1}"#));
+    assert!(json::needs_escape_scratch(br#"["x", "\""]"#));
+}
+
+#[test]
+fn test_string_scan_offsets() {
+    // The scan for the end of a plain run of string bytes works on 16 or 32
+    // bytes at a time where SIMD is available. Put an escape and the closing
+    // quote at every offset around those boundaries and compare against a
+    // byte-at-a-time expectation.
+    for len in 0..80 {
+        let plain = "x".repeat(len);
+        let j = format!(r#""{plain}""#);
+        assert_eq!(json::from_str::<String>(&j).unwrap(), plain);
+        assert!(!json::needs_escape_scratch(j.as_bytes()));
+
+        for at in 0..=len {
+            let j = format!(r#""{}\n{}""#, &plain[..at], &plain[at..]);
+            let expected = format!("{}\n{}", &plain[..at], &plain[at..]);
+            assert_eq!(json::from_str::<String>(&j).unwrap(), expected, "{j}");
+            assert!(json::needs_escape_scratch(j.as_bytes()), "{j}");
+        }
+    }
+}
+
+#[test]
+fn test_coerce_integral_floats() {
+    let coerce = json::Deserializer::new().coerce_integral_floats(true);
+
+    assert!(json::from_str::<u32>("5.0").is_err());
+    assert_eq!(coerce.from_str::<u32>("5.0").unwrap(), 5);
+    assert_eq!(coerce.from_str::<i8>("-1.0e2").unwrap(), -100);
+    assert_eq!(coerce.from_str::<u64>("0.0").unwrap(), 0);
+    assert_eq!(coerce.from_str::<Vec<u16>>("[1e3, 2]").unwrap(), [1000, 2]);
+
+    for j in ["5.5", "1e-1", "-0.0", "4294967296.0", "1e20", "-1.0"] {
+        assert!(coerce.from_str::<u32>(j).is_err(), "{j}");
+    }
+
+    let float: f64 = coerce.from_str("-0.0").unwrap();
+    assert!(float == 0.0 && float.is_sign_negative());
+    assert_eq!(coerce.from_str::<f64>("2.0").unwrap(), 2.0);
+
+    let value: Value = json::from_str("[5.0]").unwrap();
+    assert!(json::from_value::<Vec<u32>>(value.clone()).is_err());
+    assert_eq!(coerce.from_value::<Vec<u32>>(value).unwrap(), [5]);
+}
+
+#[test]
+fn test_correctly_rounded_floats() {
+    for j in [
+        "2.2250738585072011e-308",
+        "7.038531e-26",
+        "9.109e-31",
+        "2.4703282292062328e-324",
+        "1.00000000000000011102230246251565404236316680908203125",
+        "1.7976931348623158e308",
+        "-123456789012345678901234567890",
+        "9007199254740993",
+        "0.1",
+    ] {
+        let expected: f64 = j.parse().unwrap();
+        let float: f64 = json::from_str(j).unwrap();
+        assert_eq!(float.to_bits(), expected.to_bits(), "{j}");
+        let value: Value = json::from_str(j).unwrap();
+        assert_eq!(value.as_f64().map(f64::to_bits), Some(expected.to_bits()), "{j}");
+    }
+
+    for j in ["1.8e308", "-1e400", "1.", "01.5", "1e", "1e+"] {
+        assert!(json::from_str::<f64>(j).is_err(), "{j}");
+    }
+}
+
+#[test]
+fn test_imprecise_floats() {
+    let fast = json::Deserializer::new().precise_floats(false);
+
+    // Exact in f64 either way.
+    for j in ["0.1", "-2.5e10", "9007199254740992", "1e22", "0"] {
+        let expected: f64 = j.parse().unwrap();
+        assert_eq!(fast.from_str::<f64>(j).unwrap().to_bits(), expected.to_bits(), "{j}");
+    }
+
+    for j in [
+        "2.2250738585072011e-308",
+        "1.00000000000000011102230246251565404236316680908203125",
+        "1.7976931348623157e308",
+        "-123456789012345678901234567890",
+        "6.02214076e23",
+        "1e-300",
+    ] {
+        let expected: f64 = j.parse().unwrap();
+        let float: f64 = fast.from_str(j).unwrap();
+        let ulps = float.to_bits().abs_diff(expected.to_bits());
+        assert!(ulps <= 4, "{j}: {ulps} ulps");
+    }
+
+    assert_eq!(fast.from_str::<f64>("1e-400").unwrap(), 0.0);
+    assert_eq!(fast.from_str::<f64>("0e999999999999").unwrap(), 0.0);
+    for j in ["1.8e308", "-1e400", "1e999999999999"] {
+        assert!(fast.from_str::<f64>(j).is_err(), "{j}");
+    }
+}
+
+#[test]
+fn test_float_round_trip() {
+    // xorshift64*, to cover the whole range of bit patterns deterministically.
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let iterations = if cfg!(miri) { 100 } else { 200_000 };
+    for _ in 0..iterations {
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        let float = f64::from_bits(state.wrapping_mul(0x2545_f491_4f6c_dd1d));
+        if !float.is_finite() {
+            continue;
+        }
+        let j = json::to_string(&float);
+        let parsed: f64 = json::from_str(&j).unwrap();
+        assert_eq!(parsed.to_bits(), float.to_bits(), "{j}");
+    }
+}
+
+#[test]
+fn test_visitor_bytes() {
+    use miniserde::de::Visitor;
+
+    #[derive(Default)]
+    struct Capture {
+        bytes: Vec<u8>,
+    }
+
+    impl Visitor for Capture {
+        fn bytes(&mut self, b: &[u8]) -> miniserde::Result<()> {
+            self.bytes.extend_from_slice(b);
+            Ok(())
+        }
+    }
+
+    let mut capture = Capture::default();
+    capture.bytes(&[0xFF, 0x00, b'a']).unwrap();
+    assert_eq!(capture.bytes, [0xFF, 0x00, b'a']);
+
+    // By default, bytes that are UTF-8 are passed on as a string.
+    let mut out = None;
+    String::begin(&mut out).bytes(b"text").unwrap();
+    assert_eq!(out.unwrap(), "text");
+
+    let mut out = None;
+    assert!(String::begin(&mut out).bytes(&[0xFF]).is_err());
+
+    let mut out = None;
+    Option::<Box<String>>::begin(&mut out).bytes(b"boxed").unwrap();
+    assert_eq!(*out.unwrap().unwrap(), "boxed");
+}
+
+#[test]
+fn test_reusable_parser() {
+    let mut parser = json::Parser::new();
+    for i in 0..100 {
+        let j = format!(r#"{{"key\t{i}":"value\n{i}"}}"#);
+        let map: BTreeMap<String, String> = parser.parse(&j).unwrap();
+        assert_eq!(map[&format!("key\t{i}")], format!("value\n{i}"));
+
+        let map: BTreeMap<String, String> = parser.parse_slice(j.as_bytes()).unwrap();
+        assert_eq!(map.len(), 1);
+    }
+
+    // A failed document does not affect the next one.
+    assert!(parser.parse::<String>(r#""unterminated\n"#).is_err());
+    assert_eq!(parser.parse::<String>(r#""a\"b""#).unwrap(), "a\"b");
+
+    let mut parser = json::Parser::with_config(json::Deserializer::new().allow_non_finite(true));
+    assert!(parser.parse::<f64>("NaN").unwrap().is_nan());
+}
+
+#[test]
+fn test_from_slice_partial() {
+    let input = b" {\"a\":[1,2]}\r\n{\"a\":[3]} trailing";
+    let (first, len): (BTreeMap<String, Vec<u8>>, usize) = json::from_slice_partial(input).unwrap();
+    assert_eq!(first["a"], [1, 2]);
+    assert_eq!(len, 12);
+
+    let rest = &input[len..];
+    let (second, len): (BTreeMap<String, Vec<u8>>, usize) = json::from_slice_partial(rest).unwrap();
+    assert_eq!(second["a"], [3]);
+    assert_eq!(&rest[len..], b" trailing");
+
+    assert!(json::from_slice_partial::<Value>(b" trailing").is_err());
+    assert!(json::from_slice_partial::<Value>(b"").is_err());
+    assert!(json::from_slice_partial::<Value>(b"[1,").is_err());
+
+    let (value, len) = json::from_slice_partial::<String>(b"\xEF\xBB\xBF\"x\"\xFF").unwrap();
+    assert_eq!((value.as_str(), len), ("x", 6));
+}
+
+#[test]
+fn test_input_limits() {
+    let de = json::Deserializer::new().max_number_len(24);
+    assert!(de.from_str::<f64>("-1.2345678901234567e-300").is_ok());
+    assert!(de.from_str::<f64>("-1.23456789012345678e-300").is_err());
+    assert_eq!(de.from_str::<Vec<u64>>("[18446744073709551615]").unwrap(), [u64::MAX]);
+
+    let digits = "7".repeat(1_000_000);
+    let j = format!("[1,{digits}]");
+    // Parsing stops at the first digit past the limit.
+    let error = de.from_str::<Vec<f64>>(&j).unwrap_err();
+    assert_eq!(error.offset(), Some("[1,".len() + 24));
+    assert!(de.from_str::<f64>(&format!("-0.{digits}")).is_err());
+    assert!(de.from_str::<f64>(&format!("1e{digits}")).is_err());
+
+    // Without a limit, the same number is read in full and is out of range.
+    assert!(json::from_str::<f64>(&digits).is_err());
+
+    let de = json::Deserializer::new().max_input_len(8);
+    assert_eq!(de.from_str::<Vec<u8>>("[1,2,3]").unwrap(), [1, 2, 3]);
+    assert!(de.from_str::<Vec<u8>>("[1,2,3,4]").is_err());
+    assert!(de.from_slice::<Vec<u8>>(b"[1,2,3,4]").is_err());
+    assert_eq!(de.stream_from_str::<u8>("1 2 3 4").count(), 4);
+    assert!(de.stream_from_str::<u8>("1 2 3 4 5").next().unwrap().is_err());
+}
+
+#[test]
+fn test_invalid_surrogate() {
+    use miniserde::ErrorKind;
+
+    let cases = [
+        r#""\uDC00""#,
+        r#""a\uDFFFb""#,
+        r#""\uD800""#,
+        r#""\uD800x""#,
+        r#""\uD800\n""#,
+        r#""\uD800A""#,
+        r#""\uDBFF\uD800""#,
+    ];
+    for j in cases {
+        let error = json::from_str::<String>(j).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidSurrogate, "{j}");
+    }
+
+    // A valid pair, and failures unrelated to surrogates.
+    assert_eq!(json::from_str::<String>(r#""\uD83E\uDD80""#).unwrap(), "🦀");
+    for j in [r#""\uD8""#, r#""\x""#, "[1,]"] {
+        let error = json::from_str::<Value>(j).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Other, "{j}");
+    }
+
+    let error = json::from_str::<String>(r#""\uDC00""#).unwrap_err();
+    assert_eq!(error.to_string(), "miniserde error: unpaired UTF-16 surrogate at offset 7");
+}
+
+#[test]
+fn test_malformed_numbers() {
+    use miniserde::ErrorKind;
+
+    let cases = [
+        ("+1", 0),
+        (".5", 0),
+        ("-.5", 2),
+        ("1.", 2),
+        ("1.e5", 2),
+        ("01", 1),
+        ("-01", 2),
+        ("00", 1),
+        ("1e", 2),
+        ("1e+", 3),
+        ("1E-", 3),
+        ("-", 1),
+        ("-a", 2),
+        ("- 1", 2),
+        ("-Infinity", 2),
+        ("[1,+2]", 3),
+        (r#"{"a":.5}"#, 5),
+    ];
+    for (j, offset) in cases {
+        let error = json::from_str::<Value>(j).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidNumber, "{j}");
+        assert_eq!(error.offset(), Some(offset), "{j}");
+        assert!(json::from_str::<f64>(j).is_err(), "{j}");
+    }
+
+    // Well-formed numbers followed by something else are not malformed
+    // numbers themselves.
+    for j in ["1.5.3", "1e5e5", "0x10", "1_000"] {
+        assert_eq!(json::from_str::<Value>(j).unwrap_err().kind(), ErrorKind::Other, "{j}");
+    }
+
+    let error = json::from_str::<u8>("+1").unwrap_err();
+    assert_eq!(error.to_string(), "miniserde error: malformed number at offset 0");
+}
+
+#[test]
+fn test_integer_overflow() {
+    use miniserde::ErrorKind;
+
+    assert_eq!(json::from_str::<u64>("18446744073709551615").unwrap(), u64::MAX);
+    let error = json::from_str::<u64>("18446744073709551616").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::IntegerOverflow);
+    assert_eq!(error.to_string(), "miniserde error: integer out of range at offset 20");
+
+    assert_eq!(json::from_str::<i64>("-9223372036854775808").unwrap(), i64::MIN);
+    for j in ["-9223372036854775809", "9223372036854775808"] {
+        assert_eq!(json::from_str::<i64>(j).unwrap_err().kind(), ErrorKind::IntegerOverflow, "{j}");
+    }
+    for j in ["256", "-1", "1e3", "300.0"] {
+        assert_eq!(json::from_str::<u8>(j).unwrap_err().kind(), ErrorKind::IntegerOverflow, "{j}");
+    }
+
+    // Floats in range, or with a fraction, are rejected for being floats.
+    for j in ["1.5", "255.0", "-0.5", "1e400"] {
+        assert_eq!(json::from_str::<u8>(j).unwrap_err().kind(), ErrorKind::Other, "{j}");
+    }
+}
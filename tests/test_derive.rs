@@ -1,6 +1,9 @@
-#![allow(clippy::derive_partial_eq_without_eq)]
+#![allow(clippy::derive_partial_eq_without_eq, clippy::option_option)]
 
 use miniserde::{json, Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
 
 #[derive(PartialEq, Debug, Serialize, Deserialize)]
 enum Tag {
@@ -87,6 +90,68 @@ fn test_default_field_present() {
     assert_eq!(actual, expected);
 }
 
+#[derive(PartialEq, Debug, Deserialize)]
+struct OptionDefault {
+    #[serde(default = "default_retries")]
+    retries: Option<u32>,
+}
+
+#[allow(clippy::unnecessary_wraps)]
+const fn default_retries() -> Option<u32> {
+    Some(3)
+}
+
+#[test]
+fn test_option_field_custom_default() {
+    let missing: OptionDefault = json::from_str("{}").unwrap();
+    assert_eq!(missing.retries, Some(3));
+    let null: OptionDefault = json::from_str(r#"{"retries": null}"#).unwrap();
+    assert_eq!(null.retries, None);
+    let present: OptionDefault = json::from_str(r#"{"retries": 5}"#).unwrap();
+    assert_eq!(present.retries, Some(5));
+
+    let strict = json::Deserializer::new().require_all_fields(true);
+    assert!(strict.from_str::<OptionDefault>("{}").is_err());
+    assert_eq!(strict.from_str::<OptionDefault>(r#"{"retries": null}"#).unwrap().retries, None);
+}
+
+#[test]
+fn test_require_all_fields() {
+    let strict = json::Deserializer::new().require_all_fields(true);
+
+    let j = r#"{"required": "test", "with_default": 42, "optional": null}"#;
+    let actual: DefaultExample = json::from_str(j).unwrap();
+    assert_eq!(actual.with_custom_default, "default_value");
+    assert!(strict.from_str::<DefaultExample>(j).is_err());
+
+    let j = r#"{"required": "test", "with_default": 42, "with_custom_default": "custom", "optional": null}"#;
+    let actual: DefaultExample = strict.from_str(j).unwrap();
+    assert_eq!(actual.with_custom_default, "custom");
+
+    let j = r#"{"name": "partial"}"#;
+    json::from_str::<ContainerDefaultExample>(j).unwrap();
+    assert!(strict.from_str::<ContainerDefaultExample>(j).is_err());
+
+    let j = r#"[{"name": "complete", "value": 123, "enabled": false}, {"value": 1}]"#;
+    assert!(strict.from_str::<Vec<ContainerDefaultExample>>(j).is_err());
+
+    // The fields of a tagged variant are deserialized after the whole object
+    // is buffered, still with the caller's settings.
+    let j = r#"{"type": "move", "x": 1, "y": 2}"#;
+    json::from_str::<Event>(j).unwrap();
+    assert!(strict.from_str::<Event>(j).is_err());
+    let j = r#"{"type": "move", "x": 1, "y": 2, "label": null}"#;
+    strict.from_str::<Event>(j).unwrap();
+
+    let j = r#"{"name": "partial"}"#;
+    assert!(strict.from_str::<Box<ContainerDefaultExample>>(j).is_err());
+    let value: json::Value = json::from_str(j).unwrap();
+    json::from_value::<ContainerDefaultExample>(value.clone()).unwrap();
+    assert!(strict.from_value::<ContainerDefaultExample>(value).is_err());
+    let value: json::Value = json::from_str(&format!("[{j}]")).unwrap();
+    assert!(strict.from_value::<Vec<ContainerDefaultExample>>(value).is_err());
+}
+
 #[derive(PartialEq, Debug, Serialize, Deserialize)]
 #[serde(default)]
 struct ContainerDefaultExample {
@@ -97,7 +162,7 @@ struct ContainerDefaultExample {
 
 impl Default for ContainerDefaultExample {
     fn default() -> Self {
-        ContainerDefaultExample {
+        Self {
             name: "container_default".to_string(),
             value: 999,
             enabled: true,
@@ -154,6 +219,134 @@ fn test_container_custom_default_missing() {
     assert_eq!(actual, expected);
 }
 
+#[derive(PartialEq, Debug, Deserialize)]
+struct Config {
+    host: String,
+    #[serde(default = "Config::default_port")]
+    port: u16,
+    #[serde(default = "Config::DEFAULT_RETRIES")]
+    retries: u32,
+    #[serde(default = "DEFAULT_TIMEOUT")]
+    timeout: u64,
+}
+
+const DEFAULT_TIMEOUT: u64 = 30;
+
+impl Config {
+    const DEFAULT_RETRIES: u32 = 3;
+
+    const fn default_port() -> u16 {
+        8080
+    }
+}
+
+#[derive(PartialEq, Debug, Deserialize)]
+#[serde(default = "Limits::DEFAULT")]
+struct Limits {
+    depth: u32,
+    width: u32,
+}
+
+impl Limits {
+    const DEFAULT: Self = Self { depth: 8, width: 64 };
+}
+
+#[test]
+fn test_default_assoc_fn_and_const() {
+    let actual: Config = json::from_str(r#"{"host": "localhost"}"#).unwrap();
+    let expected = Config {
+        host: "localhost".to_owned(),
+        port: 8080,
+        retries: 3,
+        timeout: 30,
+    };
+    assert_eq!(actual, expected);
+
+    let actual: Config = json::from_str(r#"{"host": "h", "port": 1, "retries": 0}"#).unwrap();
+    assert_eq!((actual.port, actual.retries, actual.timeout), (1, 0, 30));
+
+    let actual: Limits = json::from_str(r#"{"width": 10}"#).unwrap();
+    assert_eq!(actual, Limits { depth: 8, width: 10 });
+}
+
+#[test]
+fn test_default_path_any_case() {
+    #[allow(non_upper_case_globals)]
+    const default_id: u32 = 7;
+
+    #[allow(non_snake_case)]
+    fn LEVEL() -> u8 {
+        3
+    }
+
+    #[derive(PartialEq, Debug, Deserialize)]
+    struct User {
+        #[serde(default = "default_id")]
+        id: u32,
+        #[serde(default = "LEVEL")]
+        level: u8,
+        #[serde(default = "Vec::new")]
+        roles: Vec<String>,
+    }
+
+    let actual: User = json::from_str("{}").unwrap();
+    let expected = User {
+        id: 7,
+        level: 3,
+        roles: Vec::new(),
+    };
+    assert_eq!(actual, expected);
+}
+
+#[derive(PartialEq, Debug, Deserialize)]
+struct NullDefaults {
+    #[serde(default, default_on_null)]
+    retries: u32,
+    #[serde(default = "custom_default", default_on_null)]
+    label: String,
+    #[serde(default)]
+    limit: u32,
+}
+
+#[test]
+fn test_default_on_null() {
+    let j = r#"{"retries": null, "label": null}"#;
+    let actual: NullDefaults = json::from_str(j).unwrap();
+    let expected = NullDefaults {
+        retries: 0,
+        label: "default_value".to_string(),
+        limit: 0,
+    };
+    assert_eq!(actual, expected);
+
+    let j = r#"{"retries": 3, "label": "x", "limit": 5}"#;
+    let actual: NullDefaults = json::from_str(j).unwrap();
+    assert_eq!(actual.retries, 3);
+    assert_eq!(actual.label, "x");
+
+    // Only fields that opt in accept null.
+    assert!(json::from_str::<NullDefaults>(r#"{"limit": null}"#).is_err());
+    assert!(json::from_str::<NullDefaults>(r#"{"retries": "3"}"#).is_err());
+}
+
+#[derive(PartialEq, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Strict {
+    #[serde(rename = "id")]
+    key: u32,
+    #[serde(default)]
+    note: String,
+}
+
+#[test]
+fn test_deny_unknown_fields() {
+    let strict: Strict = json::from_str(r#"{"id":1}"#).unwrap();
+    assert_eq!(strict, Strict { key: 1, note: String::new() });
+    json::from_str::<Strict>(r#"{"id":1,"note":""}"#).unwrap();
+    json::from_str::<Strict>(r#"{"id":1,"extra":null}"#).unwrap_err();
+    json::from_str::<Strict>(r#"{"key":1}"#).unwrap_err();
+}
+
 #[test]
 fn test_ser() {
     let example = Example {
@@ -171,3 +364,628 @@ fn test_ser() {
         r#"{"x":"X","t1":"A","t2":"renamedB","t3":["enum"],"struct":{"y":["Y","Y"]}}"#;
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn test_ser_references() {
+    let example = Example {
+        x: "X".to_owned(),
+        t1: Tag::A,
+        t2: Box::new(Tag::B),
+        t3: [Tag::r#enum],
+        r#struct: Box::new(Nested { y: None, z: None }),
+    };
+    let expected = json::to_string(&example);
+
+    let reference = &example;
+    assert_eq!(json::to_string(&reference), expected);
+
+    let mut example = example;
+    let mut_reference = &mut example;
+    assert_eq!(json::to_string(&mut_reference), expected);
+
+    let nested: Vec<&mut Example> = vec![&mut example];
+    assert_eq!(json::to_string(&nested), format!("[{expected}]"));
+}
+
+#[derive(Serialize)]
+struct Borrowed<'a, T> {
+    name: &'a str,
+    items: &'a [T],
+}
+
+#[derive(Serialize)]
+struct Pair<'a>(&'a str, u8);
+
+#[derive(Serialize, Deserialize)]
+struct Label<'a> {
+    text: Cow<'a, str>,
+}
+
+#[test]
+fn test_lifetimes() {
+    let name = String::from("list");
+    let borrowed = Borrowed {
+        name: &name,
+        items: &[1, 2],
+    };
+    assert_eq!(json::to_string(&borrowed), r#"{"name":"list","items":[1,2]}"#);
+    assert_eq!(json::to_string(&Pair(&name, 1)), r#"["list",1]"#);
+
+    let label: Label = json::from_str(r#"{"text":"hi"}"#).unwrap();
+    assert_eq!(label.text, "hi");
+    assert_eq!(json::to_string(&label), r#"{"text":"hi"}"#);
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Person<'a> {
+    #[serde(borrow)]
+    name: &'a str,
+    #[serde(borrow)]
+    bio: Cow<'a, str>,
+    #[serde(borrow)]
+    nickname: Option<&'a str>,
+    #[serde(borrow)]
+    pet: Option<Pet<'a>>,
+    age: u8,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Pet<'a> {
+    #[serde(borrow)]
+    name: &'a str,
+}
+
+#[test]
+fn test_borrow() {
+    let j = r#"{"name":"Ann","bio":"line\nbreak","pet":{"name":"Rex"},"age":30}"#;
+    let person: Person = json::from_str_borrowed(j).unwrap();
+    assert_eq!(
+        person,
+        Person {
+            name: "Ann",
+            bio: Cow::Owned("line\nbreak".to_owned()),
+            nickname: None,
+            pet: Some(Pet { name: "Rex" }),
+            age: 30,
+        },
+    );
+    assert!(j.as_bytes().as_ptr_range().contains(&person.name.as_ptr()));
+    assert!(matches!(person.bio, Cow::Owned(_)));
+
+    let j = r#"{"name":"Bo","bio":"plain","nickname":"B","pet":null,"age":4}"#;
+    let person: Person = json::from_str_borrowed(j).unwrap();
+    assert!(matches!(person.bio, Cow::Borrowed("plain")));
+    assert_eq!(person.nickname, Some("B"));
+    assert_eq!(person.pet, None);
+    assert_eq!(json::to_string(&person), j);
+
+    // A &str cannot hold a string that had to be unescaped.
+    let j = r#"{"name":"A\u006En","bio":"","age":30}"#;
+    assert!(json::from_str_borrowed::<Person>(j).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_enum_keys() {
+    use std::collections::HashMap;
+
+    #[derive(PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+    #[serde(map_key)]
+    enum Suit {
+        #[serde(rename = "hearts")]
+        Hearts,
+        #[serde(rename = "spades")]
+        Spades,
+        #[serde(default)]
+        Unknown,
+    }
+
+    let mut counts = HashMap::new();
+    counts.insert(Suit::Hearts, 13);
+    let j = json::to_string(&counts);
+    assert_eq!(j, r#"{"hearts":13}"#);
+    assert_eq!(json::from_str::<HashMap<Suit, u32>>(&j).unwrap(), counts);
+
+    let j = r#"{"spades":1,"hearts":2}"#;
+    let counts: BTreeMap<bool, HashMap<Suit, u32>> =
+        json::from_str(&format!(r#"{{"true":{j},"false":{{}}}}"#)).unwrap();
+    assert_eq!(counts[&true][&Suit::Spades], 1);
+    assert!(counts[&false].is_empty());
+    assert_eq!(json::to_string(&counts[&false]), "{}");
+
+    // The default variant catches unknown values but not unknown keys, which
+    // would otherwise overwrite each other.
+    assert_eq!(json::from_str::<Suit>(r#""clubs""#).unwrap(), Suit::Unknown);
+    assert!(json::from_str::<HashMap<Suit, u32>>(r#"{"clubs":1}"#).is_err());
+    assert!(json::from_str::<HashMap<Suit, u32>>(r#"{"clubs":1,"diamonds":2}"#).is_err());
+    assert_eq!("spades".parse::<Suit>().unwrap(), Suit::Spades);
+    assert!("Unknown".parse::<Suit>().is_ok());
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+#[serde(name_or_code)]
+enum Color {
+    Red = 1,
+    Green = 2,
+    Blue = 4,
+    Unknown = -1,
+}
+
+#[test]
+fn test_name_or_code() {
+    assert_eq!(json::from_str::<Color>(r#""Green""#).unwrap(), Color::Green);
+    assert_eq!(json::from_str::<Color>("2").unwrap(), Color::Green);
+    assert_eq!(json::from_str::<Color>("-1").unwrap(), Color::Unknown);
+    assert_eq!(
+        json::from_str::<Vec<Color>>(r#"[1, "Blue", 4, "Red"]"#).unwrap(),
+        [Color::Red, Color::Blue, Color::Blue, Color::Red],
+    );
+    assert!(json::from_str::<Color>("3").is_err());
+    assert!(json::from_str::<Color>(r#""2""#).is_err());
+    assert!(json::from_str::<Tag>("0").is_err());
+
+    assert_eq!(json::to_string(&Color::Green), r#""Green""#);
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+#[serde(name_or_code)]
+#[repr(u8)]
+enum Priority {
+    Low,
+    Normal,
+    High,
+}
+
+#[test]
+fn test_name_or_code_implicit() {
+    assert_eq!(json::from_str::<Priority>("2").unwrap(), Priority::High);
+    assert_eq!(json::from_str::<Priority>("0").unwrap(), Priority::Low);
+    assert_eq!(json::from_str::<Priority>(r#""Normal""#).unwrap(), Priority::Normal);
+    assert!(json::from_str::<Priority>("3").is_err());
+    assert!(json::from_str::<Priority>("-1").is_err());
+    assert!(json::from_str::<Priority>("256").is_err());
+    assert_eq!(json::to_string(&Priority::High), r#""High""#);
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum Event {
+    #[serde(rename = "login")]
+    Login { user: String, admin: bool },
+    #[serde(rename = "move")]
+    Move {
+        x: i32,
+        y: i32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+    },
+    #[serde(rename = "logout")]
+    Logout,
+}
+
+#[test]
+fn test_internally_tagged() {
+    let j = r#"[
+        {"type": "login", "user": "ada", "admin": true},
+        {"x": 1, "type": "move", "y": -2},
+        {"type": "logout"},
+        {"type": "move", "x": 0, "y": 0, "label": "origin"}
+    ]"#;
+    let events: Vec<Event> = json::from_str(j).unwrap();
+    let expected = vec![
+        Event::Login {
+            user: "ada".to_owned(),
+            admin: true,
+        },
+        Event::Move {
+            x: 1,
+            y: -2,
+            label: None,
+        },
+        Event::Logout,
+        Event::Move {
+            x: 0,
+            y: 0,
+            label: Some("origin".to_owned()),
+        },
+    ];
+    assert_eq!(events, expected);
+
+    let j = json::to_string(&events);
+    assert_eq!(
+        j,
+        r#"[{"type":"login","user":"ada","admin":true},{"type":"move","x":1,"y":-2},{"type":"logout"},{"type":"move","x":0,"y":0,"label":"origin"}]"#,
+    );
+    assert_eq!(json::from_str::<Vec<Event>>(&j).unwrap(), expected);
+
+    for j in [
+        r#"[{"user": "ada", "admin": true}]"#,
+        r#"[{"type": "unknown"}]"#,
+        r#"[{"type": 1}]"#,
+        r#"[{"type": "move", "x": 1}]"#,
+        r#"["logout"]"#,
+    ] {
+        assert!(json::from_str::<Vec<Event>>(j).is_err(), "{j}");
+    }
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+enum Item {
+    Empty,
+    Count(u32),
+    #[serde(rename = "label")]
+    Label(String),
+    Marker(()),
+    Maybe(Option<u8>),
+}
+
+#[derive(PartialEq, Debug, Deserialize)]
+enum Lenient {
+    Count(u32),
+    #[serde(default)]
+    Unknown,
+}
+
+#[test]
+fn test_unit_and_data_variants() {
+    let shapes = vec![
+        Item::Empty,
+        Item::Count(1),
+        Item::Label("x".to_owned()),
+        Item::Marker(()),
+        Item::Maybe(None),
+        Item::Maybe(Some(2)),
+    ];
+    let j = json::to_string(&shapes);
+    assert_eq!(
+        j,
+        r#"["Empty",{"Count":1},{"label":"x"},{"Marker":null},{"Maybe":null},{"Maybe":2}]"#,
+    );
+    assert_eq!(json::from_str::<Vec<Item>>(&j).unwrap(), shapes);
+
+    for j in [
+        r#""Count""#,
+        r#"{"Empty":null}"#,
+        r#"{"Count":1,"Count":2}"#,
+        r#"{"Count":"1"}"#,
+        r#"{"Unknown":1}"#,
+        "{}",
+        "1",
+    ] {
+        assert!(json::from_str::<Item>(j).is_err(), "{j}");
+    }
+
+    assert_eq!(json::from_str::<Lenient>(r#"{"Count":1}"#).unwrap(), Lenient::Count(1));
+    assert_eq!(json::from_str::<Lenient>(r#"{"New":[1]}"#).unwrap(), Lenient::Unknown);
+    assert_eq!(json::from_str::<Lenient>(r#""New""#).unwrap(), Lenient::Unknown);
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Marker;
+
+#[test]
+fn test_unit_struct() {
+    assert_eq!(json::to_string(&Marker), "null");
+    assert_eq!(json::from_str::<Marker>("null").unwrap(), Marker);
+    assert!(json::from_str::<Marker>("{}").is_err());
+
+    assert_eq!(json::to_string(&()), "null");
+    json::from_str::<()>("null").unwrap();
+    assert!(json::from_str::<()>("0").is_err());
+    assert!(json::from_str::<()>("[]").is_err());
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Rgb(u8, u8, String);
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Wrapper<T>(T, T);
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Id(u32);
+
+#[test]
+fn test_tuple_struct() {
+    let color = Rgb(255, 0, "red".to_owned());
+    let j = r#"[255,0,"red"]"#;
+    assert_eq!(json::to_string(&color), j);
+    assert_eq!(json::from_str::<Rgb>(j).unwrap(), color);
+
+    let pair = Wrapper(vec![1], vec![]);
+    assert_eq!(json::to_string(&pair), "[[1],[]]");
+    assert_eq!(json::from_str::<Wrapper<Vec<u8>>>("[[1],[]]").unwrap(), pair);
+
+    for j in ["[255,0]", r#"[255,0,"red",1]"#, r#"{"0":255}"#, "null"] {
+        assert!(json::from_str::<Rgb>(j).is_err(), "{j}");
+    }
+
+    // Unlike in Serde, a newtype struct is not transparent.
+    assert_eq!(json::to_string(&Id(1)), "[1]");
+    assert_eq!(json::from_str::<Id>("[1]").unwrap(), Id(1));
+    assert!(json::from_str::<Id>("1").is_err());
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Patch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nickname: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    age: Option<u8>,
+}
+
+#[test]
+fn test_double_option() {
+    let missing: Patch = json::from_str("{}").unwrap();
+    assert_eq!(missing, Patch { nickname: None, age: None });
+
+    let null: Patch = json::from_str(r#"{"nickname":null,"age":null}"#).unwrap();
+    assert_eq!(null, Patch { nickname: Some(None), age: None });
+
+    let present: Patch = json::from_str(r#"{"nickname":"Al","age":30}"#).unwrap();
+    let expected = Patch {
+        nickname: Some(Some("Al".to_owned())),
+        age: Some(30),
+    };
+    assert_eq!(present, expected);
+
+    assert_eq!(json::to_string(&missing), "{}");
+    assert_eq!(json::to_string(&null), r#"{"nickname":null}"#);
+    assert_eq!(json::to_string(&present), r#"{"nickname":"Al","age":30}"#);
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+enum Channel {
+    Stable,
+    Beta,
+    #[serde(default)]
+    Unknown,
+}
+
+#[derive(PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+enum Shape {
+    Circle { radius: f64 },
+    #[default]
+    Other,
+}
+
+#[test]
+fn test_default_variant() {
+    let channel: Channel = json::from_str(r#""Beta""#).unwrap();
+    assert_eq!(channel, Channel::Beta);
+    let channel: Channel = json::from_str(r#""Nightly""#).unwrap();
+    assert_eq!(channel, Channel::Unknown);
+    assert!(json::from_str::<Channel>("1").is_err());
+
+    let shape: Shape = json::from_str(r#"{"kind":"Square","side":1}"#).unwrap();
+    assert_eq!(shape, Shape::Other);
+    let shape: Shape = json::from_str(r#"{"kind":"Circle","radius":1}"#).unwrap();
+    assert_eq!(shape, Shape::Circle { radius: 1.0 });
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum Record {
+    #[serde(rename_all = "camelCase")]
+    User { user_name: String, last_login: u64 },
+    #[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+    Group {
+        group_name: String,
+        #[serde(rename = "members")]
+        member_count: u32,
+    },
+}
+
+#[test]
+fn test_variant_rename_all() {
+    let user = Record::User {
+        user_name: "ann".to_owned(),
+        last_login: 7,
+    };
+    let j = r#"{"type":"User","userName":"ann","lastLogin":7}"#;
+    assert_eq!(json::to_string(&user), j);
+    assert_eq!(json::from_str::<Record>(j).unwrap(), user);
+
+    let group = Record::Group {
+        group_name: "ops".to_owned(),
+        member_count: 3,
+    };
+    let j = r#"{"type":"Group","GROUP-NAME":"ops","members":3}"#;
+    assert_eq!(json::to_string(&group), j);
+    assert_eq!(json::from_str::<Record>(j).unwrap(), group);
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all_fields = "camelCase")]
+enum Session {
+    Login { user_id: u64, remote_addr: String },
+    Logout {
+        user_id: u64,
+        #[serde(rename = "why")]
+        logout_reason: String,
+    },
+    #[serde(rename_all = "kebab-case")]
+    Ping { sent_at: u64 },
+}
+
+#[test]
+fn test_rename_all_fields() {
+    let login = Session::Login {
+        user_id: 1,
+        remote_addr: "::1".to_owned(),
+    };
+    let j = r#"{"type":"Login","userId":1,"remoteAddr":"::1"}"#;
+    assert_eq!(json::to_string(&login), j);
+    assert_eq!(json::from_str::<Session>(j).unwrap(), login);
+
+    let logout = Session::Logout {
+        user_id: 1,
+        logout_reason: "idle".to_owned(),
+    };
+    let j = r#"{"type":"Logout","userId":1,"why":"idle"}"#;
+    assert_eq!(json::to_string(&logout), j);
+    assert_eq!(json::from_str::<Session>(j).unwrap(), logout);
+
+    // A variant's own rename_all takes precedence.
+    let ping = Session::Ping { sent_at: 9 };
+    let j = r#"{"type":"Ping","sent-at":9}"#;
+    assert_eq!(json::to_string(&ping), j);
+    assert_eq!(json::from_str::<Session>(j).unwrap(), ping);
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Transfer {
+    #[serde(rename(serialize = "out", deserialize = "in"))]
+    amount: u64,
+    #[serde(rename(deserialize = "src"))]
+    source: String,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all_fields = "camelCase")]
+enum Notice {
+    Sent {
+        #[serde(rename(serialize = "to"))]
+        recipient_id: u64,
+    },
+}
+
+#[test]
+fn test_rename_serialize_deserialize() {
+    let transfer = Transfer {
+        amount: 5,
+        source: "a".to_owned(),
+    };
+    assert_eq!(json::to_string(&transfer), r#"{"out":5,"source":"a"}"#);
+    let j = r#"{"in":5,"src":"a"}"#;
+    assert_eq!(json::from_str::<Transfer>(j).unwrap(), transfer);
+    assert!(json::from_str::<Transfer>(r#"{"out":5,"source":"a"}"#).is_err());
+
+    // The other direction still follows the variant's casing rule.
+    let sent = Notice::Sent { recipient_id: 7 };
+    assert_eq!(json::to_string(&sent), r#"{"type":"Sent","to":7}"#);
+    let j = r#"{"type":"Sent","recipientId":7}"#;
+    assert_eq!(json::from_str::<Notice>(j).unwrap(), sent);
+}
+
+const fn is_empty_vec(tags: &[String]) -> bool {
+    tags.is_empty()
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+const fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Post {
+    #[serde(skip_serializing_if = "is_empty_vec")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    title: String,
+    #[serde(skip_serializing_if = "is_zero")]
+    likes: u32,
+}
+
+#[test]
+fn test_skip_serializing_if_predicates() {
+    let post = Post {
+        tags: Vec::new(),
+        title: String::new(),
+        likes: 0,
+    };
+    assert_eq!(json::to_string(&post), "{}");
+
+    let post = Post {
+        tags: vec!["rust".to_owned()],
+        title: "Hi".to_owned(),
+        likes: 2,
+    };
+    assert_eq!(json::to_string(&post), r#"{"tags":["rust"],"title":"Hi","likes":2}"#);
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Profile {
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    emails: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    links: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    bio: Option<String>,
+}
+
+#[test]
+fn test_skip_serializing_if_methods() {
+    let profile = Profile {
+        emails: Vec::new(),
+        links: BTreeMap::new(),
+        bio: None,
+    };
+    assert_eq!(json::to_string(&profile), "{}");
+    assert_eq!(json::from_str::<Profile>("{}").unwrap(), profile);
+
+    let profile = Profile {
+        emails: vec!["a@b.c".to_owned()],
+        links: BTreeMap::from([("web".to_owned(), "x.y".to_owned())]),
+        bio: Some(String::new()),
+    };
+    let j = r#"{"emails":["a@b.c"],"links":{"web":"x.y"},"bio":""}"#;
+    assert_eq!(json::to_string(&profile), j);
+    assert_eq!(json::from_str::<Profile>(j).unwrap(), profile);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_skip_serializing_if_hash_map() {
+    use std::collections::HashMap;
+
+    #[derive(PartialEq, Debug, Serialize, Deserialize)]
+    struct Headers {
+        #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+        extra: HashMap<String, String>,
+    }
+
+    let headers = Headers {
+        extra: HashMap::new(),
+    };
+    assert_eq!(json::to_string(&headers), "{}");
+    assert_eq!(json::from_str::<Headers>("{}").unwrap(), headers);
+
+    let headers = Headers {
+        extra: HashMap::from([("accept".to_owned(), "*/*".to_owned())]),
+    };
+    let j = r#"{"extra":{"accept":"*/*"}}"#;
+    assert_eq!(json::to_string(&headers), j);
+    assert_eq!(json::from_str::<Headers>(j).unwrap(), headers);
+}
+
+#[test]
+fn test_recursive_option_box() {
+    #[derive(Serialize, Deserialize)]
+    #[allow(clippy::use_self)]
+    struct Node {
+        value: i32,
+        next: Option<Box<Node>>,
+    }
+
+    const DEPTH: i32 = 10_000;
+    let mut j = String::new();
+    for i in 0..DEPTH {
+        write!(j, r#"{{"value":{i},"next":"#).unwrap();
+    }
+    j += "null";
+    j += &"}".repeat(DEPTH as usize);
+
+    let head: Node = json::from_str(&j).unwrap();
+    let mut len = 0;
+    let mut node = Some(&head);
+    while let Some(n) = node {
+        assert_eq!(n.value, len);
+        len += 1;
+        node = n.next.as_deref();
+    }
+    assert_eq!(len, DEPTH);
+
+    assert_eq!(json::to_string(&head), j);
+    assert!(json::from_str::<Node>(&j[..j.len() - 1]).is_err());
+}
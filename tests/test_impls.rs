@@ -1,4 +1,10 @@
 use miniserde::json;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, LinkedList, VecDeque};
+use std::num::{Saturating, Wrapping};
+use std::ops::{Range, RangeInclusive};
+use std::rc::Rc;
+use std::sync::Arc;
 
 #[test]
 fn test_array() {
@@ -19,3 +25,210 @@ fn test_array_too_long() {
     let j = r#"["1","2","3","4"]"#;
     json::from_str::<[String; 3]>(j).unwrap_err();
 }
+
+#[test]
+fn test_byte_array() {
+    let bytes = *b"0123456789abcdef0123456789abcdef";
+    let j = json::to_string(&bytes);
+    assert_eq!(json::from_str::<[u8; 32]>(&j).unwrap(), bytes);
+
+    let short = json::to_string(&bytes[..31]);
+    json::from_str::<[u8; 32]>(&short).unwrap_err();
+
+    let empty: [u8; 0] = json::from_str("[]").unwrap();
+    assert_eq!(empty, [0u8; 0]);
+    json::from_str::<[u8; 0]>("[0]").unwrap_err();
+}
+
+#[test]
+fn test_btreemap_integer_keys() {
+    let j = r#"{"10":"a","2":"b"}"#;
+    let map: BTreeMap<u64, String> = json::from_str(j).unwrap();
+    let keys: Vec<u64> = map.keys().copied().collect();
+    assert_eq!(keys, [2, 10]);
+    assert_eq!(map[&2], "b");
+    assert_eq!(map[&10], "a");
+    assert_eq!(json::to_string(&map), r#"{"2":"b","10":"a"}"#);
+
+    json::from_str::<BTreeMap<u64, String>>(r#"{"x":"a"}"#).unwrap_err();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_hashmap_integer_keys() {
+    use std::collections::HashMap;
+
+    let mut map = HashMap::new();
+    map.insert(7u32, "seven".to_owned());
+    map.insert(4_000_000_000, "big".to_owned());
+
+    let j = json::to_string(&map);
+    assert!(j == r#"{"7":"seven","4000000000":"big"}"# || j == r#"{"4000000000":"big","7":"seven"}"#);
+    assert_eq!(json::from_str::<HashMap<u32, String>>(&j).unwrap(), map);
+
+    json::from_str::<HashMap<u32, String>>(r#"{"-1":"a"}"#).unwrap_err();
+    json::from_str::<HashMap<u32, String>>(r#"{"4294967296":"a"}"#).unwrap_err();
+}
+
+#[test]
+fn test_boxed_slices() {
+    let j = "[1,2,3]";
+
+    let boxed: Box<[u8]> = json::from_str(j).unwrap();
+    assert_eq!(*boxed, [1, 2, 3]);
+    assert_eq!(json::to_string(&boxed), j);
+
+    let rc: Rc<[u8]> = json::from_str(j).unwrap();
+    assert_eq!(*rc, [1, 2, 3]);
+    assert_eq!(json::to_string(&rc), j);
+
+    let arc: Arc<[u8]> = json::from_str(j).unwrap();
+    assert_eq!(*arc, [1, 2, 3]);
+    assert_eq!(json::to_string(&arc), j);
+
+    let empty: Box<[String]> = json::from_str("[]").unwrap();
+    assert!(empty.is_empty());
+    let nested: Vec<Rc<[Box<[u8]>]>> = json::from_str("[[[1],[]],[]]").unwrap();
+    assert_eq!(json::to_string(&nested), "[[[1],[]],[]]");
+
+    assert!(json::from_str::<Box<[u8]>>("[1,256]").is_err());
+    assert!(json::from_str::<Arc<[u8]>>("{}").is_err());
+}
+
+#[test]
+fn test_boxed_str() {
+    let j = r#""café""#;
+
+    let boxed: Box<str> = json::from_str(j).unwrap();
+    assert_eq!(&*boxed, "café");
+    assert_eq!(json::to_string(&boxed), r#""café""#);
+
+    let rc: Rc<str> = json::from_str(j).unwrap();
+    assert_eq!(&*rc, "café");
+    assert_eq!(json::to_string(&rc), r#""café""#);
+
+    let arc: Arc<str> = json::from_str(j).unwrap();
+    assert_eq!(&*arc, "café");
+    assert_eq!(json::to_string(&arc), r#""café""#);
+
+    let empty: Box<str> = json::from_str(r#""""#).unwrap();
+    assert!(empty.is_empty());
+    assert!(json::from_str::<Box<str>>("[]").is_err());
+    assert!(json::from_str::<Rc<str>>("null").is_err());
+}
+
+#[test]
+fn test_ranges() {
+    let j = json::to_string(&(0..10));
+    assert_eq!(j, r#"{"start":0,"end":10}"#);
+    assert_eq!(json::from_str::<Range<i32>>(&j).unwrap(), 0..10);
+
+    let j = json::to_string(&(0..=10));
+    assert_eq!(j, r#"{"start":0,"end":10}"#);
+    assert_eq!(json::from_str::<RangeInclusive<i32>>(&j).unwrap(), 0..=10);
+
+    let range: Range<u8> = json::from_str(r#"{"end":3,"start":1,"step":2}"#).unwrap();
+    assert_eq!(range, 1..3);
+    assert!(json::from_str::<Range<u8>>(r#"{"start":1}"#).is_err());
+}
+
+#[test]
+fn test_result() {
+    type Reply = Result<Vec<u32>, String>;
+
+    let ok: Reply = Ok(vec![1, 2]);
+    let j = json::to_string(&ok);
+    assert_eq!(j, r#"{"Ok":[1,2]}"#);
+    assert_eq!(json::from_str::<Reply>(&j).unwrap(), ok);
+
+    let err: Reply = Err("denied".to_owned());
+    let j = json::to_string(&err);
+    assert_eq!(j, r#"{"Err":"denied"}"#);
+    assert_eq!(json::from_str::<Reply>(&j).unwrap(), err);
+
+    let unit: Result<(), ()> = json::from_str(r#"{"Ok":null}"#).unwrap();
+    assert_eq!(unit, Ok(()));
+
+    for j in ["{}", r#"{"Ok":[],"Err":""}"#, r#"{"ok":[]}"#, "[]", "null"] {
+        assert!(json::from_str::<Reply>(j).is_err(), "{j}");
+    }
+}
+
+#[test]
+fn test_queues() {
+    let mut deque = VecDeque::new();
+    deque.push_back(2u32);
+    deque.push_back(3);
+    deque.push_front(1);
+    let j = json::to_string(&deque);
+    assert_eq!(j, "[1,2,3]");
+    assert_eq!(json::from_str::<VecDeque<u32>>(&j).unwrap(), deque);
+
+    let list: LinkedList<String> = ["a", "b", "c"].iter().map(|s| (*s).to_owned()).collect();
+    let j = json::to_string(&list);
+    assert_eq!(j, r#"["a","b","c"]"#);
+    assert_eq!(json::from_str::<LinkedList<String>>(&j).unwrap(), list);
+
+    assert!(json::from_str::<VecDeque<u32>>("[]").unwrap().is_empty());
+    assert!(json::from_str::<LinkedList<u32>>(r#"["x"]"#).is_err());
+}
+
+#[test]
+fn test_number_wrappers() {
+    let counter = Wrapping(u64::MAX) + Wrapping(2);
+    let j = json::to_string(&counter);
+    assert_eq!(j, "1");
+    assert_eq!(json::from_str::<Wrapping<u64>>(&j).unwrap(), counter);
+
+    let j = json::to_string(&[Saturating(-5i8), Saturating(i8::MAX)]);
+    assert_eq!(j, "[-5,127]");
+    let back: Vec<Saturating<i8>> = json::from_str(&j).unwrap();
+    assert_eq!(back, [Saturating(-5), Saturating(i8::MAX)]);
+
+    assert!(json::from_str::<Wrapping<u8>>("256").is_err());
+    assert!(json::from_str::<Wrapping<u8>>(r#""1""#).is_err());
+}
+
+#[test]
+fn test_cow() {
+    let borrowed: Cow<str> = Cow::Borrowed("text");
+    assert_eq!(json::to_string(&borrowed), r#""text""#);
+
+    let owned: Cow<str> = json::from_str(r#""te\nxt""#).unwrap();
+    assert!(matches!(owned, Cow::Owned(ref s) if s == "te\nxt"));
+
+    let slice: Cow<[u8]> = Cow::Borrowed(&[1, 2]);
+    assert_eq!(json::to_string(&slice), "[1,2]");
+    let slice: Cow<'static, [u8]> = json::from_str("[1,2]").unwrap();
+    assert!(matches!(slice, Cow::Owned(ref v) if v == &[1, 2]));
+
+    let map: Cow<BTreeMap<String, u8>> = json::from_str(r#"{"a":1}"#).unwrap();
+    assert_eq!(map["a"], 1);
+
+    let nested: Vec<Cow<str>> = json::from_str(r#"["a","b"]"#).unwrap();
+    assert_eq!(nested, ["a", "b"]);
+    json::from_str::<Cow<str>>("1").unwrap_err();
+}
+
+// Runs without the std feature too, where the impls come from core::net.
+#[cfg(any(not(no_core_net), feature = "std"))]
+#[test]
+fn test_ip_addr() {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    let v4 = Ipv4Addr::new(192, 168, 0, 1);
+    let v6 = Ipv6Addr::LOCALHOST;
+    assert_eq!(json::to_string(&v4), r#""192.168.0.1""#);
+    assert_eq!(json::to_string(&v6), r#""::1""#);
+    assert_eq!(json::to_string(&IpAddr::V6(v6)), r#""::1""#);
+
+    assert_eq!(json::from_str::<Ipv4Addr>(r#""192.168.0.1""#).unwrap(), v4);
+    assert_eq!(json::from_str::<Ipv6Addr>(r#""::1""#).unwrap(), v6);
+    assert_eq!(json::from_str::<IpAddr>(r#""192.168.0.1""#).unwrap(), IpAddr::V4(v4));
+    assert_eq!(json::from_str::<IpAddr>(r#""::1""#).unwrap(), IpAddr::V6(v6));
+
+    json::from_str::<Ipv4Addr>(r#""::1""#).unwrap_err();
+    json::from_str::<Ipv6Addr>(r#""192.168.0.1""#).unwrap_err();
+    json::from_str::<IpAddr>(r#""localhost""#).unwrap_err();
+    json::from_str::<IpAddr>("2130706433").unwrap_err();
+}
@@ -1,4 +1,7 @@
-use miniserde::json;
+#![allow(clippy::float_cmp)]
+
+use miniserde::json::{Number, Value};
+use miniserde::{json, Serialize};
 use std::f64;
 
 #[test]
@@ -16,3 +19,77 @@ fn test_ser() {
         assert_eq!(actual, *expected);
     }
 }
+
+#[derive(Serialize)]
+struct Measurement {
+    value: f64,
+}
+
+#[test]
+fn test_ser_non_finite_policy() {
+    let measurement = Measurement { value: f64::NAN };
+
+    let lossy = json::Serializer::new().to_string(&measurement).unwrap();
+    assert_eq!(lossy, r#"{"value":null}"#);
+    assert_eq!(lossy, json::to_string(&measurement));
+
+    let strict = json::Serializer::new().reject_non_finite(true);
+    strict.to_string(&measurement).unwrap_err();
+    strict.to_vec(&measurement).unwrap_err();
+
+    let finite = Measurement { value: 1.5 };
+    assert_eq!(strict.to_string(&finite).unwrap(), r#"{"value":1.5}"#);
+}
+
+#[test]
+fn test_from() {
+    assert!(matches!(Number::from(7u8), Number::U64(7)));
+    assert!(matches!(Number::from(u64::MAX), Number::U64(u64::MAX)));
+    assert!(matches!(Number::from(usize::MAX), Number::U64(n) if n == usize::MAX as u64));
+    assert!(matches!(Number::from(-7i8), Number::I64(-7)));
+    assert!(matches!(Number::from(i64::MIN), Number::I64(i64::MIN)));
+    assert!(matches!(Number::from(7i64), Number::U64(7)));
+    assert!(matches!(Number::from(0isize), Number::U64(0)));
+    assert!(matches!(Number::from(0.5f32), Number::F64(n) if n == 0.5));
+    assert!(matches!(Number::from(-1.5), Number::F64(n) if n == -1.5));
+    assert!(matches!(Number::from(f64::NAN), Number::F64(n) if n.is_nan()));
+
+    assert!(matches!(Number::from_f64(-0.0), Some(Number::F64(n)) if n == 0.0));
+    assert!(Number::from_f64(f64::NAN).is_none());
+    assert!(Number::from_f64(f64::INFINITY).is_none());
+    assert!(Number::from_f64(f64::NEG_INFINITY).is_none());
+
+    let value = Value::from(Number::from(-3));
+    assert_eq!(json::to_string(&value), "-3");
+}
+
+#[test]
+fn test_negative_zero() {
+    // `-0` is the integer zero.
+    assert!(matches!(json::from_str::<Value>("-0").unwrap(), Value::Number(Number::U64(0))));
+    assert_eq!(json::from_str::<u8>("-0").unwrap(), 0);
+    assert_eq!(json::from_str::<i64>("-0").unwrap(), 0);
+    let zero: f64 = json::from_str("-0").unwrap();
+    assert!(zero == 0.0 && zero.is_sign_positive());
+    assert_eq!(json::to_string(&json::from_str::<Value>("-0").unwrap()), "0");
+
+    // Zero with a fraction or exponent is a float and keeps its sign, however
+    // large the exponent.
+    for j in ["-0.0", "-0e0", "-0e5", "-0.0e-400", "-0e-9999", "-1e-400"] {
+        let float: f64 = json::from_str(j).unwrap();
+        assert!(float == 0.0 && float.is_sign_negative(), "{j}");
+        let value: Value = json::from_str(j).unwrap();
+        assert!(matches!(value, Value::Number(Number::F64(n)) if n.is_sign_negative()), "{j}");
+        assert_eq!(json::to_string(&value), "-0.0", "{j}");
+        assert!(json::from_str::<i64>(j).is_err(), "{j}");
+    }
+    for j in ["0.0", "0e-9999", "0e9999", "1e-400"] {
+        let float: f64 = json::from_str(j).unwrap();
+        assert!(float == 0.0 && float.is_sign_positive(), "{j}");
+    }
+
+    // Negative zero round-trips through a string.
+    let j = json::to_string(&-0.0f64);
+    assert_eq!(j, "-0.0");
+    assert!(json::from_str::<f64>(&j).unwrap().is_sign_negative());
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "portable_simd")]
+
+use miniserde::json;
+
+// The portable SIMD scans look at 32 bytes at a time. These put a special
+// character at every offset around two chunks.
+
+#[test]
+fn test_parse_string_offsets() {
+    for len in 0..100 {
+        let plain = "x".repeat(len);
+        for at in 0..=len {
+            let j = format!(r#""{}\"{}""#, &plain[..at], &plain[at..]);
+            let expected = format!("{}\"{}", &plain[..at], &plain[at..]);
+            assert_eq!(json::from_str::<String>(&j).unwrap(), expected, "{j}");
+        }
+    }
+}
+
+#[test]
+fn test_escape_string_offsets() {
+    let serializer = json::Serializer::new()
+        .ascii(true)
+        .escape_slash(true)
+        .escape_html(true);
+
+    let cases = [
+        ('"', r#"\""#, r#"\""#),
+        ('\\', r"\\", r"\\"),
+        ('\n', r"\n", r"\n"),
+        ('\u{1}', r"\u0001", r"\u0001"),
+        ('é', "é", r"\u00e9"),
+        ('/', "/", r"\/"),
+        ('<', "<", r"\u003c"),
+        ('&', "&", r"\u0026"),
+    ];
+
+    for len in 0..100 {
+        let plain = "x".repeat(len);
+        for at in 0..=len {
+            for (ch, escaped, escaped_all) in cases {
+                let string = format!("{}{ch}{}", &plain[..at], &plain[at..]);
+                let expected = format!(r#""{}{escaped}{}""#, &plain[..at], &plain[at..]);
+                assert_eq!(json::to_string(&string), expected);
+                let expected = format!(r#""{}{escaped_all}{}""#, &plain[..at], &plain[at..]);
+                assert_eq!(serializer.to_string(&string).unwrap(), expected);
+            }
+        }
+    }
+}
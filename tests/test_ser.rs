@@ -0,0 +1,329 @@
+use indoc::indoc;
+use miniserde::json::{self, Value};
+use miniserde::Serialize;
+
+#[test]
+fn test_ascii() {
+    let cases = &[
+        ("plain", r#""plain""#),
+        ("crème brûlée", r#""cr\u00e8me br\u00fbl\u00e9e""#),
+        ("漢字", r#""\u6f22\u5b57""#),
+        ("🦀", r#""\ud83e\udd80""#),
+        ("a\n\u{1f}é", r#""a\n\u001f\u00e9""#),
+    ];
+
+    for (string, expected) in cases {
+        let actual = json::to_string_ascii(string);
+        assert_eq!(actual, *expected);
+        assert!(actual.is_ascii());
+        assert_eq!(json::to_vec_ascii(string), expected.as_bytes());
+
+        let roundtrip: String = json::from_str(&actual).unwrap();
+        assert_eq!(roundtrip, *string);
+    }
+}
+
+#[test]
+fn test_utf8_passthrough() {
+    let string = "crème 漢字 🦀";
+    let actual = json::to_string(string);
+    assert_eq!(actual, format!("\"{string}\""));
+}
+
+#[test]
+fn test_escape_slash() {
+    let string = "</script>";
+    assert_eq!(json::to_string(string), r#""</script>""#);
+
+    let serializer = json::Serializer::new().escape_slash(true);
+    let actual = serializer.to_string(string).unwrap();
+    assert_eq!(actual, r#""<\/script>""#);
+    let roundtrip: String = json::from_str(&actual).unwrap();
+    assert_eq!(roundtrip, string);
+
+    let serializer = serializer.ascii(true);
+    assert_eq!(serializer.to_string("é/").unwrap(), r#""\u00e9\/""#);
+}
+
+#[test]
+fn test_escape_html() {
+    let string = "<script>&";
+    assert_eq!(json::to_string(string), r#""<script>&""#);
+
+    let serializer = json::Serializer::new().escape_html(true);
+    let actual = serializer.to_string(string).unwrap();
+    assert_eq!(actual, r#""\u003cscript\u003e\u0026""#);
+    let roundtrip: String = json::from_str(&actual).unwrap();
+    assert_eq!(roundtrip, string);
+
+    let serializer = serializer.escape_slash(true);
+    let actual = serializer.to_string("</script>").unwrap();
+    assert_eq!(actual, r#""\u003c\/script\u003e""#);
+}
+
+#[test]
+fn test_round_sig_digits() {
+    let serializer = json::Serializer::new().round_sig_digits(15);
+    let sum = 0.1 + 0.2;
+    assert_eq!(json::to_string(&sum), "0.30000000000000004");
+    assert_eq!(serializer.to_string(&sum).unwrap(), "0.3");
+    assert_eq!(serializer.to_string(&sum).unwrap(), serializer.to_string(&0.3).unwrap());
+
+    let cases = &[
+        (3, 1234.5678, "1230.0"),
+        (3, -0.000_123_456, "-0.000123"),
+        (1, 0.95, "0.9"),
+        (4, 2.0 / 3.0, "0.6667"),
+        (0, 7.7, "8.0"),
+        (17, 0.1 + 0.2, "0.30000000000000004"),
+        (100, 1e300, "1e300"),
+    ];
+    for &(digits, value, expected) in cases {
+        let serializer = json::Serializer::new().round_sig_digits(digits);
+        let actual = serializer.to_string(&vec![value; 2]).unwrap();
+        assert_eq!(actual, format!("[{expected},{expected}]"));
+    }
+}
+
+#[test]
+#[allow(clippy::approx_constant)]
+fn test_fixed_decimal_places() {
+    let serializer = json::Serializer::new().fixed_decimal_places(2);
+    assert_eq!(serializer.to_string(&3.14159).unwrap(), "3.14");
+
+    let cases = &[
+        (2, 3.0, "3.00"),
+        (2, -2.675, "-2.67"),
+        (3, 1e-9, "0.000"),
+        (0, 2.5, "2"),
+        (1, 9_999_999_999_999_998.0, "9999999999999998.0"),
+        (2, 1e16, "1e16"),
+        (2, -1.5e300, "-1.5e300"),
+    ];
+    for &(places, value, expected) in cases {
+        let serializer = json::Serializer::new().fixed_decimal_places(places);
+        let actual = serializer.to_string(&vec![value; 2]).unwrap();
+        assert_eq!(actual, format!("[{expected},{expected}]"));
+    }
+
+    // Integers and non-finite floats are unaffected.
+    assert_eq!(serializer.to_string(&(7, f64::NAN)).unwrap(), "[7,null]");
+
+    let serializer = json::Serializer::new().round_sig_digits(2).fixed_decimal_places(3);
+    assert_eq!(serializer.to_string(&1234.5678).unwrap(), "1200.000");
+
+    // With no decimal places the output is a JSON integer.
+    let serializer = json::Serializer::new().fixed_decimal_places(0);
+    let j = serializer.to_string(&3.0).unwrap();
+    assert_eq!(j, "3");
+    let value: json::Value = json::from_str(&j).unwrap();
+    assert_eq!(value.as_u64(), Some(3));
+}
+
+#[test]
+fn test_numeric_arrays() {
+    // References to numbers go through the generic per-element path.
+    fn generic<T: miniserde::Serialize>(slice: &[T]) -> String {
+        json::to_string(&slice.iter().collect::<Vec<&T>>())
+    }
+
+    let u8s = [0, 1, 9, 10, 99, 100, 255u8];
+    assert_eq!(json::to_string(&u8s), generic(&u8s));
+    let u64s = vec![0, u64::MAX, 1 << 40];
+    assert_eq!(json::to_string(&u64s), generic(&u64s));
+    let i16s = vec![i16::MIN, -1, 0, 1, i16::MAX];
+    assert_eq!(json::to_string(&i16s), generic(&i16s));
+    let isizes: &[isize] = &[-123_456, 789];
+    assert_eq!(json::to_string(isizes), generic(isizes));
+    let f32s = vec![0.1f32, -1.5, 3e38];
+    assert_eq!(json::to_string(&f32s), generic(&f32s));
+    let f64s = vec![0.0, -0.0, 1e-7, 1.5e300, f64::NAN, f64::INFINITY];
+    assert_eq!(json::to_string(&f64s), generic(&f64s));
+    let empty: Vec<i32> = Vec::new();
+    assert_eq!(json::to_string(&empty), "[]");
+    assert_eq!(json::to_string(&vec![vec![1u32, 2], vec![]]), "[[1,2],[]]");
+
+    let many: Vec<u32> = (0..10_000).map(|i| i * 7919).collect();
+    assert_eq!(json::to_string(&many), generic(&many));
+
+    let serializer = json::Serializer::new().round_sig_digits(2);
+    let floats = [1.2345, 67.89];
+    assert_eq!(serializer.to_string(&floats).unwrap(), "[1.2,68.0]");
+
+    let serializer = json::Serializer::new().reject_non_finite(true);
+    assert!(serializer.to_string(&[1.0, f64::NAN]).is_err());
+
+    let serializer = json::Serializer::new().pretty(true);
+    let j = serializer.to_string(&[1u8, 2]).unwrap();
+    assert_eq!(j, "[\n  1,\n  2\n]");
+}
+
+#[test]
+fn test_slices() {
+    let items: &[u32] = &[1, 2, 3];
+    assert_eq!(json::to_string(items), "[1,2,3]");
+    assert_eq!(json::to_string(&items[1..]), "[2,3]");
+    assert_eq!(json::to_string(&items[..0]), "[]");
+
+    let words = ["a".to_owned(), "b".to_owned()];
+    assert_eq!(json::to_string(&words[..]), r#"["a","b"]"#);
+    let nested: &[&[u8]] = &[&[1], &[]];
+    assert_eq!(json::to_string(nested), "[[1],[]]");
+}
+
+#[test]
+fn test_pretty() {
+    let value: Value = json::from_str(r#"{"a":[1,{"b":null}],"c":{},"d":[]}"#).unwrap();
+
+    let expected = indoc! {r#"
+        {
+          "a": [
+            1,
+            {
+              "b": null
+            }
+          ],
+          "c": {},
+          "d": []
+        }"#};
+    assert_eq!(json::to_string_pretty(&value), expected);
+    assert_eq!(json::to_vec_pretty(&value), expected.as_bytes());
+
+    let roundtrip: Value = json::from_str(expected).unwrap();
+    assert_eq!(json::to_string(&roundtrip), json::to_string(&value));
+}
+
+#[test]
+fn test_max_pretty_depth() {
+    let j = r#"{"outer":{"inner":{"deep":[1,2]},"list":[[3],4]},"x":true}"#;
+    let value: Value = json::from_str(j).unwrap();
+
+    let serializer = json::Serializer::new().pretty(true).max_pretty_depth(2);
+    let expected = indoc! {r#"
+        {
+          "outer": {
+            "inner": {"deep":[1,2]},
+            "list": [[3],4]
+          },
+          "x": true
+        }"#};
+    assert_eq!(serializer.to_string(&value).unwrap(), expected);
+
+    let compact = json::Serializer::new().pretty(true).max_pretty_depth(0);
+    assert_eq!(compact.to_string(&value).unwrap(), j);
+
+    let unlimited = json::Serializer::new().pretty(true).max_pretty_depth(usize::MAX);
+    assert_eq!(
+        unlimited.to_string(&value).unwrap(),
+        json::to_string_pretty(&value),
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_stream_string() {
+    use miniserde::json::StreamString;
+    use std::io::{self, Cursor, Read};
+
+    // Reads at most a few bytes at a time, to split characters across reads.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.0.len()).min(7);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    let mut string = String::new();
+    while string.len() < 1 << 20 {
+        string.push_str("plain text, \"quotes\", é, 漢字, 🦀\n\t");
+    }
+    let expected = json::to_string(&string);
+
+    let streamed = StreamString::new(Cursor::new(string.as_bytes()));
+    assert_eq!(json::to_string(&streamed), expected);
+
+    let streamed = StreamString::new(Trickle(string.as_bytes()));
+    let serializer = json::Serializer::new();
+    assert_eq!(serializer.to_string(&streamed).unwrap(), expected);
+
+    let streamed = StreamString::new(Trickle(string.as_bytes()));
+    assert_eq!(
+        json::to_string_ascii(&streamed),
+        json::to_string_ascii(&string),
+    );
+
+    let streamed = StreamString::new(string.as_bytes());
+    assert_eq!(json::to_value(&streamed).as_str(), Some(string.as_str()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_stream_string_invalid_utf8() {
+    use miniserde::json::StreamString;
+
+    let cases: [(&[u8], &str); 3] = [
+        (b"abc\xFFdef", "\"abc\u{FFFD}def\""),
+        (b"\xFF\xFEx", "\"\u{FFFD}\u{FFFD}x\""),
+        (b"truncated \xE6\xBC", "\"truncated \u{FFFD}\""),
+    ];
+    for (bytes, expected) in cases {
+        assert_eq!(json::to_string(&StreamString::new(bytes)), expected);
+        let lossy = String::from_utf8_lossy(bytes).into_owned();
+        assert_eq!(json::to_value(&StreamString::new(bytes)), Value::String(lossy));
+    }
+}
+
+#[test]
+fn test_iter() {
+    let squares = json::iter((0..1000).map(|i| i * i));
+    let expected = (0..1000).map(|i| (i * i).to_string()).collect::<Vec<_>>();
+    assert_eq!(json::to_string(&squares), format!("[{}]", expected.join(",")));
+
+    // The iterator is used up by the first serialization.
+    assert_eq!(json::to_string(&squares), "[]");
+
+    let words = ["a", "b"];
+    let nested = json::iter(words.iter().map(|w| json::iter(w.chars().map(String::from))));
+    assert_eq!(json::to_string(&nested), r#"[["a"],["b"]]"#);
+    assert_eq!(json::to_string(&json::iter(Vec::<u8>::new())), "[]");
+}
+
+#[test]
+fn test_trait_objects() {
+    let plugins: Vec<Box<dyn Serialize>> = vec![
+        Box::new(1u8),
+        Box::new("two"),
+        Box::new(vec![3.5]),
+        Box::new(None::<bool>),
+    ];
+    assert_eq!(json::to_string(&plugins), r#"[1,"two",[3.5],null]"#);
+
+    let borrowed: Vec<&dyn Serialize> = plugins.iter().map(AsRef::as_ref).collect();
+    assert_eq!(json::to_string(&borrowed), json::to_string(&plugins));
+
+    let send: Box<dyn Serialize + Send + Sync> = Box::new("x".to_owned());
+    assert_eq!(json::to_string(&send), r#""x""#);
+}
+
+#[test]
+fn test_object_serializer() {
+    let mut out = String::new();
+    let mut object = json::ObjectSerializer::new(&mut out);
+    object.field("a", &1).unwrap();
+    object.field("b", &vec![2, 3]).unwrap();
+    object.end();
+    assert_eq!(out, r#"{"a":1,"b":[2,3]}"#);
+
+    let mut out = String::from("[");
+    json::ObjectSerializer::new(&mut out).end();
+    out.push(',');
+    let serializer = json::Serializer::new().ascii(true).reject_non_finite(true).pretty(true);
+    let mut object = json::ObjectSerializer::with_config(&mut out, serializer);
+    object.field("é", &[1.5]).unwrap();
+    assert!(object.field("nan", &f64::NAN).is_err());
+    assert_eq!(out, r#"[{},{"\u00e9":[1.5],"nan":"#);
+}
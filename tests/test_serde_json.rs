@@ -0,0 +1,49 @@
+#![cfg(feature = "serde_json")]
+
+use miniserde::json::{self, Number, Value};
+
+#[test]
+fn test_round_trip() {
+    let j = r#"{"a":[1,-2,3.5,true,null,"s"],"b":{"c":{"d":[]},"e":{}},"f":18446744073709551615}"#;
+
+    let theirs: serde_json::Value = serde_json::from_str(j).unwrap();
+    let ours = Value::from(theirs.clone());
+    assert_eq!(json::to_string(&ours), j);
+    assert!(matches!(ours["a"][1], Value::Number(Number::I64(-2))));
+    assert!(matches!(ours["f"], Value::Number(Number::U64(u64::MAX))));
+
+    let back = serde_json::Value::from(ours);
+    assert_eq!(back, theirs);
+}
+
+#[test]
+fn test_scalars() {
+    assert!(matches!(Value::from(serde_json::Value::Null), Value::Null));
+    assert!(matches!(Value::from(serde_json::json!(7)), Value::Number(Number::U64(7))));
+
+    let nan = serde_json::Value::from(Value::Number(Number::F64(f64::NAN)));
+    assert_eq!(nan, serde_json::Value::Null);
+    let half = serde_json::Value::from(Value::Number(Number::F64(0.5)));
+    assert_eq!(half, serde_json::json!(0.5));
+}
+
+#[test]
+fn test_deeply_nested() {
+    let mut theirs = serde_json::Value::Null;
+    for _ in 0..100_000 {
+        theirs = serde_json::Value::Array(vec![theirs]);
+    }
+    let ours = Value::from(theirs);
+    let back = serde_json::Value::from(ours);
+
+    let mut depth = 0;
+    let mut current = &back;
+    while let serde_json::Value::Array(vec) = current {
+        current = &vec[0];
+        depth += 1;
+    }
+    assert_eq!(depth, 100_000);
+
+    // Dropping serde_json::Value recurses, and would overflow the stack.
+    std::mem::forget(back);
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "time")]
+
+use miniserde::{json, Deserialize, Serialize};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Event {
+    at: OffsetDateTime,
+}
+
+#[test]
+fn test_rfc3339_round_trip() {
+    let j = r#"{"at":"1985-04-12T23:20:50.52Z"}"#;
+    let event: Event = json::from_str(j).unwrap();
+    let date = Date::from_calendar_date(1985, Month::April, 12).unwrap();
+    let time = Time::from_hms_milli(23, 20, 50, 520).unwrap();
+    assert_eq!(event.at, PrimitiveDateTime::new(date, time).assume_utc());
+    assert_eq!(json::to_string(&event), j);
+
+    let j = r#"{"at":"1996-12-19T16:39:57-08:00"}"#;
+    let event: Event = json::from_str(j).unwrap();
+    assert_eq!(event.at.offset(), UtcOffset::from_hms(-8, 0, 0).unwrap());
+    assert_eq!(json::to_string(&event), j);
+}
+
+#[test]
+fn test_rfc3339_invalid() {
+    for s in ["", "1985-04-12", "1985-04-12T23:20:50", "1985-13-12T23:20:50Z", "yesterday"] {
+        let j = format!(r#"{{"at":"{s}"}}"#);
+        assert!(json::from_str::<Event>(&j).is_err(), "{s}");
+    }
+    assert!(json::from_str::<Event>(r#"{"at":482196050}"#).is_err());
+}
+
+#[test]
+fn test_unrepresentable() {
+    // An offset with seconds is written as the same instant in UTC.
+    let offset = UtcOffset::from_hms(1, 0, 30).unwrap();
+    let at = OffsetDateTime::UNIX_EPOCH.to_offset(offset);
+    assert_eq!(json::to_string(&at), r#""1970-01-01T00:00:00Z""#);
+
+}
+
+#[test]
+fn test_expanded_year() {
+    let date = Date::from_calendar_date(-1, Month::January, 1).unwrap();
+    let at = PrimitiveDateTime::new(date, Time::MIDNIGHT).assume_utc();
+    let j = json::to_string(&at);
+    assert_eq!(j, r#""-000001-01-01T00:00:00Z""#);
+    assert_eq!(json::from_str::<OffsetDateTime>(&j).unwrap(), at);
+
+    let date = Date::from_calendar_date(-4, Month::February, 29).unwrap();
+    let time = Time::from_hms_milli(12, 30, 0, 250).unwrap();
+    let offset = UtcOffset::from_hms(5, 30, 0).unwrap();
+    let at = PrimitiveDateTime::new(date, time).assume_offset(offset);
+    let j = json::to_string(&at);
+    assert_eq!(j, r#""-000004-02-29T12:30:00.25+05:30""#);
+    assert_eq!(json::from_str::<OffsetDateTime>(&j).unwrap(), at);
+
+    let at: OffsetDateTime = json::from_str(r#""+001985-04-12T23:20:50.52Z""#).unwrap();
+    assert_eq!(json::to_string(&at), r#""1985-04-12T23:20:50.52Z""#);
+
+    for s in [
+        "-000100-02-29T00:00:00Z",
+        "-00001-01-01T00:00:00Z",
+        "+1985-04-12T23:20:50Z",
+        "-0000x1-01-01T00:00:00Z",
+        "-",
+    ] {
+        assert!(json::from_str::<OffsetDateTime>(&format!(r#""{s}""#)).is_err(), "{s}");
+    }
+}
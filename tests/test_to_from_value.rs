@@ -1,5 +1,7 @@
+use miniserde::json::{Number, Value};
+use miniserde::ser::Fragment;
 use miniserde::{json, Deserialize, Serialize};
-use miniserde::json::Value;
+use std::cell::Cell;
 
 #[derive(Serialize)]
 struct Example {
@@ -44,6 +46,80 @@ fn test_to_value_primitives() {
     assert!(matches!(string_value, Value::String(s) if s == "hello"));
 }
 
+/// An item that counts how many of its kind are alive at once.
+struct Tracked<'a> {
+    n: u64,
+    live: &'a Cell<usize>,
+}
+
+impl<'a> Tracked<'a> {
+    fn new(n: u64, live: &'a Cell<usize>, peak: &'a Cell<usize>) -> Self {
+        live.set(live.get() + 1);
+        peak.set(peak.get().max(live.get()));
+        Tracked { n, live }
+    }
+}
+
+impl Drop for Tracked<'_> {
+    fn drop(&mut self) {
+        self.live.set(self.live.get() - 1);
+    }
+}
+
+impl Serialize for Tracked<'_> {
+    fn begin(&self) -> Fragment<'_> {
+        Fragment::U64(self.n)
+    }
+}
+
+#[test]
+fn test_to_value_lazy_iter() {
+    let live = Cell::new(0);
+    let peak = Cell::new(0);
+    let items = json::iter((0..10_000).map(|n| Tracked::new(n, &live, &peak)));
+
+    let Value::Array(array) = json::to_value(&items) else {
+        panic!("expected Value::Array");
+    };
+    assert_eq!(array.len(), 10_000);
+    assert!(matches!(array[9_999], Value::Number(Number::U64(9_999))));
+    // The item being converted, plus at most the next one while it replaces it.
+    assert!(peak.get() <= 2);
+    assert_eq!(live.get(), 0);
+}
+
+#[derive(Serialize)]
+struct Envelope {
+    id: u32,
+    payload: Value,
+}
+
+#[test]
+fn test_to_value_of_value() {
+    let value: Value = json::from_str(r#"{"a":[1,-2,3.5,{"b":null}],"c":"d"}"#).unwrap();
+    assert_eq!(json::to_value(&value), value.clone());
+    assert_eq!(json::to_value(&&value), value);
+    assert_eq!(json::to_value(&Box::new(value.clone())), value);
+
+    let envelope = Envelope {
+        id: 1,
+        payload: value,
+    };
+    let expected: Value =
+        json::from_str(r#"{"id":1,"payload":{"a":[1,-2,3.5,{"b":null}],"c":"d"}}"#).unwrap();
+    assert_eq!(json::to_value(&envelope), expected);
+}
+
+#[test]
+fn test_clone_deeply_nested() {
+    let depth = if cfg!(miri) { 40 } else { 100_000 };
+    let j = "[{\"x\":".repeat(depth) + "null" + &"}]".repeat(depth);
+    let value: Value = json::from_str(&j).unwrap();
+    let cloned = value.clone();
+    assert_eq!(json::to_string(&cloned), j);
+    assert_eq!(json::to_string(&json::to_value(&value)), j);
+}
+
 #[test]
 fn test_from_value() {
     let value = Value::Object({
@@ -57,3 +133,11 @@ fn test_from_value() {
     assert_eq!(example.code, 200);
     assert_eq!(example.message, "reminiscent of Serde");
 }
+
+#[test]
+fn test_from_value_size_hint() {
+    let v: Vec<u32> = (0..1000).collect();
+    let out: Vec<u32> = json::from_value(json::to_value(&v)).unwrap();
+    assert_eq!(out, v);
+    assert_eq!(out.capacity(), 1000);
+}
@@ -1,7 +1,12 @@
-#![allow(clippy::uninlined_format_args)]
+#![allow(
+    clippy::approx_constant,
+    clippy::cast_sign_loss,
+    clippy::uninlined_format_args
+)]
 
 use indoc::indoc;
 use miniserde::json::{self, Value, Array, Number, Object};
+use std::collections::BTreeMap;
 
 #[test]
 fn test_round_trip_deeply_nested() {
@@ -54,6 +59,134 @@ fn test_debug() {
     assert_eq!(debug, expected);
 }
 
+#[test]
+fn test_display() {
+    let j = r#"{"Array":[true,{"k":"v"}],"EmptyArray":[],"EmptyObject":{},"Number":-1.5}"#;
+    let value: Value = json::from_str(j).unwrap();
+
+    assert_eq!(format!("{}", value), j);
+    assert_eq!(value.to_string(), j);
+    assert_eq!(format!("{:#}", value), json::to_string_pretty(&value));
+
+    let expected = indoc! {r#"
+        {
+          "Array": [
+            true,
+            {
+              "k": "v"
+            }
+          ],
+          "EmptyArray": [],
+          "EmptyObject": {},
+          "Number": -1.5
+        }"#
+    };
+    assert_eq!(format!("{:#}", value), expected);
+    assert_eq!(format!("{:#}", Value::Null), "null");
+}
+
+#[test]
+fn test_canonical_key_order() {
+    let j = r#"{"b":{"z":1,"a":[{"y":null,"x":true}],"é":0,"B":0},"a":"first","":0}"#;
+    let value: Value = json::from_str(j).unwrap();
+    let canonical = r#"{"":0,"a":"first","b":{"B":0,"a":[{"x":true,"y":null}],"z":1,"é":0}}"#;
+    assert_eq!(json::to_string(&value), canonical);
+
+    let mut object = Object::new();
+    object.insert("b".to_owned(), Value::Null);
+    object.insert("a".to_owned(), Value::Null);
+    let keys: Vec<&str> = object.keys().map(String::as_str).collect();
+    assert_eq!(keys, ["a", "b"]);
+}
+
+#[test]
+fn test_deep_merge() {
+    let base = r#"{"a":{"x":1,"list":[1,2]},"b":[1],"c":{"d":true},"e":null}"#;
+    let patch = r#"{"a":{"y":2,"list":[3]},"b":{"k":0},"c":[],"e":"set","f":null}"#;
+
+    let mut value: Value = json::from_str(base).unwrap();
+    value.deep_merge(json::from_str(patch).unwrap(), false);
+    assert_eq!(
+        json::to_string(&value),
+        r#"{"a":{"list":[3],"x":1,"y":2},"b":{"k":0},"c":[],"e":"set","f":null}"#,
+    );
+
+    let mut value: Value = json::from_str(base).unwrap();
+    value.deep_merge(json::from_str(patch).unwrap(), true);
+    assert_eq!(
+        json::to_string(&value),
+        r#"{"a":{"list":[1,2,3],"x":1,"y":2},"b":{"k":0},"c":[],"e":"set","f":null}"#,
+    );
+
+    // Arrays are concatenated as a whole; their elements are not merged.
+    let mut value: Value = json::from_str(r#"[{"a":1}]"#).unwrap();
+    value.deep_merge(json::from_str(r#"[{"b":2}]"#).unwrap(), true);
+    assert_eq!(json::to_string(&value), r#"[{"a":1},{"b":2}]"#);
+
+    let mut value = Value::Bool(true);
+    value.deep_merge(json::from_str(r#"{"a":1}"#).unwrap(), true);
+    assert_eq!(json::to_string(&value), r#"{"a":1}"#);
+}
+
+#[test]
+fn test_deep_merge_deeply_nested() {
+    let depth = if cfg!(miri) { 40 } else { 100_000 };
+    let mut base = Value::Null;
+    let mut patch = Value::Null;
+    for _ in 0..depth {
+        let mut object = Object::new();
+        object.insert("n".to_owned(), base);
+        base = Value::Object(object);
+        let mut object = Object::new();
+        object.insert("n".to_owned(), patch);
+        patch = Value::Object(object);
+    }
+    base.deep_merge(patch, false);
+}
+
+#[test]
+fn test_eq_and_loose_eq() {
+    let one = Value::Number(Number::U64(1));
+    let float = Value::Number(Number::F64(1.0));
+    let signed = Value::Number(Number::I64(1));
+    assert_eq!(one, one.clone());
+    assert_ne!(one, float);
+    assert_ne!(one, signed);
+    assert!(one.loose_eq(&float));
+    assert!(one.loose_eq(&signed));
+    assert!(float.loose_eq(&signed));
+
+    let a: Value = json::from_str(r#"{"x":[1,{"y":-2}],"z":null}"#).unwrap();
+    let b: Value = json::from_str(r#"{"x":[1.0,{"y":-2e0}],"z":null}"#).unwrap();
+    assert_ne!(a, b);
+    assert!(a.loose_eq(&b));
+
+    let c: Value = json::from_str(r#"{"x":[1.5,{"y":-2}],"z":null}"#).unwrap();
+    assert!(!a.loose_eq(&c));
+    let d: Value = json::from_str(r#"{"x":[1,{"y":-2}],"w":null}"#).unwrap();
+    assert!(!a.loose_eq(&d));
+
+    // Equal as f64, but not the same number.
+    let big = Value::Number(Number::U64(9_007_199_254_740_993));
+    let rounded = Value::Number(Number::F64(9_007_199_254_740_992.0));
+    assert!(!big.loose_eq(&rounded));
+
+    let nan = Value::Number(Number::F64(f64::NAN));
+    assert_ne!(nan, nan);
+    assert!(!nan.loose_eq(&nan));
+    assert!(Value::Number(Number::F64(-0.0)).loose_eq(&Value::Number(Number::U64(0))));
+}
+
+#[test]
+fn test_eq_deeply_nested() {
+    let depth = if cfg!(miri) { 40 } else { 100_000 };
+    let j = "[".repeat(depth) + &"]".repeat(depth);
+    let a: Value = json::from_str(&j).unwrap();
+    let b: Value = json::from_str(&j).unwrap();
+    assert_eq!(a, b);
+    assert!(a.loose_eq(&b));
+}
+
 #[test]
 fn test_indexing() {
     use miniserde::json::{Array, Object};
@@ -194,6 +327,18 @@ fn test_indexing() {
     }
 }
 
+#[test]
+fn test_as_null() {
+    let value: Value = json::from_str(r#"{"a":null,"b":false,"c":[],"d":{},"e":0,"f":""}"#).unwrap();
+    assert_eq!(value["a"].as_null(), Some(()));
+    for key in ["b", "c", "d", "e", "f"] {
+        assert_eq!(value[key].as_null(), None, "{key}");
+    }
+    assert_eq!(value.as_null(), None);
+    // Indexing a missing key gives Null too.
+    assert_eq!(value["missing"].as_null(), Some(()));
+}
+
 #[test]
 fn test_accessor_methods() {
     // Test as_bool method
@@ -366,4 +511,196 @@ fn test_accessor_methods_edge_cases() {
     let object_val = Value::Object(object);
     let object_ref = object_val.as_object().unwrap();
     assert_eq!(object_ref.len(), 1);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_flatten_unflatten() {
+    let j = indoc! {r#"
+        {
+          "name": "server",
+          "port": 8080,
+          "debug": false,
+          "owner": null,
+          "tls": {
+            "cert": "a.pem",
+            "ciphers": ["aes", "chacha"]
+          },
+          "listeners": [
+            {"host": "localhost", "weight": 1.5},
+            {"host": "example.com", "tags": []}
+          ],
+          "extra": {}
+        }
+    "#};
+    let value: Value = json::from_str(j).unwrap();
+
+    let map = value.flatten_to_map();
+    let expected = [
+        ("debug", "false"),
+        ("extra", "{}"),
+        ("listeners.0.host", "localhost"),
+        ("listeners.0.weight", "1.5"),
+        ("listeners.1.host", "example.com"),
+        ("listeners.1.tags", "[]"),
+        ("name", "server"),
+        ("owner", "null"),
+        ("port", "8080"),
+        ("tls.cert", "a.pem"),
+        ("tls.ciphers.0", "aes"),
+        ("tls.ciphers.1", "chacha"),
+    ];
+    let actual: Vec<(&str, &str)> = map.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+    assert_eq!(actual, expected);
+
+    let rebuilt = Value::unflatten(map).unwrap();
+    assert_eq!(json::to_string(&rebuilt), json::to_string(&value));
+}
+
+#[test]
+fn test_unflatten_array_indices() {
+    let map = (0..12)
+        .map(|i| (format!("a.{}", i), i.to_string()))
+        .collect();
+    let value = Value::unflatten(map).unwrap();
+    assert_eq!(
+        json::to_string(&value),
+        r#"{"a":[0,1,2,3,4,5,6,7,8,9,10,11]}"#,
+    );
+}
+
+#[test]
+fn test_flatten_scalar() {
+    let map = Value::String("x".to_owned()).flatten_to_map();
+    assert_eq!(map.len(), 1);
+    assert_eq!(map[""], "x");
+    assert_eq!(json::to_string(&Value::unflatten(map).unwrap()), r#""x""#);
+}
+
+#[test]
+fn test_unflatten_index_out_of_range() {
+    for path in ["a.1", "a.4000000000", "a.18446744073709551615", "a.99999999999999999999"] {
+        let map = BTreeMap::from([(path.to_owned(), "x".to_owned())]);
+        assert!(Value::unflatten(map).is_err(), "{}", path);
+    }
+
+    // Holes below the number of entries are filled with null.
+    let map = BTreeMap::from([
+        ("a.2".to_owned(), "x".to_owned()),
+        ("b".to_owned(), "y".to_owned()),
+        ("c".to_owned(), "z".to_owned()),
+    ]);
+    let value = Value::unflatten(map).unwrap();
+    assert_eq!(json::to_string(&value), r#"{"a":[null,null,"x"],"b":"y","c":"z"}"#);
+}
+
+#[test]
+fn test_object_entry() {
+    fn increment(object: &mut Object, key: &str) {
+        object
+            .entry(key.to_owned())
+            .and_modify(|count| {
+                if let Value::Number(Number::U64(n)) = count {
+                    *n += 1;
+                }
+            })
+            .or_insert(Value::Number(Number::U64(1)));
+    }
+
+    let mut object = Object::new();
+    increment(&mut object, "a");
+    increment(&mut object, "b");
+    increment(&mut object, "a");
+    assert_eq!(json::to_string(&object), r#"{"a":2,"b":1}"#);
+
+    let value = object
+        .entry("c".to_owned())
+        .or_insert_with(|| Value::String("new".to_owned()));
+    assert_eq!(value.as_str(), Some("new"));
+}
+
+#[test]
+fn test_remove() {
+    let mut value: Value = json::from_str(r#"{"a":[1,2,3],"b":null}"#).unwrap();
+    let Value::Object(object) = &mut value else {
+        unreachable!();
+    };
+    assert!(object.contains_key("b"));
+    assert!(matches!(object.remove("b"), Some(Value::Null)));
+    assert!(!object.contains_key("b"));
+    assert!(object.remove("b").is_none());
+    assert!(object.remove("missing").is_none());
+
+    let Some(Value::Array(array)) = object.get_mut("a") else {
+        unreachable!();
+    };
+    assert!(matches!(array.remove(1), Value::Number(Number::U64(2))));
+    assert_eq!(json::to_string(&value), r#"{"a":[1,3]}"#);
+}
+
+#[test]
+#[should_panic(expected = "removal index")]
+fn test_array_remove_out_of_bounds() {
+    let mut array = Array::new();
+    array.push(Value::Null);
+    array.remove(1);
+}
+
+#[test]
+fn test_array_with_capacity() {
+    let mut array = Array::with_capacity(10);
+    assert!(array.is_empty());
+    assert!(array.capacity() >= 10);
+    let ptr = array.as_ptr();
+    for _ in 0..10 {
+        array.push(Value::Null);
+    }
+    assert_eq!(array.as_ptr(), ptr);
+}
+
+#[test]
+fn test_iterators() {
+    let j = r#"{
+        "users": [
+            {"name": "Alice", "settings": {"theme": "dark"}},
+            {"name": "Bob", "settings": {"theme": "light"}}
+        ],
+        "config": {"debug": true}
+    }"#;
+    let mut value: Value = json::from_str(j).unwrap();
+
+    let object = value.as_object().unwrap();
+    assert_eq!(object.keys().collect::<Vec<_>>(), ["config", "users"]);
+    assert_eq!(object.values().count(), 2);
+    let first = object.iter().next().unwrap();
+    assert_eq!(first.0, "config");
+    assert_eq!(object["users"].as_array().unwrap().iter().count(), 2);
+
+    let mut themes = Vec::new();
+    for (key, value) in object {
+        if key == "users" {
+            for user in value.as_array().unwrap() {
+                let settings = user["settings"].as_object().unwrap();
+                themes.extend(settings.values().filter_map(Value::as_str));
+            }
+        }
+    }
+    assert_eq!(themes, ["dark", "light"]);
+
+    let Value::Object(object) = &mut value else {
+        unreachable!();
+    };
+    for (_key, value) in &mut *object {
+        if let Value::Array(users) = value {
+            for user in users {
+                *user = Value::Null;
+            }
+        }
+    }
+    if let Some((_key, Value::Object(config))) = object.iter_mut().next() {
+        config.clear();
+    }
+    assert_eq!(
+        json::to_string(&value),
+        r#"{"config":{},"users":[null,null]}"#,
+    );
+}
@@ -0,0 +1,168 @@
+use miniserde::with::base64::{self, Base64};
+use miniserde::with::{iso8601_duration, lenient_bool, rectangular};
+use miniserde::{json, Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Blob {
+    name: String,
+    #[serde(with = "base64")]
+    data: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Flag {
+    #[serde(with = "lenient_bool")]
+    lenient: bool,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Matrix {
+    #[serde(with = "rectangular")]
+    rows: Vec<Vec<f64>>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Timeout {
+    #[serde(with = "iso8601_duration")]
+    after: Duration,
+}
+
+#[test]
+fn test_iso8601_duration_round_trip() {
+    let cases = [
+        (Duration::from_millis(1500), "PT1.5S"),
+        (Duration::ZERO, "PT0S"),
+        (Duration::from_secs(90), "PT90S"),
+        (Duration::new(3, 1), "PT3.000000001S"),
+        (Duration::from_micros(250), "PT0.00025S"),
+        (Duration::MAX, "PT18446744073709551615.999999999S"),
+    ];
+    for (after, encoded) in cases {
+        let timeout = Timeout { after };
+        let j = json::to_string(&timeout);
+        assert_eq!(j, format!(r#"{{"after":"{encoded}"}}"#));
+        assert_eq!(json::from_str::<Timeout>(&j).unwrap(), timeout);
+    }
+}
+
+#[test]
+fn test_iso8601_duration_parts() {
+    let cases = [
+        ("P1D", Duration::from_secs(86400)),
+        ("PT2H", Duration::from_secs(7200)),
+        ("PT3M", Duration::from_secs(180)),
+        ("P1DT2H3M4.5S", Duration::from_millis(93_784_500)),
+        ("PT1H0.25S", Duration::from_millis(3_600_250)),
+        ("PT0.100S", Duration::from_millis(100)),
+    ];
+    for (encoded, after) in cases {
+        let j = format!(r#"{{"after":"{encoded}"}}"#);
+        assert_eq!(json::from_str::<Timeout>(&j).unwrap().after, after, "{encoded}");
+    }
+
+    for encoded in [
+        "", "P", "PT", "P1DT", "1S", "PT1", "PT-1S", "P1Y", "P1W", "PT1M1H", "PT.5S", "PT1.S",
+        "PT1.0000000001S", "PT1,5S", "pt1s", "PT18446744073709551616S", "P213503982334602D",
+    ] {
+        let j = format!(r#"{{"after":"{encoded}"}}"#);
+        assert!(json::from_str::<Timeout>(&j).is_err(), "{encoded}");
+    }
+    assert!(json::from_str::<Timeout>(r#"{"after":1.5}"#).is_err());
+}
+
+#[test]
+fn test_base64_round_trip() {
+    let cases: &[(&[u8], &str)] = &[
+        (b"", ""),
+        (b"f", "Zg=="),
+        (b"fo", "Zm8="),
+        (b"foo", "Zm9v"),
+        (b"foob", "Zm9vYg=="),
+        (b"fooba", "Zm9vYmE="),
+        (b"foobar", "Zm9vYmFy"),
+        (&[0x00, 0xFF, 0xFE, 0x80, 0x7F], "AP/+gH8="),
+    ];
+
+    for &(data, encoded) in cases {
+        let blob = Blob {
+            name: "x".to_owned(),
+            data: data.to_vec(),
+        };
+        let j = json::to_string(&blob);
+        assert_eq!(j, format!(r#"{{"name":"x","data":"{encoded}"}}"#));
+        assert_eq!(json::from_str::<Blob>(&j).unwrap(), blob);
+    }
+
+    let all: Vec<u8> = (0..=255).collect();
+    let blob = Blob {
+        name: String::new(),
+        data: all,
+    };
+    let j = json::to_string(&blob);
+    assert_eq!(json::from_str::<Blob>(&j).unwrap(), blob);
+}
+
+#[test]
+fn test_base64_invalid() {
+    for encoded in ["Zg", "Zg=", "Z===", "Zg==Zg==", "Zh==", "Zm9=", "Zm9v!A==", "Zm 9v"] {
+        let j = format!(r#"{{"name":"x","data":"{encoded}"}}"#);
+        assert!(json::from_str::<Blob>(&j).is_err(), "{encoded}");
+    }
+    assert!(json::from_str::<Blob>(r#"{"name":"x","data":[1,2]}"#).is_err());
+    assert!(json::from_str::<Blob>(r#"{"name":"x"}"#).is_err());
+}
+
+#[test]
+fn test_rectangular() {
+    let j = r#"{"rows":[[1.0,2.0,3.0],[4.0,5.0,6.0]]}"#;
+    let matrix: Matrix = json::from_str(j).unwrap();
+    assert_eq!(matrix.rows, [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    assert_eq!(json::to_string(&matrix), j);
+
+    for j in [r#"{"rows":[]}"#, r#"{"rows":[[],[]]}"#] {
+        json::from_str::<Matrix>(j).unwrap();
+    }
+
+    for j in [
+        r#"{"rows":[[1.0,2.0],[3.0]]}"#,
+        r#"{"rows":[[1.0],[2.0],[3.0,4.0]]}"#,
+        r#"{"rows":[[],[1.0]]}"#,
+    ] {
+        assert!(json::from_str::<Matrix>(j).is_err(), "{j}");
+    }
+}
+
+#[test]
+fn test_bytes() {
+    let bytes: &[u8] = &[0, 127, 128, 255];
+    assert_eq!(json::to_string(bytes), "[0,127,128,255]");
+    assert_eq!(json::to_string(&Base64(bytes)), r#""AH+A/w==""#);
+}
+
+#[test]
+fn test_lenient_bool() {
+    let cases = [
+        ("true", true),
+        ("false", false),
+        ("1", true),
+        ("0", false),
+        (r#""true""#, true),
+        (r#""false""#, false),
+        (r#""yes""#, true),
+        (r#""no""#, false),
+    ];
+    for (j, expected) in cases {
+        let flag: Flag = json::from_str(&format!(r#"{{"lenient":{j}}}"#)).unwrap();
+        assert_eq!(flag.lenient, expected, "{j}");
+        assert_eq!(json::to_string(&flag), format!(r#"{{"lenient":{expected}}}"#));
+
+        let strict = json::from_str::<bool>(j);
+        assert_eq!(strict.is_ok(), j == "true" || j == "false", "{j}");
+    }
+
+    for j in ["2", "-1", "1.0", r#""Yes""#, r#""""#, "null"] {
+        let result = json::from_str::<Flag>(&format!(r#"{{"lenient":{j}}}"#));
+        assert!(result.is_err(), "{j}");
+    }
+}
@@ -0,0 +1,21 @@
+use miniserde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+struct Skip {
+    #[serde(skip_serializing_if = "|v| v.is_empty()")]
+    x: Vec<i32>,
+}
+
+#[derive(Deserialize)]
+struct Default {
+    #[serde(default = "1 + 1")]
+    x: i32,
+}
+
+#[derive(Serialize)]
+struct With {
+    #[serde(with = "")]
+    x: i32,
+}
+
+fn main() {}
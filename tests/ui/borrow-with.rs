@@ -0,0 +1,11 @@
+use miniserde::Deserialize;
+
+mod name {}
+
+#[derive(Deserialize)]
+struct Person<'a> {
+    #[serde(borrow, with = "name")]
+    name: &'a str,
+}
+
+fn main() {}
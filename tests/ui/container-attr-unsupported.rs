@@ -0,0 +1,15 @@
+use miniserde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Renamed {
+    user_id: i32,
+}
+
+#[derive(Deserialize)]
+#[serde(transparent)]
+struct Transparent {
+    x: i32,
+}
+
+fn main() {}
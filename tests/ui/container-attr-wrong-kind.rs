@@ -0,0 +1,53 @@
+use miniserde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+#[serde(tag = "type")]
+struct Tagged {
+    x: i32,
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+enum Defaulted {
+    A,
+    B,
+}
+
+#[derive(Deserialize)]
+#[serde(name_or_code)]
+struct Coded {
+    x: i32,
+}
+
+#[derive(Deserialize)]
+#[serde(name_or_code)]
+enum CodedData {
+    A,
+    B(i32),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Tuple(i32);
+
+#[derive(Serialize)]
+#[serde(map_key)]
+struct Keyed {
+    x: i32,
+}
+
+#[derive(Deserialize)]
+#[serde(map_key)]
+enum KeyedData {
+    A,
+    B(i32),
+}
+
+#[derive(Serialize)]
+#[serde(rename_all_fields = "UPPERCASE")]
+enum Untagged {
+    A(i32),
+    B,
+}
+
+fn main() {}
@@ -2,7 +2,9 @@ use miniserde::Deserialize;
 
 #[derive(Deserialize)]
 enum Enum {
-    Variant(i32)
+    Unit,
+    Newtype(i32),
+    Tuple(i32, i32),
 }
 
 fn main() {}
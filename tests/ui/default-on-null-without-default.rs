@@ -1,6 +1,9 @@
 use miniserde::Deserialize;
 
 #[derive(Deserialize)]
-struct UnitStruct;
+struct Config {
+    #[serde(default_on_null)]
+    retries: u32,
+}
 
 fn main() {}
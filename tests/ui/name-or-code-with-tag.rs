@@ -1,6 +1,10 @@
 use miniserde::Deserialize;
 
 #[derive(Deserialize)]
-struct TupleStruct(i32, i32);
+#[serde(tag = "type", name_or_code)]
+enum Enum {
+    A,
+    B,
+}
 
 fn main() {}
@@ -2,7 +2,9 @@ use miniserde::Serialize;
 
 #[derive(Serialize)]
 enum Enum {
-    Variant(i32)
+    Unit,
+    Newtype(i32),
+    Tuple(i32, i32),
 }
 
 fn main() {}
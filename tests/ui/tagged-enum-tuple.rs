@@ -0,0 +1,10 @@
+use miniserde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum Enum {
+    Struct { x: i32 },
+    Tuple(i32),
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use miniserde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct TupleStruct(#[serde(rename = "x")] i32, i32);
+
+fn main() {}
@@ -0,0 +1,18 @@
+use miniserde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+enum Untagged {
+    #[serde(rename_all = "camelCase")]
+    A(i32),
+    B,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+enum Tagged {
+    #[serde(rename_all = "camelCase")]
+    A,
+    B { some_field: i32 },
+}
+
+fn main() {}